@@ -0,0 +1,66 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use rou3::{ArcRouter, Router, add_route, add_route_arc, find_route, find_route_arc};
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+const THREADS: usize = 16;
+const LOOKUPS_PER_THREAD: usize = 1_000;
+const ROUTES: usize = 200;
+
+fn pattern(i: usize) -> String {
+    format!("/api/resource{}/:id/items/:item", i)
+}
+
+fn path(i: usize) -> String {
+    format!("/api/resource{}/42/items/7", i % ROUTES)
+}
+
+/// Runs `lookup` for `LOOKUPS_PER_THREAD` paths on each of `THREADS` threads at once,
+/// `iters` times, and returns the total wall clock time.
+fn run_concurrent(iters: u64, lookup: &(impl Fn(&str) + Sync)) -> Duration {
+    let paths: Vec<String> = (0..LOOKUPS_PER_THREAD).map(path).collect();
+    let start = Instant::now();
+    for _ in 0..iters {
+        std::thread::scope(|scope| {
+            for _ in 0..THREADS {
+                scope.spawn(|| {
+                    for path in &paths {
+                        lookup(path);
+                    }
+                });
+            }
+        });
+    }
+    start.elapsed()
+}
+
+fn bench_concurrent_reads(c: &mut Criterion) {
+    let router = Router::new();
+    let arc_router = ArcRouter::new();
+    for i in 0..ROUTES {
+        add_route(&router, "GET", &pattern(i), i).unwrap();
+        add_route_arc(&arc_router, "GET", &pattern(i), i).unwrap();
+    }
+
+    let mut group = c.benchmark_group("concurrent_reads_16_threads");
+    group.bench_function("rwlock_router", |b| {
+        b.iter_custom(|iters| {
+            run_concurrent(iters, &|path| {
+                black_box(find_route(&router, "GET", path, true).unwrap());
+            })
+        })
+    });
+    group.bench_function("arc_router", |b| {
+        b.iter_custom(|iters| {
+            run_concurrent(iters, &|path| {
+                black_box(find_route_arc(&arc_router, "GET", path, true).unwrap());
+            })
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_concurrent_reads);
+criterion_main!(benches);
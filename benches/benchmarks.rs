@@ -1,7 +1,18 @@
 use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
-use rou3::{Router, add_route, find_all_routes, find_route};
+use rou3::{
+    ExactRouter, NormalizedPath, Router, add_route, add_static, compress, find_all_routes,
+    find_route, find_route_best, find_route_normalized, find_route_segments, find_routes_batch,
+    find_static,
+};
 use std::hint::black_box;
 
+/// The hash map backing the routing tree, used to tell `fxhash` and `ahash` runs apart.
+const HASH_MAP_BACKEND: &str = if cfg!(feature = "fxhash") {
+    "fxhash"
+} else {
+    "ahash"
+};
+
 fn bench_build_router_with_various_routes(c: &mut Criterion) {
     let mut group = c.benchmark_group("build_router");
     for &size in &[100usize, 1_000, 5_000] {
@@ -24,15 +35,21 @@ fn bench_build_router_with_various_routes(c: &mut Criterion) {
     group.finish();
 }
 
-fn bench_lookup_routes(c: &mut Criterion) {
-    let mut group = c.benchmark_group("lookup_routes");
+fn setup_lookup_router() -> Router<usize> {
     let size = 5_000;
     let router = Router::new();
     for i in 0..size {
         add_route(&router, "GET", &format!("/static/{}", i), i).unwrap();
         add_route(&router, "GET", &format!("/user/:id{}", i), i).unwrap();
         add_route(&router, "GET", &format!("/files/{}/docs/**:path", i), i).unwrap();
+        add_route(&router, "GET", &format!("/nested/{}/leaf/:id", i), i).unwrap();
     }
+    router
+}
+
+fn bench_lookup_routes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lookup_routes");
+    let router = setup_lookup_router();
 
     group.bench_function("lookup_static_last", |b| {
         b.iter(|| {
@@ -52,6 +69,28 @@ fn bench_lookup_routes(c: &mut Criterion) {
             black_box(find_route(&router, "GET", "/files/4999/docs/a/b/c.txt", true).unwrap());
         })
     });
+
+    // Only static routes are in the static map; this one is found through a node
+    // with 5000 static children.
+    group.bench_function("lookup_static_child_last", |b| {
+        b.iter(|| {
+            black_box(find_route(&router, "GET", "/nested/4999/leaf/1", false).unwrap());
+        })
+    });
+
+    let static_last = NormalizedPath::new("/static/4999");
+    group.bench_function("lookup_static_last_normalized", |b| {
+        b.iter(|| {
+            black_box(find_route_normalized(&router, "GET", &static_last, false).unwrap());
+        })
+    });
+
+    let param_last = NormalizedPath::new("/user/somevalue4999");
+    group.bench_function("lookup_param_last_normalized", |b| {
+        b.iter(|| {
+            black_box(find_route_normalized(&router, "GET", &param_last, true).unwrap());
+        })
+    });
     group.finish();
 }
 
@@ -169,6 +208,21 @@ fn bench_api_style_lookups(c: &mut Criterion) {
             )
         })
     });
+    let pre_split_post_path = ["api", "v1", "users", "user123abc", "posts", "post789xyz"];
+    group.bench_function("get_post_specific_pre_split", |b| {
+        b.iter(|| {
+            black_box(
+                find_route_segments(
+                    &router,
+                    "GET",
+                    black_box(&pre_split_post_path),
+                    "/api/v1/users/user123abc/posts/post789xyz",
+                    true,
+                )
+                .unwrap(),
+            )
+        })
+    });
     group.bench_function("serve_file_wildcard", |b| {
         b.iter(|| {
             black_box(find_route(&router, "GET", "/api/v1/files/docs/report.pdf", true).unwrap())
@@ -195,11 +249,174 @@ fn bench_api_style_lookups(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_batch_lookups(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch_lookups_16_threads");
+    let router = Router::new();
+    for i in 0..200 {
+        add_route(&router, "GET", &format!("/static/{}", i), i).unwrap();
+        add_route(&router, "GET", &format!("/users/:id/items/{}", i), i).unwrap();
+    }
+    add_route(&router, "GET", "/files/**:path", 0).unwrap();
+    let paths: Vec<String> = (0..64)
+        .map(|i| match i % 3 {
+            0 => format!("/static/{}", i),
+            1 => format!("/users/user{}/items/{}", i, i),
+            _ => format!("/files/docs/{}.txt", i),
+        })
+        .collect();
+    let requests: Vec<(&str, &str)> = paths.iter().map(|p| ("GET", p.as_str())).collect();
+
+    group.bench_function("sequential_find_route", |b| {
+        b.iter(|| {
+            std::thread::scope(|s| {
+                for _ in 0..16 {
+                    s.spawn(|| {
+                        for &(method, path) in &requests {
+                            black_box(find_route(&router, method, path, true).unwrap());
+                        }
+                    });
+                }
+            })
+        })
+    });
+    group.bench_function("find_routes_batch", |b| {
+        b.iter(|| {
+            std::thread::scope(|s| {
+                for _ in 0..16 {
+                    s.spawn(|| black_box(find_routes_batch(&router, &requests, true)));
+                }
+            })
+        })
+    });
+    group.finish();
+}
+
+fn bench_exact_router(c: &mut Criterion) {
+    let mut group = c.benchmark_group("static_lookups_10000");
+    let size = 10_000;
+    let router = Router::new();
+    let exact_router = ExactRouter::new();
+    for i in 0..size {
+        let path = format!("/static/{}/page", i);
+        add_route(&router, "GET", &path, i).unwrap();
+        add_static(&exact_router, "GET", &path, i).unwrap();
+    }
+    let paths: Vec<String> = (0..size).map(|i| format!("/static/{}/page", i)).collect();
+
+    group.bench_function("router_find_route", |b| {
+        b.iter(|| {
+            for path in &paths {
+                black_box(find_route(&router, "GET", path, false).unwrap());
+            }
+        })
+    });
+    group.bench_function("exact_router_find_static", |b| {
+        b.iter(|| {
+            for path in &paths {
+                black_box(find_static(&exact_router, "GET", path).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_compressed_trie(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deep_static_prefix_lookups");
+    let size = 1_000;
+    // A trailing parameter keeps these out of the static path map, so lookups walk the trie.
+    for (name, pattern, path) in [
+        ("", "/svc{}/a/b/c/d/e/f/:id", "/svc{}/a/b/c/d/e/f/42"),
+        (
+            "api_settings/",
+            "/api{}/v1/users/settings/notifications/:channel",
+            "/api{}/v1/users/settings/notifications/email",
+        ),
+    ] {
+        let build = || {
+            let router = Router::new();
+            for i in 0..size {
+                add_route(&router, "GET", &pattern.replace("{}", &i.to_string()), i).unwrap();
+            }
+            router
+        };
+        let uncompressed = build();
+        let compressed = build();
+        compress(&compressed);
+        let paths: Vec<String> = (0..size)
+            .map(|i| path.replace("{}", &i.to_string()))
+            .collect();
+
+        group.bench_function(format!("{name}uncompressed"), |b| {
+            b.iter(|| {
+                for path in &paths {
+                    black_box(find_route(&uncompressed, "GET", path, false).unwrap());
+                }
+            })
+        });
+        group.bench_function(format!("{name}compressed"), |b| {
+            b.iter(|| {
+                for path in &paths {
+                    black_box(find_route(&compressed, "GET", path, false).unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Runs the `lookup_routes` workload under an id naming the hash map backend.
+///
+/// Compare the two backends with `cargo bench --bench benchmarks -- hash_map_backend`
+/// followed by the same command with `--features fxhash`; the router comparison
+/// (`cargo bench --bench router_comparison`) likewise reports `rou3 (fxhash)`.
+fn bench_hash_map_backend(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hash_map_backend");
+    let router = setup_lookup_router();
+    for (name, path, capture) in [
+        ("lookup_static_last", "/static/4999", false),
+        ("lookup_param_last", "/user/somevalue4999", true),
+        ("lookup_wildcard_last", "/files/4999/docs/a/b/c.txt", true),
+    ] {
+        group.bench_function(format!("{HASH_MAP_BACKEND}/{name}"), |b| {
+            b.iter(|| black_box(find_route(&router, "GET", black_box(path), capture).unwrap()))
+        });
+    }
+    group.bench_function(format!("{HASH_MAP_BACKEND}/build_5000"), |b| {
+        b.iter(|| black_box(setup_lookup_router()))
+    });
+    group.finish();
+}
+
+/// Measures what `find_route_best` costs over `find_route` on paths where both pick
+/// the same route: the extra time is the `find_all_routes` search and selection.
+fn bench_best_match(c: &mut Criterion) {
+    let mut group = c.benchmark_group("best_match");
+    let router = setup_lookup_router();
+    for (name, path) in [
+        ("static", "/static/4999"),
+        ("param", "/nested/4999/leaf/42"),
+        ("wildcard", "/files/4999/docs/a/b/c.txt"),
+    ] {
+        group.bench_function(format!("find_route/{name}"), |b| {
+            b.iter(|| black_box(find_route(&router, "GET", black_box(path), true).unwrap()))
+        });
+        group.bench_function(format!("find_route_best/{name}"), |b| {
+            b.iter(|| black_box(find_route_best(&router, "GET", black_box(path), true).unwrap()))
+        });
+    }
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_build_router_with_various_routes,
     bench_lookup_routes,
     bench_find_all_matching_routes,
-    bench_api_style_lookups
+    bench_api_style_lookups,
+    bench_batch_lookups,
+    bench_exact_router,
+    bench_compressed_trie,
+    bench_hash_map_backend,
+    bench_best_match
 );
 criterion_main!(benches);
@@ -1,8 +1,10 @@
 use iai_callgrind::{
-    EventKind, FlamegraphConfig, LibraryBenchmarkConfig, RegressionConfig, Tool, ValgrindTool,
-    library_benchmark, library_benchmark_group, main as iai_main,
+    Bbv, Callgrind, Dhat, Drd, EventKind, FlamegraphConfig, Helgrind, LibraryBenchmarkConfig,
+    Massif, Memcheck, library_benchmark, library_benchmark_group, main as iai_main,
+};
+use rou3::{
+    Router, add_route, find_all_routes, find_route, find_route_borrowed, find_route_segments,
 };
-use rou3::{Router, add_route, find_all_routes, find_route};
 use std::hint::black_box;
 
 fn setup_large_router_for_lookups() -> Router<usize> {
@@ -148,6 +150,54 @@ pub fn bench_add_many_routes_iai_fn() {
     black_box(router);
 }
 
+// The router is built in the benchmark arguments so the DHAT allocation counts only
+// cover the lookup itself.
+const USER_POST_SEGMENTS: [&str; 6] = ["api", "v1", "users", "user123abc", "posts", "post789xyz"];
+
+#[library_benchmark]
+#[bench::user_post(setup_api_style_router())]
+pub fn bench_find_route_user_post_iai_fn(router: Router<&'static str>) {
+    black_box(
+        find_route(
+            &router,
+            "GET",
+            "/api/v1/users/user123abc/posts/post789xyz",
+            true,
+        )
+        .unwrap(),
+    );
+}
+
+#[library_benchmark]
+#[bench::user_post(setup_api_style_router())]
+pub fn bench_find_route_segments_owned_params_iai_fn(router: Router<&'static str>) {
+    black_box(
+        find_route_segments(
+            &router,
+            "GET",
+            &USER_POST_SEGMENTS,
+            "api/v1/users/user123abc/posts/post789xyz",
+            true,
+        )
+        .unwrap(),
+    );
+}
+
+#[library_benchmark]
+#[bench::user_post(setup_api_style_router())]
+pub fn bench_find_route_borrowed_params_iai_fn(router: Router<&'static str>) {
+    black_box(
+        find_route_borrowed(
+            &router,
+            "GET",
+            &USER_POST_SEGMENTS,
+            "api/v1/users/user123abc/posts/post789xyz",
+            true,
+        )
+        .unwrap(),
+    );
+}
+
 library_benchmark_group!(
     name = all_iai_benchmarks;
     benchmarks =
@@ -159,21 +209,24 @@ library_benchmark_group!(
         bench_api_search_optional_absent_iai_fn,
         bench_find_all_match_path_medium_router_iai_fn,
         bench_find_all_match_wildcard_path_medium_router_iai_fn,
-        bench_add_many_routes_iai_fn
+        bench_add_many_routes_iai_fn,
+        bench_find_route_user_post_iai_fn,
+        bench_find_route_segments_owned_params_iai_fn,
+        bench_find_route_borrowed_params_iai_fn
 );
 
 iai_main!(
     config = LibraryBenchmarkConfig::default()
-    .tool(Tool::new(ValgrindTool::DHAT))
-    .tool(Tool::new(ValgrindTool::Massif))
-    .tool(Tool::new(ValgrindTool::BBV))
-    .tool(Tool::new(ValgrindTool::Memcheck))
-    .tool(Tool::new(ValgrindTool::Helgrind))
-    .tool(Tool::new(ValgrindTool::DRD))
-    .flamegraph(FlamegraphConfig::default())
-    .regression(
-        RegressionConfig::default()
-        .limits([(EventKind::Ir, 5.0)])
+    .tool(Dhat::default())
+    .tool(Massif::default())
+    .tool(Bbv::default())
+    .tool(Memcheck::default())
+    .tool(Helgrind::default())
+    .tool(Drd::default())
+    .tool(
+        Callgrind::default()
+        .flamegraph(FlamegraphConfig::default())
+        .soft_limits([(EventKind::Ir, 5.0)])
     );
     library_benchmark_groups = all_iai_benchmarks
 );
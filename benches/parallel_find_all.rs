@@ -0,0 +1,32 @@
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use rou3::{Router, add_route, find_all_routes, find_all_routes_parallel};
+use std::hint::black_box;
+
+/// Builds a router with `size` wildcard routes that all match `/files/...`, plus as
+/// many static routes, so looking up one of the static paths matches every wildcard too.
+fn wildcard_router(size: usize) -> Router<usize> {
+    let router = Router::new();
+    for i in 0..size {
+        add_route(&router, "GET", &format!("/files/**:path{}", i), i).unwrap();
+        add_route(&router, "GET", &format!("/files/static{}", i), i + size).unwrap();
+    }
+    router
+}
+
+fn bench_parallel_find_all(c: &mut Criterion) {
+    let mut group = c.benchmark_group("find_all_wildcards");
+    for &size in &[100usize, 1_000, 5_000] {
+        let router = wildcard_router(size);
+        let path = format!("/files/static{}", size - 1);
+        group.bench_with_input(BenchmarkId::new("sequential", size), &path, |b, path| {
+            b.iter(|| black_box(find_all_routes(&router, "GET", path, true)))
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", size), &path, |b, path| {
+            b.iter(|| black_box(find_all_routes_parallel(&router, "GET", path, true)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parallel_find_all);
+criterion_main!(benches);
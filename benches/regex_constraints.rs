@@ -0,0 +1,32 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use rou3::{Router, add_route, find_route};
+use std::hint::black_box;
+
+/// Builds a router with `size` parameterized routes, with or without a `\d+`
+/// constraint on their parameter.
+fn param_router(size: usize, constrained: bool) -> Router<usize> {
+    let router = Router::new();
+    let param = if constrained { r":id<\d+>" } else { ":id" };
+    for i in 0..size {
+        add_route(&router, "GET", &format!("/items{}/{}", i, param), i).unwrap();
+    }
+    router
+}
+
+fn bench_regex_constraints(c: &mut Criterion) {
+    let size = 1_000;
+    let path = format!("/items{}/12345", size - 1);
+    let mut group = c.benchmark_group("param_lookup");
+    let unconstrained = param_router(size, false);
+    group.bench_function("unconstrained", |b| {
+        b.iter(|| black_box(find_route(&unconstrained, "GET", &path, true)))
+    });
+    let constrained = param_router(size, true);
+    group.bench_function("regex_constrained", |b| {
+        b.iter(|| black_box(find_route(&constrained, "GET", &path, true)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_regex_constraints);
+criterion_main!(benches);
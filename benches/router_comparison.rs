@@ -161,7 +161,12 @@ fn compare_routers(c: &mut Criterion) {
     for route_pattern in routes!(rou3_colon_add) {
         add_route(&rou3_router, "GET", route_pattern, true).expect("rou3 add failed");
     }
-    group.bench_function("rou3", |b| {
+    let rou3_name = if cfg!(feature = "fxhash") {
+        "rou3 (fxhash)"
+    } else {
+        "rou3"
+    };
+    group.bench_function(rou3_name, |b| {
         b.iter(|| {
             for path_to_lookup in black_box(&lookup_paths) {
                 let result =
@@ -186,15 +191,16 @@ fn compare_routers(c: &mut Criterion) {
     });
 
     // --- wayfind ---
-    let mut wayfind_router = wayfind::Router::new();
+    let mut wayfind_builder = wayfind::RouterBuilder::new();
     for route_pattern in routes!(wayfind) {
-        wayfind_router.insert(route_pattern, true).unwrap();
+        wayfind_builder.insert(route_pattern, true).unwrap();
     }
+    let wayfind_router = wayfind_builder.build();
     group.bench_function("wayfind", |b| {
         b.iter(|| {
             for path_to_lookup in black_box(&lookup_paths) {
                 let result = black_box(wayfind_router.search(path_to_lookup).unwrap());
-                assert!(*result.data);
+                assert!(*result.data());
             }
         });
     });
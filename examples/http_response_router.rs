@@ -0,0 +1,83 @@
+//! Routes requests to handler functions and turns the match into an `http::Response`.
+//!
+//! Each route stores a `fn() -> http::Response<String>` wrapped in `Handler`, which
+//! implements `Into<http::Response<String>>`, so `http::Response::from` can route and
+//! respond in one expression. Any body type works the same way; `String` keeps the
+//! example free of a server dependency.
+//!
+//! Run with `cargo run --example http_response_router --features http`.
+
+use http::{Response, StatusCode};
+use rou3::{Router, add_route, find_route};
+
+/// A route handler. Handlers are compared by name, since function pointers
+/// have no reliable identity.
+#[derive(Clone, Copy)]
+struct Handler {
+    name: &'static str,
+    respond: fn() -> Response<String>,
+}
+
+impl PartialEq for Handler {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Handler {}
+
+impl From<Handler> for Response<String> {
+    fn from(handler: Handler) -> Self {
+        (handler.respond)()
+    }
+}
+
+fn index() -> Response<String> {
+    Response::new("Welcome!".to_string())
+}
+
+fn health() -> Response<String> {
+    Response::builder()
+        .header("content-type", "application/json")
+        .body(r#"{"status":"ok"}"#.to_string())
+        .expect("valid response")
+}
+
+fn not_found() -> Response<String> {
+    Response::builder()
+        .status(StatusCode::NOT_FOUND)
+        .body("Not Found".to_string())
+        .expect("valid response")
+}
+
+fn main() {
+    let router = Router::new();
+    add_route(
+        &router,
+        "GET",
+        "/",
+        Handler {
+            name: "index",
+            respond: index,
+        },
+    )
+    .unwrap();
+    add_route(
+        &router,
+        "GET",
+        "/health",
+        Handler {
+            name: "health",
+            respond: health,
+        },
+    )
+    .unwrap();
+
+    for path in ["/", "/health", "/missing"] {
+        let response = match find_route(&router, "GET", path, false) {
+            Ok(matched) => Response::from(matched),
+            Err(_) => not_found(),
+        };
+        println!("GET {path} -> {} {}", response.status(), response.body());
+    }
+}
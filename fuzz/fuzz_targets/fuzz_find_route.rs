@@ -0,0 +1,30 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rou3::{NormalizedPath, Router, RouterError, add_route, find_route};
+
+fn setup_router() -> Router<&'static str> {
+    let router = Router::new();
+    add_route(&router, "GET", "/", "root").unwrap();
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "POST", "/users", "create_user").unwrap();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "GET", "/users/:id/posts/:post_id?", "get_user_post").unwrap();
+    add_route(&router, "GET", "/files/*", "single_file").unwrap();
+    add_route(&router, "GET", "/assets/**:filepath", "serve_asset").unwrap();
+    add_route(&router, "", "/any", "any_method").unwrap();
+    router
+}
+
+fuzz_target!(|input: (bool, NormalizedPath)| {
+    let (capture, path) = input;
+    let router = setup_router();
+    for method in ["GET", "POST", "DELETE"] {
+        match find_route(&router, method, path.as_str(), capture) {
+            Ok(_)
+            | Err(RouterError::RouteNotFound { .. })
+            | Err(RouterError::MethodNotAllowed { .. }) => {}
+            Err(e) => panic!("unexpected error for {method} {}: {e}", path.as_str()),
+        }
+    }
+});
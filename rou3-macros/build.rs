@@ -0,0 +1,4 @@
+fn main() {
+    // The sample route file registered by this crate's tests and docs.
+    println!("cargo:rerun-if-changed=routes.json");
+}
@@ -0,0 +1,134 @@
+//! # rou3-macros
+//!
+//! Compile-time route registration for [rou3](https://docs.rs/rou3).
+//!
+//! [`register_routes!`] reads a JSON route list while the calling crate is compiled
+//! and expands to one `rou3::add_route` call per route, so the routes are baked into
+//! the binary and no route file has to be shipped or read at runtime.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use serde_json::Value;
+use std::path::PathBuf;
+use syn::{
+    Expr, LitStr, Token,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+};
+
+/// The arguments of `register_routes!`: a router expression and a route file path.
+struct RegisterRoutesInput {
+    router: Expr,
+    file: LitStr,
+}
+
+impl Parse for RegisterRoutesInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let router = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let file = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self { router, file })
+    }
+}
+
+/// Registers the routes listed in a JSON file, read at compile time.
+///
+/// The file path is resolved relative to the calling crate's `Cargo.toml`
+/// directory and uses the same format as `Router::from_json_str`:
+///
+/// ```json
+/// [{"method": "GET", "path": "/users/:id", "data": "get_user"}]
+/// ```
+///
+/// Each route's `data` must be a JSON string, number, or boolean; it is emitted as
+/// the corresponding Rust literal. The macro expands to an expression of type
+/// `Result<(), rou3::RouterError>` that adds the routes in file order and stops at
+/// the first route `add_route` rejects. Each pattern is also checked with
+/// `rou3::operations::util::validate_route_pattern` in a `const` assertion, so a
+/// malformed pattern fails the build instead. The file is included with
+/// `include_str!`, so editing it triggers a rebuild of the calling crate.
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, find_route};
+/// use rou3_macros::register_routes;
+///
+/// let router = Router::new();
+/// register_routes!(&router, "routes.json").unwrap();
+/// assert_eq!(find_route(&router, "GET", "/users/42", false).unwrap().data, "get_user");
+/// ```
+#[proc_macro]
+pub fn register_routes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as RegisterRoutesInput);
+    expand_register_routes(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_register_routes(input: &RegisterRoutesInput) -> syn::Result<TokenStream2> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| syn::Error::new(Span::call_site(), "CARGO_MANIFEST_DIR is not set"))?;
+    let path = PathBuf::from(manifest_dir).join(input.file.value());
+    let error = |message: String| syn::Error::new(input.file.span(), message);
+
+    let json = std::fs::read_to_string(&path)
+        .map_err(|read_err| error(format!("cannot read {}: {read_err}", path.display())))?;
+    let routes: Vec<Value> = serde_json::from_str(&json)
+        .map_err(|parse_err| error(format!("invalid route list: {parse_err}")))?;
+
+    let mut checks = Vec::with_capacity(routes.len());
+    let mut calls = Vec::with_capacity(routes.len());
+    for (index, route) in routes.iter().enumerate() {
+        let field = |name: &str| {
+            route
+                .get(name)
+                .ok_or_else(|| error(format!("route {index} has no `{name}` field")))
+        };
+        let method = field("method")?
+            .as_str()
+            .ok_or_else(|| error(format!("route {index}: `method` must be a string")))?;
+        let pattern = field("path")?
+            .as_str()
+            .ok_or_else(|| error(format!("route {index}: `path` must be a string")))?;
+        let data = data_literal(field("data")?).ok_or_else(|| {
+            error(format!(
+                "route {index}: `data` must be a string, number, or boolean"
+            ))
+        })?;
+        checks.push(quote! {
+            const _: () = assert!(::rou3::operations::util::validate_route_pattern(#pattern));
+        });
+        calls.push(quote! {
+            ::rou3::add_route(__rou3_router, #method, #pattern, #data)?;
+        });
+    }
+
+    let router = &input.router;
+    let include_path = path.to_string_lossy().into_owned();
+    Ok(quote! {
+        {
+            const _: &str = include_str!(#include_path);
+            #(#checks)*
+            let __rou3_router = #router;
+            (|| -> ::core::result::Result<(), ::rou3::RouterError> {
+                #(#calls)*
+                ::core::result::Result::Ok(())
+            })()
+        }
+    })
+}
+
+/// Converts a route's JSON `data` into a Rust literal, if it is a scalar.
+fn data_literal(value: &Value) -> Option<TokenStream2> {
+    match value {
+        Value::String(text) => Some(quote! { #text }),
+        Value::Bool(flag) => Some(quote! { #flag }),
+        Value::Number(number) => {
+            let literal: TokenStream2 = number.to_string().parse().ok()?;
+            Some(literal)
+        }
+        Value::Null | Value::Array(_) | Value::Object(_) => None,
+    }
+}
@@ -0,0 +1,51 @@
+use rou3::{Router, find_route, list_routes};
+use rou3_macros::register_routes;
+
+#[test]
+fn test_register_routes_from_json() {
+    let router = Router::new();
+    register_routes!(&router, "routes.json").unwrap();
+
+    assert_eq!(
+        find_route(&router, "GET", "/", false).unwrap().data,
+        "index"
+    );
+    assert_eq!(
+        find_route(&router, "POST", "/users", false).unwrap().data,
+        "create_user"
+    );
+    let matched = find_route(&router, "GET", "/users/42", true).unwrap();
+    assert_eq!(matched.data, "get_user");
+    assert_eq!(
+        matched.params.unwrap().get("id").map(String::as_str),
+        Some("42")
+    );
+    assert_eq!(
+        find_route(&router, "PUT", "/static/css/app.css", false)
+            .unwrap()
+            .data,
+        "static_files"
+    );
+    assert_eq!(list_routes(&router).len(), 5);
+}
+
+#[test]
+fn test_register_routes_numeric_data() {
+    let router: Router<u32> = Router::new();
+    register_routes!(&router, "tests/numeric_routes.json").unwrap();
+    assert_eq!(
+        find_route(&router, "GET", "/items/7", false).unwrap().data,
+        1
+    );
+    assert_eq!(
+        find_route(&router, "DELETE", "/items/7", false)
+            .unwrap()
+            .data,
+        2
+    );
+}
+
+#[test]
+fn test_register_routes_rejects_invalid_pattern_at_compile_time() {
+    trybuild::TestCases::new().compile_fail("tests/ui/invalid_routes.rs");
+}
@@ -0,0 +1,9 @@
+use rou3::Router;
+use rou3_macros::register_routes;
+
+fn main() {
+    let router = Router::new();
+    // trybuild compiles this file in `target/tests/trybuild/rou3-macros`, which is
+    // where the macro resolves the route file from.
+    let _ = register_routes!(&router, "../../../../rou3-macros/tests/invalid_routes.json");
+}
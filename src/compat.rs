@@ -0,0 +1,27 @@
+//! The lock and hash collections that differ between the `std` and `no_std` builds.
+//!
+//! With the `std` feature these are `parking_lot` locks and `std` hash maps. Without
+//! it they are `spin` locks and `hashbrown` maps, which only need `alloc`.
+
+#[cfg(feature = "std")]
+pub(crate) use parking_lot::RwLock;
+#[cfg(not(feature = "std"))]
+pub(crate) use spin::RwLock;
+
+#[cfg(feature = "std")]
+pub(crate) use ahash::AHashMap;
+/// `ahash::AHashMap` requires `std`; this is the same map built on `hashbrown`.
+#[cfg(not(feature = "std"))]
+pub(crate) type AHashMap<K, V> = hashbrown::HashMap<K, V, ahash::RandomState>;
+
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::HashMap;
+#[cfg(feature = "std")]
+pub(crate) use std::collections::HashMap;
+
+/// A hash set with `ahash` hashing. Construct it with `HashSet::default()`.
+#[cfg(feature = "std")]
+pub(crate) type HashSet<T> = std::collections::HashSet<T, ahash::RandomState>;
+/// A hash set with `ahash` hashing. Construct it with `HashSet::default()`.
+#[cfg(not(feature = "std"))]
+pub(crate) type HashSet<T> = hashbrown::HashSet<T, ahash::RandomState>;
@@ -7,22 +7,48 @@
 //! The `Router` is the main entry point, holding the root of the routing tree
 //! and a separate map for optimized lookups of purely static routes.
 
-use crate::types::MethodData;
-use ahash::AHashMap;
+use crate::compat::{AHashMap, RwLock};
+use crate::{
+    error::RouterError,
+    operations::{
+        add_route, add_route_with_description, list_routes,
+        util::{normalize, percent_decode_segment, split_path},
+    },
+    types::{MethodData, ParamEntry, RouteEntry, TrailingSlashMode, WildcardPreference},
+};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+use core::fmt;
 use indexmap::IndexMap;
-use parking_lot::RwLock;
 
 /// Represents a node in the routing tree.
 #[derive(Debug, Clone)]
 pub struct Node<T> {
     /// Stores handlers for HTTP methods. Key is method string (e.g., "GET", "" for ANY).
-    pub methods: AHashMap<String, Vec<MethodData<T>>>,
-    /// Children nodes for static path segments.
-    pub static_children: AHashMap<String, Box<Node<T>>>,
+    pub methods: RouteMap<String, Handlers<T>>,
+    /// Children nodes for static path segments, in insertion order.
+    pub static_children: IndexMap<String, Box<Node<T>>, RouteHasher>,
     /// Child node for a parameterized path segment (e.g., `/:id`, `/*`).
+    ///
+    /// Parameters are shared by name: `/users/:userId` and `/users/:teamId/posts`
+    /// both go through this one node, and each handler maps positions back to its
+    /// own names with its `params_map`. Patterns that differ only in parameter names
+    /// match exactly the same paths, so one of them is shadowed whichever node they
+    /// live on; `strict_shadowing` and `find_ambiguous_routes` report them.
     pub param_child: Option<Box<Node<T>>>,
     /// Child node for a wildcard path segment (e.g., `/**:filepath`).
     pub wildcard_child: Option<Box<Node<T>>>,
+    /// Static segments merged into this node by [`compress`], joined with `/`.
+    /// They must all match, in order, after the segment that leads to this node.
+    /// Empty unless the router has been compressed.
+    pub edge_label: String,
 }
 
 impl<T> Default for Node<T> {
@@ -35,13 +61,54 @@ impl<T> Node<T> {
     /// Constructs a new `Node` with empty method handlers and no children.
     pub fn new() -> Self {
         Self {
-            methods: AHashMap::default(),
-            static_children: AHashMap::default(),
+            methods: RouteMap::default(),
+            static_children: IndexMap::default(),
             param_child: None,
             wildcard_child: None,
+            edge_label: String::new(),
         }
     }
 
+    /// Iterates over the static segments of this node's `edge_label`.
+    pub(crate) fn edge_label_segments(&self) -> impl Iterator<Item = &str> {
+        (!self.edge_label.is_empty())
+            .then(|| self.edge_label.split('/'))
+            .into_iter()
+            .flatten()
+    }
+
+    /// Matches this node's `edge_label` against `segments` starting at `idx`.
+    /// Returns the index just past the label, or `None` if any label segment differs.
+    pub(crate) fn consume_edge_label(&self, segments: &[&str], idx: usize) -> Option<usize> {
+        let mut idx = idx;
+        for label_segment in self.edge_label_segments() {
+            if segments.get(idx) != Some(&label_segment) {
+                return None;
+            }
+            idx += 1;
+        }
+        Some(idx)
+    }
+
+    /// Splits this node's `edge_label` before the label segment at `at`.
+    ///
+    /// This node keeps the first `at` label segments; segment `at` becomes a new
+    /// static child that takes the rest of the label together with this node's
+    /// handlers and children.
+    pub(crate) fn split_edge_label(&mut self, at: usize) {
+        let label: Vec<String> = self.edge_label_segments().map(String::from).collect();
+        let tail = Node {
+            methods: core::mem::take(&mut self.methods),
+            static_children: core::mem::take(&mut self.static_children),
+            param_child: self.param_child.take(),
+            wildcard_child: self.wildcard_child.take(),
+            edge_label: label[at + 1..].join("/"),
+        };
+        self.edge_label = label[..at].join("/");
+        self.static_children
+            .insert(label[at].clone(), Box::new(tail));
+    }
+
     /// Checks if this node is effectively empty (no handlers and no children).
     /// Used for pruning during route removal.
     pub fn is_empty_recursive(&self) -> bool {
@@ -52,19 +119,290 @@ impl<T> Node<T> {
     }
 }
 
+/// The hasher of the routing tree's maps ([`RouteMap`] and `Node::static_children`).
+///
+/// This is `ahash::RandomState` by default, whose random seeding resists HashDoS
+/// attacks. With the `fxhash` feature it is `rustc_hash::FxBuildHasher`, which is
+/// deterministic across runs and can be faster for short keys, but whose collisions
+/// can be forced by crafted keys; keep the default when routes or request paths
+/// come from untrusted input.
+#[cfg(not(feature = "fxhash"))]
+pub type RouteHasher = ahash::RandomState;
+/// The hasher of the routing tree's maps: `rustc_hash::FxBuildHasher`, as the
+/// `fxhash` feature is enabled. It is deterministic but not HashDoS resistant.
+#[cfg(feature = "fxhash")]
+pub type RouteHasher = rustc_hash::FxBuildHasher;
+
+/// The hash map used for the handlers of a node or static route, keyed by method.
+pub type RouteMap<K, V> = crate::compat::HashMap<K, V, RouteHasher>;
+
+/// The handlers registered for one method on a node or static route, in the order
+/// they are tried.
+///
+/// This is a `Vec` by default. With the `smallvec` feature it is a `SmallVec` that
+/// stores one handler inline, the usual case, and only allocates for more.
+#[cfg(not(feature = "smallvec"))]
+pub type Handlers<T> = Vec<MethodData<T>>;
+/// The handlers registered for one method on a node or static route: a `SmallVec`
+/// storing one handler inline, as the `smallvec` feature is enabled.
+#[cfg(feature = "smallvec")]
+pub type Handlers<T> = smallvec::SmallVec<[MethodData<T>; 1]>;
+
+/// The hasher of `Router::static_map`: `std`'s randomly seeded `RandomState`, or
+/// `rustc_hash::FxBuildHasher` with the `fxhash` feature.
+#[cfg(all(feature = "std", not(feature = "fxhash")))]
+pub type StaticMapHasher = std::hash::RandomState;
+/// The hasher of `Router::static_map`: `ahash::RandomState`, as `std` is not linked.
+#[cfg(not(any(feature = "std", feature = "fxhash")))]
+pub type StaticMapHasher = ahash::RandomState;
+/// The hasher of `Router::static_map`: `rustc_hash::FxBuildHasher`, as the `fxhash`
+/// feature is enabled.
+#[cfg(feature = "fxhash")]
+pub type StaticMapHasher = rustc_hash::FxBuildHasher;
+
 /// Type alias for the value part of the static_map in the Router.
 /// Represents a map from HTTP method strings to a list of method-specific data.
-pub type StaticPathMethods<T> = AHashMap<String, Vec<MethodData<T>>>;
+pub type StaticPathMethods<T> = RouteMap<String, Handlers<T>>;
+
+/// Type alias for the Router's static_map, keyed by normalized path string.
+pub type StaticMap<T> = IndexMap<String, StaticPathMethods<T>, StaticMapHasher>;
+
+/// Behavioral options for a `Router`.
+///
+/// Construct one with [`RouterConfig::builder`] and pass it to [`Router::with_config`].
+/// `RouterConfig::default()` matches the behavior of `Router::new()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouterConfig {
+    /// Whether static segments are matched case-sensitively. Defaults to `true`.
+    /// When `false`, static segments of patterns and request paths are compared
+    /// ASCII case-insensitively; captured parameter values keep their original case.
+    pub case_sensitive: bool,
+    /// The maximum number of segments a route pattern or request path may have.
+    /// Deeper paths are rejected with `RouterError::InvalidPath`. Defaults to `None` (unlimited).
+    pub max_depth: Option<usize>,
+    /// Whether request path segments are percent-decoded before matching. Defaults to `false`.
+    /// Encoded slashes (`%2F`) are left encoded so they never split a segment.
+    pub percent_decode: bool,
+    /// Normalized path prefix prepended to every route pattern, if set.
+    /// See [`Router::with_global_prefix`].
+    pub global_prefix: Option<String>,
+    /// Whether empty segments (`//`) are rejected instead of collapsed. Defaults to `false`.
+    /// When `true`, `add_route` returns `RouterError::InvalidSegment` for such patterns
+    /// and `find_route` returns `RouterError::RouteNotFound` for such request paths.
+    pub strict_empty_segments: bool,
+    /// How trailing slashes are treated. Defaults to `TrailingSlashMode::Ignore`.
+    pub trailing_slash: TrailingSlashMode,
+    /// Which wildcard route wins when several match. Defaults to
+    /// `WildcardPreference::FirstRegistered`.
+    pub wildcard_preference: WildcardPreference,
+    /// Whether `add_route` rejects a route that an existing route would always be
+    /// chosen over, returning `RouterError::ShadowedRoute`. Defaults to `false`, in
+    /// which case such a route is added and, with the `tracing` feature, a `tracing`
+    /// warning is emitted.
+    pub strict_shadowing: bool,
+}
+
+impl Default for RouterConfig {
+    fn default() -> Self {
+        Self {
+            case_sensitive: true,
+            max_depth: None,
+            percent_decode: false,
+            global_prefix: None,
+            strict_empty_segments: false,
+            trailing_slash: TrailingSlashMode::Ignore,
+            wildcard_preference: WildcardPreference::FirstRegistered,
+            strict_shadowing: false,
+        }
+    }
+}
+
+impl RouterConfig {
+    /// Starts building a `RouterConfig` from the default options.
+    ///
+    /// # Example
+    /// ```rust
+    /// use rou3::{Router, RouterConfig};
+    ///
+    /// let config = RouterConfig::builder().case_insensitive().max_depth(20).build();
+    /// let router: Router<&str> = Router::with_config(config);
+    /// ```
+    pub fn builder() -> RouterConfigBuilder {
+        RouterConfigBuilder::default()
+    }
+
+    /// Normalizes a request path according to this configuration.
+    ///
+    /// Returns `RouterError::RouteNotFound` for paths `strict_empty_segments`
+    /// rejects and `RouterError::InvalidPath` for paths deeper than `max_depth`.
+    pub(crate) fn prepare_request_path(
+        &self,
+        method: &str,
+        path: &str,
+    ) -> Result<RequestPath<'static>, RouterError> {
+        if self.strict_empty_segments && path.contains("//") {
+            return Err(RouterError::not_found(method, path));
+        }
+        let mut normalized = self
+            .decode_request_path(Cow::Owned(normalize(path)))
+            .into_owned();
+        let mut redirect_to = None;
+        if has_trailing_slash(path) {
+            match self.trailing_slash {
+                TrailingSlashMode::Ignore => {}
+                TrailingSlashMode::Strict => normalized.push('/'),
+                TrailingSlashMode::Redirect => redirect_to = Some(format!("/{}", normalize(path))),
+            }
+        }
+        self.finish_request_path(Cow::Owned(normalized), redirect_to)
+    }
+
+    /// Prepares a path that is already normalized, borrowing it unless it has
+    /// to be percent-decoded. Errors as `prepare_request_path` does.
+    pub(crate) fn prepare_normalized_path<'a>(
+        &self,
+        normalized: &'a str,
+    ) -> Result<RequestPath<'a>, RouterError> {
+        let normalized = self.decode_request_path(Cow::Borrowed(normalized));
+        self.finish_request_path(normalized, None)
+    }
+
+    fn decode_request_path<'a>(&self, normalized: Cow<'a, str>) -> Cow<'a, str> {
+        if self.percent_decode && normalized.contains('%') {
+            Cow::Owned(
+                split_path(&normalized)
+                    .map(percent_decode_segment)
+                    .collect::<Vec<_>>()
+                    .join("/"),
+            )
+        } else {
+            normalized
+        }
+    }
+
+    fn finish_request_path<'a>(
+        &self,
+        normalized: Cow<'a, str>,
+        redirect_to: Option<String>,
+    ) -> Result<RequestPath<'a>, RouterError> {
+        self.check_depth(&normalized)?;
+        let folded = (!self.case_sensitive).then(|| normalized.to_ascii_lowercase());
+        Ok(RequestPath {
+            normalized,
+            folded,
+            redirect_to,
+        })
+    }
+
+    fn check_depth(&self, normalized_path: &str) -> Result<(), RouterError> {
+        match self.max_depth {
+            Some(max_depth) if split_path(normalized_path).count() > max_depth => Err(
+                RouterError::InvalidPath("exceeds maximum path depth".to_string()),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Builder for [`RouterConfig`].
+#[derive(Debug, Clone, Default)]
+pub struct RouterConfigBuilder {
+    config: RouterConfig,
+}
+
+impl RouterConfigBuilder {
+    /// Matches static segments ASCII case-insensitively.
+    pub fn case_insensitive(mut self) -> Self {
+        self.config.case_sensitive = false;
+        self
+    }
+
+    /// Limits route patterns and request paths to `max_depth` segments.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.config.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Percent-decodes request path segments before matching.
+    pub fn percent_decode(mut self) -> Self {
+        self.config.percent_decode = true;
+        self
+    }
+
+    /// Mounts every route under `prefix`. An empty prefix (or `/`) sets no prefix.
+    pub fn global_prefix(mut self, prefix: &str) -> Self {
+        let normalized_prefix = normalize(prefix);
+        self.config.global_prefix = (!normalized_prefix.is_empty()).then_some(normalized_prefix);
+        self
+    }
+
+    /// Rejects paths with empty segments (`//`) instead of collapsing them.
+    pub fn strict_empty_segments(mut self) -> Self {
+        self.config.strict_empty_segments = true;
+        self
+    }
+
+    /// Sets how trailing slashes on patterns and request paths are treated.
+    pub fn trailing_slash(mut self, mode: TrailingSlashMode) -> Self {
+        self.config.trailing_slash = mode;
+        self
+    }
+
+    /// Sets which wildcard route wins when several match a request path.
+    pub fn wildcard_preference(mut self, preference: WildcardPreference) -> Self {
+        self.config.wildcard_preference = preference;
+        self
+    }
+
+    /// Rejects routes shadowed by an existing route instead of only warning about them.
+    pub fn strict_shadowing(mut self) -> Self {
+        self.config.strict_shadowing = true;
+        self
+    }
+
+    /// Finishes building the `RouterConfig`.
+    pub fn build(self) -> RouterConfig {
+        self.config
+    }
+}
+
+/// A callback notified of route changes, called with the HTTP method and a path.
+/// See [`Router::set_on_add`] and [`Router::set_on_remove`].
+pub type RouteHook = Arc<dyn Fn(&str, &str) + Send + Sync>;
+
+/// A callback notified of successful lookups, called with the HTTP method, the
+/// normalized request path, and the matched route pattern. See [`Router::set_on_match`].
+pub type MatchHook = Arc<dyn Fn(&str, &str, &str) + Send + Sync>;
 
 /// The main router structure.
-#[derive(Debug)]
 pub struct Router<T> {
     /// The root node of the routing tree.
     pub root: RwLock<Box<Node<T>>>,
     /// Optimized map for purely static routes.
     /// Key: normalized path string.
     /// Value: Map of method string to list of `MethodData`.
-    pub static_map: RwLock<IndexMap<String, StaticPathMethods<T>>>,
+    pub static_map: RwLock<StaticMap<T>>,
+    /// Behavioral options this router was created with.
+    pub config: RouterConfig,
+    /// Called after a route is added. Set with [`Router::set_on_add`].
+    pub on_add: Option<RouteHook>,
+    /// Called after a route is removed. Set with [`Router::set_on_remove`].
+    pub on_remove: Option<RouteHook>,
+    /// Called after `find_route` matches a route. Set with [`Router::set_on_match`].
+    pub on_match: Option<MatchHook>,
+}
+
+impl<T: fmt::Debug> fmt::Debug for Router<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Router")
+            .field("root", &self.root)
+            .field("static_map", &self.static_map)
+            .field("config", &self.config)
+            .field("on_add", &self.on_add.as_ref().map(|_| "<hook>"))
+            .field("on_remove", &self.on_remove.as_ref().map(|_| "<hook>"))
+            .field("on_match", &self.on_match.as_ref().map(|_| "<hook>"))
+            .finish()
+    }
 }
 
 impl<T: Clone> Default for Router<T> {
@@ -76,9 +414,1015 @@ impl<T: Clone> Default for Router<T> {
 impl<T: Clone> Router<T> {
     /// Constructs a new `Router`.
     pub fn new() -> Self {
+        Self::with_config(RouterConfig::default())
+    }
+
+    /// Constructs a new `Router` with the given options.
+    pub fn with_config(config: RouterConfig) -> Self {
         Self {
             root: RwLock::new(Box::new(Node::new())),
-            static_map: RwLock::new(IndexMap::default()),
+            static_map: RwLock::new(StaticMap::default()),
+            config,
+            on_add: None,
+            on_remove: None,
+            on_match: None,
+        }
+    }
+
+    /// Constructs a new `Router` that mounts every route under `prefix`.
+    ///
+    /// Patterns passed to `add_route`, `remove_route`, `enable_route`, and
+    /// `disable_route` are written without the prefix; the router prepends it.
+    /// Request paths given to `find_route` must include the prefix, and paths
+    /// outside it return `RouterError::RouteNotFound`. `list_routes` reports the
+    /// full, prefixed patterns.
+    ///
+    /// An empty prefix (or `/`) behaves like `Router::new()`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use rou3::{Router, add_route, find_route};
+    ///
+    /// let router = Router::with_global_prefix("/api/v1");
+    /// add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    ///
+    /// assert_eq!(find_route(&router, "GET", "/api/v1/users/7", false).unwrap().data, "get_user");
+    /// assert!(find_route(&router, "GET", "/users/7", false).is_err());
+    /// ```
+    pub fn with_global_prefix(prefix: &str) -> Self {
+        Self::with_config(RouterConfig::builder().global_prefix(prefix).build())
+    }
+
+    /// Constructs a new `Router` that rejects route patterns and request paths with
+    /// more than `max_depth` segments.
+    ///
+    /// `add_route` and `find_route` return `RouterError::InvalidPath` for deeper
+    /// paths, so a request with thousands of `/`-separated segments is refused before
+    /// the routing tree is walked.
+    ///
+    /// # Example
+    /// ```rust
+    /// use rou3::{Router, RouterError, add_route, find_route};
+    ///
+    /// let router = Router::with_max_depth(3);
+    /// add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    ///
+    /// let deep_path = "/x".repeat(1_000);
+    /// assert!(matches!(
+    ///     find_route(&router, "GET", &deep_path, false),
+    ///     Err(RouterError::InvalidPath(_))
+    /// ));
+    /// ```
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Self::with_config(RouterConfig::builder().max_depth(max_depth).build())
+    }
+
+    /// Constructs a new `Router` containing the given routes, e.g. those listed by
+    /// iterating over another router or saved in a [`RouterSnapshot`].
+    ///
+    /// Each entry is added with `add_route`, or `add_route_with_description` if it
+    /// has a description; its other fields are not used.
+    ///
+    /// # Errors
+    /// Returns the error from `add_route` for the first entry that cannot be added;
+    /// the remaining entries are not added.
+    ///
+    /// # Example
+    /// ```rust
+    /// use rou3::{Router, add_route, find_route};
+    ///
+    /// let router = Router::new();
+    /// add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    ///
+    /// let copy = Router::from_entries(&router).unwrap();
+    /// assert_eq!(find_route(&copy, "GET", "/users/7", false).unwrap().data, "get_user");
+    /// ```
+    pub fn from_entries<I: IntoIterator<Item = RouteEntry<T>>>(
+        entries: I,
+    ) -> Result<Self, RouterError> {
+        let router = Self::new();
+        for entry in entries {
+            add_entry(
+                &router,
+                &entry.method,
+                &entry.pattern,
+                entry.data,
+                entry.description,
+            )?;
+        }
+        Ok(router)
+    }
+
+    /// Adds a route and returns the router, so registrations can be chained with `?`.
+    ///
+    /// This is the method form of [`add_route`](crate::add_route), with the same
+    /// validation and errors.
+    ///
+    /// # Example
+    /// ```rust
+    /// use rou3::{Router, RouterError, find_route};
+    ///
+    /// fn build() -> Result<Router<&'static str>, RouterError> {
+    ///     let router = Router::new();
+    ///     router
+    ///         .add("GET", "/users/:id", "get_user")?
+    ///         .add("POST", "/users", "create_user")?;
+    ///     Ok(router)
+    /// }
+    ///
+    /// let router = build().unwrap();
+    /// assert_eq!(find_route(&router, "POST", "/users", false).unwrap().data, "create_user");
+    /// ```
+    pub fn add(&self, method: &str, path: &str, data: T) -> Result<&Self, RouterError> {
+        add_route(self, method, path, data)?;
+        Ok(self)
+    }
+}
+
+/// A request path prepared for matching according to a router's `RouterConfig`.
+pub(crate) struct RequestPath<'a> {
+    /// The normalized (and, if enabled, percent-decoded) path. Parameters are
+    /// extracted from this so they keep their original case. Borrowed when the
+    /// caller's path was already normalized and needed no decoding.
+    pub(crate) normalized: Cow<'a, str>,
+    /// `normalized` folded to lowercase when matching is case-insensitive.
+    folded: Option<String>,
+    /// Set under `TrailingSlashMode::Redirect` when the request path had a trailing
+    /// slash; holds the canonical path to redirect to if it matches.
+    pub(crate) redirect_to: Option<String>,
+}
+
+impl RequestPath<'_> {
+    /// The path static segments should be matched against.
+    pub(crate) fn lookup(&self) -> &str {
+        self.folded.as_deref().unwrap_or(&self.normalized)
+    }
+
+    /// Whether `lookup()` differs from `normalized` (case-insensitive matching).
+    pub(crate) fn is_folded(&self) -> bool {
+        self.folded.is_some()
+    }
+}
+
+impl<T> Router<T> {
+    /// Sets a hook called after every successful `add_route` (or variant), with
+    /// the route's method and normalized pattern (e.g., `users/:id`).
+    ///
+    /// The hook runs after the router's locks are released, so it may read the
+    /// router. Use it for audit logging, metrics, or cache invalidation.
+    pub fn set_on_add(&mut self, hook: impl Fn(&str, &str) + Send + Sync + 'static) {
+        self.on_add = Some(Arc::new(hook));
+    }
+
+    /// Sets a hook called after `remove_route` removes a route, with the method
+    /// and the pattern passed to `remove_route`. It is not called when nothing
+    /// was removed.
+    ///
+    /// The hook runs after the router's locks are released, so it may read the
+    /// router.
+    pub fn set_on_remove(&mut self, hook: impl Fn(&str, &str) + Send + Sync + 'static) {
+        self.on_remove = Some(Arc::new(hook));
+    }
+
+    /// Sets a hook called after every successful `find_route`, with the method, the
+    /// normalized request path (e.g., `users/42`), and the matched route pattern
+    /// (e.g., `/users/:id`).
+    ///
+    /// The hook runs after the router's locks are released. Use it to tag request
+    /// metrics by route pattern, e.g. with `metrics::counter!` or a StatsD client,
+    /// without wrapping `find_route`.
+    pub fn set_on_match(&mut self, hook: impl Fn(&str, &str, &str) + Send + Sync + 'static) {
+        self.on_match = Some(Arc::new(hook));
+    }
+
+    /// Normalizes a route pattern according to the router's configuration: prepends
+    /// the global prefix, folds static segments to lowercase when matching is
+    /// case-insensitive, keeps a trailing slash under `TrailingSlashMode::Strict`,
+    /// and enforces `strict_empty_segments` and `max_depth`.
+    pub(crate) fn normalize_pattern(&self, pattern: &str) -> Result<String, RouterError> {
+        if self.config.strict_empty_segments && pattern.contains("//") {
+            return Err(RouterError::InvalidSegment {
+                segment: pattern.to_string(),
+                reason: "empty segments are not allowed".to_string(),
+            });
+        }
+        let mut normalized_pattern = normalize(pattern);
+        if self.config.trailing_slash == TrailingSlashMode::Strict && has_trailing_slash(pattern) {
+            normalized_pattern.push('/');
+        }
+        let mut full_pattern = match &self.config.global_prefix {
+            Some(prefix) if normalized_pattern.is_empty() => prefix.clone(),
+            Some(prefix) => format!("{prefix}/{normalized_pattern}"),
+            None => normalized_pattern,
+        };
+        if !self.config.case_sensitive {
+            full_pattern = split_path(&full_pattern)
+                .map(|segment| {
+                    if segment.starts_with([':', '*']) {
+                        segment.to_string()
+                    } else {
+                        segment.to_ascii_lowercase()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+        }
+        self.config.check_depth(&full_pattern)?;
+        Ok(full_pattern)
+    }
+
+    /// Normalizes a request path according to the router's configuration.
+    ///
+    /// Returns `RouterError::RouteNotFound` for paths `strict_empty_segments`
+    /// rejects and `RouterError::InvalidPath` for paths deeper than `max_depth`.
+    pub(crate) fn prepare_request_path(
+        &self,
+        method: &str,
+        path: &str,
+    ) -> Result<RequestPath<'static>, RouterError> {
+        self.config.prepare_request_path(method, path)
+    }
+
+    /// Prepares a path that is already normalized, borrowing it unless it has
+    /// to be percent-decoded. Errors as `prepare_request_path` does.
+    pub(crate) fn prepare_normalized_path<'a>(
+        &self,
+        normalized: &'a str,
+    ) -> Result<RequestPath<'a>, RouterError> {
+        self.config.prepare_normalized_path(normalized)
+    }
+}
+
+/// Whether `path` ends in a slash that follows a non-empty segment (i.e., isn't just `/`).
+fn has_trailing_slash(path: &str) -> bool {
+    path.ends_with('/') && !path.trim_matches('/').is_empty()
+}
+
+/// A node of a routing tree, as yielded by [`iter_nodes`].
+#[derive(Debug)]
+pub struct NodeRef<'a, T> {
+    /// The segments leading to the node, with static segments verbatim, `:*` for a
+    /// parametric segment, and `**` for a wildcard (e.g., `/users/:*/files/**`).
+    /// The root's prefix is empty unless `compress` gave it an edge label.
+    pub path_prefix: String,
+    /// The node itself.
+    pub node: &'a Node<T>,
+    /// The number of segments in `path_prefix`.
+    pub depth: usize,
+}
+
+impl<'a, T> NodeRef<'a, T> {
+    /// The `NodeRef` of `node`, reached from `parent` through `segment`.
+    fn child(parent: &Self, segment: &str, node: &'a Node<T>) -> Self {
+        let mut path_prefix = format!("{}/{segment}", parent.path_prefix);
+        for label_segment in node.edge_label_segments() {
+            path_prefix.push('/');
+            path_prefix.push_str(label_segment);
+        }
+        NodeRef {
+            path_prefix,
+            node,
+            depth: parent.depth + 1 + node.edge_label_segments().count(),
+        }
+    }
+}
+
+/// A depth-first iterator over the nodes of a routing tree, created with [`iter_nodes`].
+///
+/// Nodes are yielded before their children, and children in the order `list_routes`
+/// uses: static (sorted by segment), then parametric, then wildcard.
+#[derive(Debug)]
+pub struct RouterIter<'a, T> {
+    stack: Vec<NodeRef<'a, T>>,
+}
+
+impl<'a, T> Iterator for RouterIter<'a, T> {
+    type Item = NodeRef<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.stack.pop()?;
+        let node = current.node;
+        // Children are pushed in reverse so they are popped in traversal order.
+        if let Some(wildcard_child_node) = &node.wildcard_child {
+            self.stack
+                .push(NodeRef::child(&current, "**", wildcard_child_node));
+        }
+        if let Some(param_child_node) = &node.param_child {
+            self.stack
+                .push(NodeRef::child(&current, ":*", param_child_node));
+        }
+        let mut static_children: Vec<_> = node.static_children.iter().collect();
+        static_children.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+        for (segment, child) in static_children {
+            self.stack.push(NodeRef::child(&current, segment, child));
+        }
+        Some(current)
+    }
+}
+
+/// Iterates over every node of the routing tree rooted at `root`, depth-first.
+///
+/// The tree lives behind the router's lock, so pass the locked root; the lock is
+/// held for as long as the iterator is used:
+///
+/// ```rust
+/// use rou3::{Router, add_route, iter_nodes};
+///
+/// let router = Router::new();
+/// add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+///
+/// let prefixes: Vec<String> = iter_nodes(&router.root.read())
+///     .map(|node_ref| node_ref.path_prefix)
+///     .collect();
+/// assert_eq!(prefixes, ["", "/users", "/users/:*"]);
+/// ```
+///
+/// This is the building block for tree-walking utilities such as `list_routes`,
+/// `memory_usage`, and `compute_trie_stats`.
+pub fn iter_nodes<T>(root: &Node<T>) -> RouterIter<'_, T> {
+    let mut path_prefix = String::new();
+    for label_segment in root.edge_label_segments() {
+        path_prefix.push('/');
+        path_prefix.push_str(label_segment);
+    }
+    RouterIter {
+        stack: vec![NodeRef {
+            path_prefix,
+            node: root,
+            depth: root.edge_label_segments().count(),
+        }],
+    }
+}
+
+/// Routes requests to one of several `Router`s based on the request's host.
+///
+/// Each sub-router is registered under a host pattern: either an exact host
+/// (`api.example.com`), a leading wildcard (`*.example.com`) that matches any
+/// subdomain, or `*` to match every host. See `add_host` and `find_in_host`.
+#[derive(Debug)]
+pub struct VHostRouter<T> {
+    /// Sub-routers keyed by lowercase host pattern.
+    pub hosts: RwLock<AHashMap<String, Router<T>>>,
+}
+
+impl<T> Default for VHostRouter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> VHostRouter<T> {
+    /// Constructs an empty `VHostRouter`.
+    pub fn new() -> Self {
+        Self {
+            hosts: RwLock::new(AHashMap::default()),
+        }
+    }
+}
+
+/// Routes requests to one of several independent `Router`s by namespace name,
+/// such as a tenant id.
+///
+/// Each namespace owns a separate router with its own routes, configuration, and
+/// hooks, so routes added for one tenant can never match another tenant's requests.
+/// Routers are stored behind `Arc`s so a lookup only holds the namespace map's lock
+/// long enough to clone one. See `add_namespace`, `remove_namespace`, and
+/// `find_in_namespace`.
+#[derive(Debug)]
+pub struct NamespaceRouter<T> {
+    /// Routers keyed by namespace name.
+    pub namespaces: RwLock<AHashMap<String, Arc<Router<T>>>>,
+}
+
+impl<T> Default for NamespaceRouter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> NamespaceRouter<T> {
+    /// Constructs an empty `NamespaceRouter`.
+    pub fn new() -> Self {
+        Self {
+            namespaces: RwLock::new(AHashMap::default()),
+        }
+    }
+}
+
+/// A router whose routing tree is read without taking a lock.
+///
+/// The root is held in an `arc_swap::ArcSwap`, so `find_route_arc` only loads a
+/// pointer to the current tree instead of taking the read lock a `Router` takes,
+/// and readers never wait for writers. `add_route_arc` and `remove_route_arc` clone
+/// the whole tree, change the clone, and swap it in, so writes cost a full copy and
+/// are serialized; use this for route tables that are built once and read from many
+/// threads. The static map is still behind a `RwLock`, which only writers hold for
+/// more than a lookup. Hooks are not supported.
+#[cfg(feature = "arc-swap")]
+#[derive(Debug)]
+pub struct ArcRouter<T> {
+    /// The root node of the routing tree, replaced as a whole on every write.
+    pub root: arc_swap::ArcSwap<Box<Node<T>>>,
+    /// Optimized map for purely static routes, as in `Router::static_map`.
+    pub static_map: RwLock<StaticMap<T>>,
+    /// Behavioral options this router was created with.
+    pub config: RouterConfig,
+    /// Serializes writers, so concurrent writes don't drop each other's changes.
+    pub(crate) writer: parking_lot::Mutex<()>,
+}
+
+#[cfg(feature = "arc-swap")]
+impl<T> Default for ArcRouter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "arc-swap")]
+impl<T> ArcRouter<T> {
+    /// Constructs an empty `ArcRouter` with the default options.
+    pub fn new() -> Self {
+        Self::with_config(RouterConfig::default())
+    }
+
+    /// Constructs an empty `ArcRouter` with the given options.
+    pub fn with_config(config: RouterConfig) -> Self {
+        Self {
+            root: arc_swap::ArcSwap::from_pointee(Box::new(Node::new())),
+            static_map: RwLock::new(StaticMap::default()),
+            config,
+            writer: parking_lot::Mutex::new(()),
+        }
+    }
+}
+
+/// A router for purely static routes that never touches the routing tree.
+///
+/// Routes are added with `add_static` and looked up with `find_static`, both of
+/// which only use the wrapped router's `static_map`. Patterns with parameters or
+/// wildcards are rejected, so every lookup is a single hash map probe.
+#[derive(Debug)]
+pub struct ExactRouter<T>(Router<T>);
+
+impl<T: Clone> Default for ExactRouter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> ExactRouter<T> {
+    /// Constructs a new `ExactRouter`.
+    pub fn new() -> Self {
+        Self::with_config(RouterConfig::default())
+    }
+
+    /// Constructs a new `ExactRouter` with the given options.
+    pub fn with_config(config: RouterConfig) -> Self {
+        Self(Router::with_config(config))
+    }
+}
+
+impl<T> ExactRouter<T> {
+    /// Returns the wrapped router. Only its `static_map` is populated.
+    pub(crate) fn inner(&self) -> &Router<T> {
+        &self.0
+    }
+}
+
+/// Registers routes on a router under a shared path prefix.
+///
+/// Every pattern passed to [`RouteGroup::add`] is appended to the group's prefix, so
+/// adding `/users` to a group for `/api/v1` registers `/api/v1/users`. The
+/// `route_group!` macro builds a group and adds a block of routes to it.
+///
+/// # Example
+/// ```rust
+/// use rou3::{RouteGroup, Router, find_route};
+///
+/// let router = Router::new();
+/// let api = RouteGroup::new(&router, "/api/v1");
+/// api.add("GET", "/users/:id", "get_user").unwrap();
+///
+/// assert_eq!(find_route(&router, "GET", "/api/v1/users/7", false).unwrap().data, "get_user");
+/// ```
+#[derive(Debug)]
+pub struct RouteGroup<'r, T> {
+    router: &'r Router<T>,
+    prefix: String,
+}
+
+impl<'r, T: Clone> RouteGroup<'r, T> {
+    /// Constructs a group that adds routes to `router` under `prefix`.
+    pub fn new(router: &'r Router<T>, prefix: &str) -> Self {
+        Self {
+            router,
+            prefix: format!("/{}", normalize(prefix)),
         }
     }
+
+    /// The group's prefix, with a leading slash and no trailing slash (e.g., `/api/v1`).
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Adds a route for `prefix` followed by `path`, as `add_route` would.
+    ///
+    /// # Errors
+    /// Returns the error from `add_route` if the combined pattern is invalid.
+    pub fn add(&self, method: &str, path: &str, data: T) -> Result<(), RouterError> {
+        let pattern = format!(
+            "{}/{}",
+            self.prefix.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        );
+        add_route(self.router, method, &pattern, data)
+    }
+}
+
+/// Iterates over every registered route, in the order returned by `list_routes`.
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, add_route};
+///
+/// let router = Router::new();
+/// add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+/// for route in &router {
+///     assert_eq!((route.method.as_str(), route.pattern.as_str()), ("GET", "/users/:id"));
+/// }
+/// ```
+impl<T: Clone + Eq + core::hash::Hash> IntoIterator for &Router<T> {
+    type Item = RouteEntry<T>;
+    type IntoIter = alloc::vec::IntoIter<RouteEntry<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        list_routes(self).into_iter()
+    }
+}
+
+/// A point-in-time copy of a router's routes, taken with [`snapshot`].
+///
+/// Only the method, pattern, data, and description of each route are captured;
+/// other route metadata, disabled state, and the router's `RouterConfig` are not.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RouterSnapshot<T: Clone> {
+    /// The routes registered when the snapshot was taken, in `list_routes` order.
+    pub routes: Vec<RouteEntry<T>>,
+}
+
+/// Captures the routes currently registered in `router`.
+///
+/// Pass the result to [`restore`] to rebuild an equivalent router later, e.g. to
+/// roll back after experimenting with a modified routing table.
+pub fn snapshot<T: Clone + Eq + core::hash::Hash>(router: &Router<T>) -> RouterSnapshot<T> {
+    RouterSnapshot {
+        routes: list_routes(router),
+    }
+}
+
+/// Builds a fresh `Router` containing the routes captured in `snapshot`.
+///
+/// # Errors
+/// Returns the error from `add_route` for the first route that cannot be added.
+pub fn restore<T: Clone>(snapshot: RouterSnapshot<T>) -> Result<Router<T>, RouterError> {
+    Router::from_entries(snapshot.routes)
+}
+
+/// Adds a route listed by `list_routes`, keeping its description.
+fn add_entry<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    pattern: &str,
+    data: T,
+    description: Option<String>,
+) -> Result<(), RouterError> {
+    match description {
+        Some(description) => {
+            add_route_with_description(router, method, pattern, data, &description)
+        }
+        None => add_route(router, method, pattern, data),
+    }
+}
+
+/// Builds a `Router<U>` with the same routes as `router`, each handler's data
+/// converted with `f`.
+///
+/// This is handy for wrapping every handler in a common type in one go, e.g.
+/// `transform_all(&router, Arc::new)`. The new router has the same `RouterConfig`
+/// (routes are re-added relative to its global prefix, if any). As with
+/// [`snapshot`], only the method, pattern, data, and description of each route are
+/// carried over; other route metadata and hooks are not.
+pub fn transform_all<T: Clone + Eq + core::hash::Hash, U: Clone>(
+    router: &Router<T>,
+    f: impl Fn(T) -> U,
+) -> Router<U> {
+    let prefix_depth = router
+        .config
+        .global_prefix
+        .as_deref()
+        .map_or(0, |prefix| prefix.split('/').count());
+    // Guards are not carried over, so a guarded handler followed by a fallback on the
+    // same pattern would look shadowed; strictness is restored once routes are added.
+    let mut transformed = Router::with_config(RouterConfig {
+        strict_shadowing: false,
+        ..router.config.clone()
+    });
+    for entry in list_routes(router) {
+        let relative_pattern = entry
+            .pattern
+            .split('/')
+            .skip(1 + prefix_depth)
+            .collect::<Vec<_>>()
+            .join("/");
+        add_entry(
+            &transformed,
+            &entry.method,
+            &relative_pattern,
+            f(entry.data),
+            entry.description,
+        )
+        .expect("a pattern listed from a router is valid for the same config");
+    }
+    transformed.config.strict_shadowing = router.config.strict_shadowing;
+    transformed
+}
+
+/// Approximate memory footprint of a router, as reported by [`memory_usage`].
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct MemoryStats {
+    /// The number of nodes in the routing tree, including the root.
+    pub trie_nodes: usize,
+    /// The number of paths in the `static_map`.
+    pub static_entries: usize,
+    /// The number of handlers (`MethodData`) stored in the routing tree.
+    pub total_method_handlers: usize,
+    /// An estimate of the heap memory owned by the router, in bytes. Heap memory
+    /// owned by the route data `T` itself is not included.
+    pub estimated_heap_bytes: usize,
+}
+
+/// Hash maps keep spare capacity; estimate their size as 1.6x their entries.
+fn hash_map_bytes(len: usize, entry_size: usize) -> usize {
+    len * entry_size * 8 / 5
+}
+
+/// `IndexMap` stores entries (with their hashes) in a `Vec` plus a table of indices.
+fn index_map_bytes(capacity: usize, len: usize, entry_size: usize) -> usize {
+    capacity * (entry_size + size_of::<u64>()) + hash_map_bytes(len, size_of::<usize>())
+}
+
+fn method_data_heap_bytes<T>(md: &MethodData<T>) -> usize {
+    let params_bytes = md.params_map.as_ref().map_or(0, |entries| {
+        entries.capacity() * size_of::<ParamEntry>()
+            + entries
+                .iter()
+                .map(|entry| match entry {
+                    ParamEntry::Index(param) => {
+                        param.name.capacity()
+                            + param.allowlist.as_ref().map_or(0, |allowlist| {
+                                allowlist.capacity() * size_of::<String>()
+                                    + allowlist.iter().map(String::capacity).sum::<usize>()
+                            })
+                    }
+                    ParamEntry::Wildcard(_, name, _) | ParamEntry::Repeated(_, name) => {
+                        name.capacity()
+                    }
+                })
+                .sum::<usize>()
+    });
+    let middleware_bytes = md.middleware.capacity() * size_of::<String>()
+        + md.middleware.iter().map(String::capacity).sum::<usize>();
+    let scopes_bytes = md.required_scopes.as_ref().map_or(0, |scopes| {
+        scopes.capacity() * size_of::<String>() + scopes.iter().map(String::capacity).sum::<usize>()
+    });
+    params_bytes + middleware_bytes + scopes_bytes
+}
+
+fn methods_heap_bytes<T>(methods: &StaticPathMethods<T>) -> usize {
+    hash_map_bytes(methods.len(), size_of::<(String, Handlers<T>)>())
+        + methods
+            .iter()
+            .map(|(method, handlers)| {
+                method.capacity()
+                    + handlers_buffer_bytes(handlers)
+                    + handlers.iter().map(method_data_heap_bytes).sum::<usize>()
+            })
+            .sum::<usize>()
+}
+
+#[cfg(not(feature = "smallvec"))]
+fn handlers_buffer_bytes<T>(handlers: &Handlers<T>) -> usize {
+    handlers.capacity() * size_of::<MethodData<T>>()
+}
+
+/// A `SmallVec` only owns heap memory once it spills past its inline capacity.
+#[cfg(feature = "smallvec")]
+fn handlers_buffer_bytes<T>(handlers: &Handlers<T>) -> usize {
+    if handlers.spilled() {
+        handlers.capacity() * size_of::<MethodData<T>>()
+    } else {
+        0
+    }
+}
+
+/// Heap memory owned by `node` itself: its handlers, its child map, the keys and
+/// boxes of its children, and its edge label. Children's contents are not included.
+fn node_heap_bytes<T>(node: &Node<T>) -> usize {
+    let child_count = node.static_children.len()
+        + usize::from(node.param_child.is_some())
+        + usize::from(node.wildcard_child.is_some());
+    methods_heap_bytes(&node.methods)
+        + index_map_bytes(
+            node.static_children.capacity(),
+            node.static_children.len(),
+            size_of::<(String, Box<Node<T>>)>(),
+        )
+        + node
+            .static_children
+            .keys()
+            .map(String::capacity)
+            .sum::<usize>()
+        + child_count * size_of::<Node<T>>()
+        + node.edge_label.capacity()
+}
+
+/// Estimates how much memory `router` uses.
+///
+/// The routing tree is walked to count nodes and handlers, and the heap size is
+/// estimated from the sizes of `Node` and `MethodData`, the strings they own, and
+/// hash map overhead. The result is an approximation intended for monitoring and
+/// comparing configurations, not an exact allocator measurement.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn memory_usage<T>(router: &Router<T>) -> MemoryStats {
+    let mut stats = MemoryStats::default();
+    let root_lock = router.root.read();
+    let mut trie_bytes = size_of::<Node<T>>();
+    for NodeRef { node, .. } in iter_nodes(&root_lock) {
+        stats.trie_nodes += 1;
+        stats.total_method_handlers += node
+            .methods
+            .values()
+            .map(|handlers| handlers.len())
+            .sum::<usize>();
+        trie_bytes += node_heap_bytes(node);
+    }
+    drop(root_lock);
+
+    let static_map_lock = router.static_map.read();
+    stats.static_entries = static_map_lock.len();
+    let static_map_bytes = index_map_bytes(
+        static_map_lock.capacity(),
+        static_map_lock.len(),
+        size_of::<(String, StaticPathMethods<T>)>(),
+    ) + static_map_lock
+        .iter()
+        .map(|(path, methods)| path.capacity() + methods_heap_bytes(methods))
+        .sum::<usize>();
+
+    stats.estimated_heap_bytes = trie_bytes + static_map_bytes;
+    stats
+}
+
+fn shrink_node<T>(node: &mut Node<T>) {
+    node.edge_label.shrink_to_fit();
+    node.methods.shrink_to_fit();
+    node.methods
+        .values_mut()
+        .for_each(|handlers| handlers.shrink_to_fit());
+    node.static_children.shrink_to_fit();
+    for child in node.static_children.values_mut() {
+        shrink_node(child);
+    }
+    for child in node.param_child.iter_mut().chain(&mut node.wildcard_child) {
+        shrink_node(child);
+    }
+}
+
+/// Releases excess capacity held by `router`'s internal maps.
+///
+/// Removing routes leaves the hash maps of the routing tree and the static path map
+/// sized for their former contents. Calling this after unloading a batch of routes
+/// (e.g., when a plugin is removed) returns that memory to the allocator.
+///
+/// Write locks on the routing tree and the static path map are held for the whole
+/// traversal, so concurrent lookups block until it completes.
+///
+/// # Panics
+/// This function may panic if acquiring write locks on the router's internal structures fails.
+pub fn shrink_to_fit<T>(router: &Router<T>) {
+    let mut root_lock = router.root.write();
+    let mut static_map_lock = router.static_map.write();
+    shrink_node(&mut root_lock);
+    static_map_lock.shrink_to_fit();
+    for methods in static_map_lock.values_mut() {
+        methods.shrink_to_fit();
+        methods
+            .values_mut()
+            .for_each(|handlers| handlers.shrink_to_fit());
+    }
+}
+
+/// Shape of a router's routing tree, as reported by [`compute_trie_stats`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrieStats {
+    /// The number of nodes in the routing tree, including the root.
+    pub total_nodes: usize,
+    /// The number of path segments from the root to the deepest node.
+    pub max_depth: usize,
+    /// The average number of children of the nodes that have any, or `0.0` for a
+    /// tree that only has a root.
+    pub avg_branching_factor: f64,
+    /// The number of nodes without children.
+    pub leaf_count: usize,
+    /// The number of nodes reached through a static segment.
+    pub static_node_count: usize,
+    /// The number of nodes reached through a parametric segment (`:name` or `*`).
+    pub param_node_count: usize,
+    /// The number of nodes reached through a wildcard segment (`**`).
+    pub wildcard_node_count: usize,
+    /// The number of handlers per HTTP method, with `""` for handlers of any method.
+    pub handlers_per_method: AHashMap<String, usize>,
+}
+
+/// Describes the shape of `router`'s routing tree in a single traversal.
+///
+/// This is meant for performance auditing: a high `avg_branching_factor` means many
+/// sibling static segments sharing a parent (cheap hash lookups), while a high
+/// `max_depth` points to deeply nested APIs whose lookups walk many nodes. Only the
+/// trie is inspected; the static path map is not.
+///
+/// # Panics
+/// This function may panic if acquiring the read lock on the routing tree fails.
+pub fn compute_trie_stats<T>(router: &Router<T>) -> TrieStats {
+    let mut stats = TrieStats::default();
+    let (mut child_edges, mut inner_nodes) = (0, 0);
+    for NodeRef { node, depth, .. } in iter_nodes(&router.root.read()) {
+        stats.total_nodes += 1;
+        stats.max_depth = stats.max_depth.max(depth);
+        for (method, handlers) in &node.methods {
+            *stats.handlers_per_method.entry(method.clone()).or_default() += handlers.len();
+        }
+
+        // Each child is counted by the kind of edge that leads to it.
+        stats.static_node_count += node.static_children.len();
+        stats.param_node_count += usize::from(node.param_child.is_some());
+        stats.wildcard_node_count += usize::from(node.wildcard_child.is_some());
+        let children = node.static_children.len()
+            + usize::from(node.param_child.is_some())
+            + usize::from(node.wildcard_child.is_some());
+        if children == 0 {
+            stats.leaf_count += 1;
+        } else {
+            child_edges += children;
+            inner_nodes += 1;
+        }
+    }
+    if inner_nodes > 0 {
+        stats.avg_branching_factor = child_edges as f64 / inner_nodes as f64;
+    }
+    stats
+}
+
+fn compress_node<T>(node: &mut Node<T>) {
+    while node.methods.is_empty()
+        && node.param_child.is_none()
+        && node.wildcard_child.is_none()
+        && node.static_children.len() == 1
+        // An empty key (a trailing-slash segment) cannot be told apart from "no label".
+        && node.static_children.keys().all(|key| !key.is_empty())
+    {
+        let Some((key, child)) = node.static_children.pop() else {
+            break;
+        };
+        let child = *child;
+        for label_segment in core::iter::once(key.as_str()).chain(child.edge_label_segments()) {
+            if !node.edge_label.is_empty() {
+                node.edge_label.push('/');
+            }
+            node.edge_label.push_str(label_segment);
+        }
+        node.methods = child.methods;
+        node.static_children = child.static_children;
+        node.param_child = child.param_child;
+        node.wildcard_child = child.wildcard_child;
+    }
+    for child in node.static_children.values_mut() {
+        compress_node(child);
+    }
+    if let Some(param_child_node) = &mut node.param_child {
+        compress_node(param_child_node);
+    }
+}
+
+/// Merges chains of single-child static nodes in `router`'s routing tree.
+///
+/// Any node below the root that has no handlers, no parametric or wildcard child,
+/// and exactly one static child absorbs that child: the child's segment is appended
+/// to the node's `edge_label` and the node takes over the child's handlers and
+/// children. A deep static prefix such as `/a/b/c/d/e/f/:id` is then walked through
+/// one node instead of six.
+///
+/// Routes added afterwards split labels where they diverge, and every lookup,
+/// listing, and removal matches labels transparently, so compressing is invisible
+/// to callers. It only affects the trie; purely static routes are still answered
+/// from the static path map first.
+///
+/// # Panics
+/// This function may panic if acquiring the write lock on the routing tree fails.
+pub fn compress<T>(router: &Router<T>) {
+    let mut root_lock = router.root.write();
+    for child in root_lock.static_children.values_mut() {
+        compress_node(child);
+    }
+    if let Some(param_child_node) = &mut root_lock.param_child {
+        compress_node(param_child_node);
+    }
+}
+
+/// Compresses `router`'s routing tree on demand; an alias of [`compress`].
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, add_route, compact, find_route};
+///
+/// let router = Router::new();
+/// add_route(&router, "GET", "/api/v1/users/settings/:section", "settings").unwrap();
+/// compact(&router);
+/// let matched = find_route(&router, "GET", "/api/v1/users/settings/notifications", false);
+/// assert_eq!(matched.unwrap().data, "settings");
+/// ```
+///
+/// # Panics
+/// This function may panic if acquiring the write lock on the routing tree fails.
+pub fn compact<T: Clone>(router: &Router<T>) {
+    compress(router);
+}
+
+/// A single route as it appears in a JSON route list.
+///
+/// This type only exists to give `serde` a shape to (de)serialize; see
+/// [`Router::from_json_str`] and [`to_json_string`].
+#[cfg(feature = "json_config")]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RouteConfig<T> {
+    /// The HTTP method, or `""` for any method.
+    pub method: String,
+    /// The route pattern (e.g., `/users/:id`).
+    pub path: String,
+    /// The data to associate with the route.
+    pub data: T,
+}
+
+#[cfg(feature = "json_config")]
+impl<T: Clone + serde::de::DeserializeOwned> Router<T> {
+    /// Constructs a `Router` from a JSON array of routes.
+    ///
+    /// The expected format is:
+    ///
+    /// ```json
+    /// [{"method": "GET", "path": "/users/:id", "data": {"handler": "get_user"}}]
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `RouterError::InvalidPath` if the JSON cannot be parsed, or the
+    /// error from `add_route` for the first route with an invalid pattern.
+    pub fn from_json_str(json: &str) -> Result<Router<T>, RouterError> {
+        let routes: Vec<RouteConfig<T>> = serde_json::from_str(json)
+            .map_err(|parse_err| RouterError::InvalidPath(parse_err.to_string()))?;
+        let router = Router::new();
+        for route in routes {
+            add_route(&router, &route.method, &route.path, route.data)?;
+        }
+        Ok(router)
+    }
+}
+
+/// Serializes every route in the router to a JSON array.
+///
+/// The output uses the same format accepted by [`Router::from_json_str`], so a
+/// router can be persisted and rebuilt later:
+///
+/// ```json
+/// [{"method": "GET", "path": "/users/:id", "data": "get_user"}]
+/// ```
+///
+/// Routes are listed in the order returned by `list_routes`.
+///
+/// # Errors
+/// Returns a `serde_json::Error` if `T` fails to serialize.
+#[cfg(feature = "json_config")]
+pub fn to_json_string<T: serde::Serialize + Clone>(
+    router: &Router<T>,
+) -> Result<String, serde_json::Error> {
+    let routes: Vec<RouteConfig<T>> = list_routes(router)
+        .into_iter()
+        .map(|entry| RouteConfig {
+            method: entry.method,
+            path: entry.pattern,
+            data: entry.data,
+        })
+        .collect();
+    serde_json::to_string(&routes)
 }
@@ -1,5 +1,10 @@
 //! Defines the error types used throughout the `rou3` crate.
 
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
 use thiserror::Error;
 
 /// The primary error type for `rou3` operations.
@@ -19,6 +24,31 @@ pub enum RouterError {
         path: String,
     },
 
+    /// Indicates that routes match the path, but none of them for the request's method.
+    /// Frameworks can answer `405 Method Not Allowed` with `allowed` as the `Allow`
+    /// header instead of `404 Not Found`.
+    #[error("method '{method}' not allowed for path '{path}' (allowed: {})", allowed.join(", "))]
+    MethodNotAllowed {
+        /// The HTTP method of the request.
+        method: String,
+        /// The request path.
+        path: String,
+        /// The sorted methods that have a route for the path, including `HEAD` when
+        /// `GET` is allowed.
+        allowed: Vec<String>,
+    },
+
+    /// Indicates that routes match the request, but none of them produces a content
+    /// type the request's `Accept` header allows. Returned by `find_route_accepting`;
+    /// frameworks can answer `406 Not Acceptable` instead of `404 Not Found`.
+    #[error("no acceptable content type for method '{method}' and path '{path}'")]
+    NotAcceptable {
+        /// The HTTP method of the request.
+        method: String,
+        /// The request path.
+        path: String,
+    },
+
     /// Represents an error when attempting to parse or interpret a segment of a path.
     #[error("invalid segment '{segment}': {reason}")]
     InvalidSegment {
@@ -27,4 +57,59 @@ pub enum RouterError {
         /// The reason why the segment is invalid.
         reason: String,
     },
+
+    /// Indicates that the request path matched a route only after removing its trailing
+    /// slash, under `TrailingSlashMode::Redirect`. Callers should redirect to `canonical`.
+    #[error("trailing slash redirect to '{canonical}'")]
+    TrailingSlashRedirect {
+        /// The request path without its trailing slash.
+        canonical: String,
+    },
+
+    /// Indicates that a route would never match because an existing route is always
+    /// chosen over it. Only returned when `RouterConfig::strict_shadowing` is set.
+    #[error("route '{pattern}' is shadowed by existing route '{shadowed_by}'")]
+    ShadowedRoute {
+        /// The pattern of the rejected route.
+        pattern: String,
+        /// The pattern of the existing route that shadows it.
+        shadowed_by: String,
+    },
+
+    /// Indicates that the same method and pattern were registered more than once, so
+    /// only the first handler is ever used. Reported by `validate`.
+    #[error("duplicate route {method} '{pattern}'")]
+    DuplicateRoute {
+        /// The HTTP method of the duplicated route (`""` for any method).
+        method: String,
+        /// The duplicated route pattern.
+        pattern: String,
+    },
+
+    /// Indicates that a bulk operation such as `add_route_multi` only partly succeeded.
+    #[error("only {count} routes added, last error: {last_error}")]
+    PartialSuccess {
+        /// The number of routes that were added.
+        count: usize,
+        /// The error of the last route that could not be added.
+        last_error: Box<RouterError>,
+    },
+}
+
+impl RouterError {
+    /// Builds a `RouteNotFound` error for `method` and `path`.
+    pub(crate) fn not_found(method: &str, path: &str) -> Self {
+        Self::RouteNotFound {
+            method: method.to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    /// Builds a `NotAcceptable` error for `method` and `path`.
+    pub(crate) fn not_acceptable(method: &str, path: &str) -> Self {
+        Self::NotAcceptable {
+            method: method.to_string(),
+            path: path.to_string(),
+        }
+    }
 }
@@ -0,0 +1,200 @@
+//! Renders a router's routing tree in formats meant for documentation and tooling.
+
+use crate::context::{Node, Router};
+#[cfg(feature = "postman")]
+use crate::operations::list_routes;
+#[cfg(feature = "postman")]
+use alloc::string::ToString;
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{Debug, Write};
+
+/// Renders the routing tree of `router` as a Mermaid `graph TD` diagram.
+///
+/// Each trie node becomes a Mermaid node labelled with its path segment: static
+/// segments verbatim (e.g., `/users`), parametric segments as `/*`, and wildcard
+/// segments as `/**` (the placeholders used when walking the tree in `list_routes`). Nodes that
+/// hold handlers list them below the segment as `METHOD: data`, using the `Debug`
+/// representation of the data, with `*` standing for handlers registered for any method.
+/// A node merged by `compress` shows its whole edge label (e.g., `/api/v1/users`).
+///
+/// Edges to static children are solid (`-->`), edges to the parametric child are
+/// dotted (`-.->`), and edges to the wildcard child are dotted and labelled
+/// (`-. ** .->`). Children are emitted in the same order as `list_routes`, so the
+/// output is stable and can be checked into a README or docs page inside a
+/// ```` ```mermaid ```` block.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn export_mermaid<T: Debug>(router: &Router<T>) -> String {
+    let root_lock = router.root.read();
+    let mut out = String::from("graph TD\n");
+    let mut next_id = 0;
+    write_mermaid_node(&root_lock, "/", &mut next_id, &mut out);
+    out
+}
+
+/// Writes `node` and its subtree, returning the Mermaid id assigned to `node`.
+fn write_mermaid_node<T: Debug>(
+    node: &Node<T>,
+    segment: &str,
+    next_id: &mut usize,
+    out: &mut String,
+) -> String {
+    let id = format!("N{next_id}");
+    *next_id += 1;
+
+    let mut label = escape_mermaid(segment);
+    for label_segment in node.edge_label_segments() {
+        let _ = write!(label, "/{}", escape_mermaid(label_segment));
+    }
+    let mut methods: Vec<_> = node.methods.iter().collect();
+    methods.sort_by_key(|(method, _)| method.as_str());
+    for (method, handlers) in methods {
+        let method = if method.is_empty() { "*" } else { method };
+        for md in handlers {
+            let _ = write!(
+                label,
+                "<br/>{}: {}",
+                escape_mermaid(method),
+                escape_mermaid(&format!("{:?}", md.data))
+            );
+        }
+    }
+    let _ = writeln!(out, "    {id}[\"{label}\"]");
+
+    let mut static_keys: Vec<_> = node.static_children.keys().collect();
+    static_keys.sort();
+    for key in static_keys {
+        let child_id =
+            write_mermaid_node(&node.static_children[key], &format!("/{key}"), next_id, out);
+        let _ = writeln!(out, "    {id} --> {child_id}");
+    }
+    if let Some(param_child_node) = &node.param_child {
+        let child_id = write_mermaid_node(param_child_node, "/*", next_id, out);
+        let _ = writeln!(out, "    {id} -.-> {child_id}");
+    }
+    if let Some(wildcard_child_node) = &node.wildcard_child {
+        let child_id = write_mermaid_node(wildcard_child_node, "/**", next_id, out);
+        let _ = writeln!(out, "    {id} -. ** .-> {child_id}");
+    }
+    id
+}
+
+/// Replaces characters that would end a quoted Mermaid label with HTML entity codes.
+fn escape_mermaid(text: &str) -> String {
+    text.replace('#', "#35;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+/// The JSON schema URL identifying a Postman collection in the v2.1 format.
+#[cfg(feature = "postman")]
+const POSTMAN_SCHEMA: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+/// Exports every route of `router` as a Postman collection (v2.1 format), which
+/// Postman can import in one step.
+///
+/// Routes are listed with `list_routes` and grouped into one folder per first path
+/// segment (e.g., `users` for `/users/:id`); routes with a single segment or none
+/// stay at the top level. Each route becomes a request named `METHOD /pattern`, with
+/// `*` for routes registered for any method (sent as `GET`, since a Postman request
+/// needs a method). Parameters become Postman variables: `:id`, `:id?`, `:id+`, and
+/// `**:path` all become `{{id}}` or `{{path}}`, and unnamed `*` and `**` become `{{_}}`.
+/// URLs start with a `{{baseUrl}}` collection variable, preset to `http://localhost`.
+/// A request's description is the route's description or, if it has none, the
+/// `Debug` representation of its data.
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, add_route, export_postman};
+///
+/// let router = Router::new();
+/// add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+///
+/// let collection = export_postman(&router, "Users API");
+/// let request = &collection["item"][0]["item"][0]["request"];
+/// assert_eq!(request["method"], "GET");
+/// assert_eq!(request["url"]["raw"], "{{baseUrl}}/users/{{id}}");
+/// ```
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+#[cfg(feature = "postman")]
+pub fn export_postman<T: Debug + Clone + Eq + core::hash::Hash>(
+    router: &Router<T>,
+    collection_name: &str,
+) -> serde_json::Value {
+    use serde_json::{Value, json};
+
+    let mut items: Vec<Value> = Vec::new();
+    let mut folders: indexmap::IndexMap<String, Vec<Value>> = indexmap::IndexMap::new();
+    for entry in list_routes(router) {
+        let segments: Vec<&str> = entry
+            .pattern
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        let path: Vec<String> = segments
+            .iter()
+            .map(|segment| postman_segment(segment))
+            .collect();
+        let (name, method) = match entry.method.as_str() {
+            "" => (format!("* {}", entry.pattern), "GET"),
+            method => (format!("{method} {}", entry.pattern), method),
+        };
+        let description = entry
+            .description
+            .unwrap_or_else(|| format!("{:?}", entry.data));
+        let item = json!({
+            "name": name,
+            "request": {
+                "method": method,
+                "url": {
+                    "raw": format!("{{{{baseUrl}}}}/{}", path.join("/")),
+                    "host": ["{{baseUrl}}"],
+                    "path": path,
+                },
+                "description": description,
+            },
+        });
+        match segments[..] {
+            [folder, _, ..] => folders.entry(folder.to_string()).or_default().push(item),
+            _ => items.push(item),
+        }
+    }
+    items.extend(
+        folders
+            .into_iter()
+            .map(|(name, item)| json!({ "name": name, "item": item })),
+    );
+
+    json!({
+        "info": {
+            "name": collection_name,
+            "schema": POSTMAN_SCHEMA,
+        },
+        "item": items,
+        "variable": [{ "key": "baseUrl", "value": "http://localhost" }],
+    })
+}
+
+/// Converts one segment of a route pattern to Postman syntax, turning parameters
+/// into `{{name}}` variables.
+#[cfg(feature = "postman")]
+fn postman_segment(segment: &str) -> String {
+    let name = if let Some(name) = segment.strip_prefix("**:") {
+        name
+    } else if let Some(name) = segment.strip_prefix(':') {
+        // `:name<regex>`, `:name?`, and `:name+` all name the variable `name`.
+        name.split('<')
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches(['?', '+'])
+    } else if segment.trim_end_matches('?') == "*" || segment.trim_end_matches('?') == "**" {
+        "_"
+    } else {
+        return segment.to_string();
+    };
+    format!("{{{{{name}}}}}")
+}
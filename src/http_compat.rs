@@ -0,0 +1,35 @@
+//! Integration with the [`http`] crate, enabled by the `http` feature.
+
+use crate::types::MatchedRoute;
+
+/// Converts a matched route into the response its data produces.
+///
+/// This lets routers whose data is a response (or a handler type implementing
+/// `Into<http::Response<B>>`) route and respond in one expression:
+///
+/// ```rust
+/// use rou3::{Router, add_route, find_route};
+///
+/// #[derive(Clone, PartialEq, Eq)]
+/// struct Text(&'static str);
+///
+/// impl From<Text> for http::Response<String> {
+///     fn from(text: Text) -> Self {
+///         http::Response::new(text.0.to_string())
+///     }
+/// }
+///
+/// let router = Router::new();
+/// add_route(&router, "GET", "/hello", Text("Hello!")).unwrap();
+///
+/// let response = http::Response::from(find_route(&router, "GET", "/hello", false).unwrap());
+/// assert_eq!(response.body(), "Hello!");
+/// ```
+impl<T, B> From<MatchedRoute<T>> for http::Response<B>
+where
+    T: Eq + Into<http::Response<B>>,
+{
+    fn from(matched: MatchedRoute<T>) -> Self {
+        matched.data.into()
+    }
+}
@@ -14,7 +14,7 @@
 //! - Method-based routing (GET, POST, etc.), including an "any" method.
 //! - Route removal.
 //! - Parameter extraction.
-//! - Thread-safe router using `parking_lot::RwLock`.
+//! - Thread-safe router using `parking_lot::RwLock` (`spin::RwLock` under `no_std`).
 //! - Efficient data structures (`AHashMap`, `IndexMap`) for performance.
 //! - Structured error handling with `thiserror`.
 //!
@@ -64,15 +64,277 @@
 //! }
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(not(any(feature = "std", feature = "no_std")))]
+compile_error!("rou3 requires either the `std` feature (on by default) or the `no_std` feature");
+
+mod compat;
 pub mod context;
 pub mod error;
+pub mod export;
+#[cfg(feature = "http")]
+mod http_compat;
+#[cfg(feature = "openapi")]
+pub mod openapi;
 pub mod operations;
+#[cfg(feature = "proptest")]
+pub mod proptest_strategies;
+#[cfg(feature = "serde_json")]
+pub mod serde_compat;
+#[cfg(any(feature = "fuzzing", feature = "testing"))]
+pub mod testing;
 pub mod types;
 
+#[cfg(feature = "arc-swap")]
+pub use context::ArcRouter;
+pub use context::ExactRouter;
+pub use context::Handlers;
+pub use context::MatchHook;
+pub use context::MemoryStats;
+pub use context::NamespaceRouter;
+pub use context::NodeRef;
+pub use context::RouteGroup;
+pub use context::RouteHook;
 pub use context::Router;
+pub use context::RouterConfig;
+pub use context::RouterConfigBuilder;
+pub use context::RouterIter;
+pub use context::RouterSnapshot;
+pub use context::TrieStats;
+pub use context::VHostRouter;
+pub use context::compact;
+pub use context::compress;
+pub use context::compute_trie_stats;
+pub use context::iter_nodes;
+pub use context::memory_usage;
+pub use context::restore;
+pub use context::shrink_to_fit;
+pub use context::snapshot;
+pub use context::transform_all;
 pub use error::RouterError;
+pub use export::export_mermaid;
+#[cfg(feature = "postman")]
+pub use export::export_postman;
+#[cfg(feature = "openapi")]
+pub use openapi::from_openapi_paths;
+pub use operations::AmbiguityReport;
+pub use operations::HotPathSuggestion;
+pub use operations::RouterDiff;
+pub use operations::RouterValidation;
+pub use operations::RouterWarning;
+pub use operations::add_deprecated_route;
+pub use operations::add_host;
+pub use operations::add_namespace;
 pub use operations::add_route;
+#[cfg(feature = "arc-swap")]
+pub use operations::add_route_arc;
+pub use operations::add_route_for_host;
+pub use operations::add_route_multi;
+pub use operations::add_route_producing;
+pub use operations::add_route_shared;
+pub use operations::add_route_with_allowlist;
+#[cfg(feature = "serde_json")]
+pub use operations::add_route_with_attrs;
+pub use operations::add_route_with_cache;
+pub use operations::add_route_with_cors;
+pub use operations::add_route_with_defaults;
+pub use operations::add_route_with_description;
+pub use operations::add_route_with_guard;
+pub use operations::add_route_with_middleware;
+pub use operations::add_route_with_priority;
+pub use operations::add_route_with_rate_limit;
+pub use operations::add_route_with_scopes;
+pub use operations::add_route_with_timeout;
+pub use operations::add_route_with_transforms;
+pub use operations::add_static;
+pub use operations::add_upgrade_route;
+pub use operations::add_versioned_routes;
+pub use operations::add_weighted_route;
+pub use operations::analyze_hot_paths;
+pub use operations::diff_routers;
+pub use operations::disable_route;
+pub use operations::drain_method_routes;
+pub use operations::enable_route;
 pub use operations::find_all_routes;
+#[cfg(feature = "rayon")]
+pub use operations::find_all_routes_parallel;
+pub use operations::find_ambiguous_routes;
+pub use operations::find_in_host;
+pub use operations::find_in_namespace;
 pub use operations::find_route;
+pub use operations::find_route_accepting;
+#[cfg(feature = "arc-swap")]
+pub use operations::find_route_arc;
+pub use operations::find_route_best;
+pub use operations::find_route_borrowed;
+pub use operations::find_route_chain;
+pub use operations::find_route_for_request;
+pub use operations::find_route_normalized;
+pub use operations::find_route_segments;
+#[cfg(feature = "tracing")]
+pub use operations::find_route_traced;
+#[cfg(feature = "rand")]
+pub use operations::find_route_weighted;
+pub use operations::find_route_with_host;
+pub use operations::find_route_with_mode;
+pub use operations::find_route_with_query;
+pub use operations::find_routes_batch;
+pub use operations::find_static;
+pub use operations::get_options_response;
+pub use operations::get_route_handle;
+pub use operations::list_api_versions;
+pub use operations::list_routes;
+pub use operations::remove_namespace;
 pub use operations::remove_route;
+#[cfg(feature = "arc-swap")]
+pub use operations::remove_route_arc;
+pub use operations::route_count;
+pub use operations::route_schemas;
+pub use operations::validate;
+#[cfg(feature = "serde_json")]
+pub use serde_compat::from_json_value;
+#[cfg(feature = "serde_json")]
+pub use serde_compat::to_json_value;
+pub use types::BorrowedMatchedRoute;
+pub use types::CacheConfig;
+pub use types::CorsConfig;
+pub use types::DeprecationInfo;
+pub use types::IndexParam;
+pub use types::MatchMode;
+pub use types::MatchResult;
 pub use types::MatchedRoute;
+pub use types::NormalizedPath;
+pub use types::OptionsResponse;
+#[cfg(feature = "regex")]
+pub use types::ParamConstraint;
+pub use types::ParamKind;
+pub use types::ParamSchema;
+pub use types::ParamTransform;
+pub use types::RateLimit;
+pub use types::RoutableRequest;
+pub use types::RouteEntry;
+pub use types::RouteGuard;
+pub use types::RouteSchema;
+pub use types::TrailingSlashMode;
+pub use types::UpgradeInfo;
+pub use types::WildcardPreference;
+
+/// The core router operations as an object-safe trait.
+///
+/// Code that accepts `&dyn RouterInterface<T>` instead of `&Router<T>` can be
+/// exercised in unit tests with a mock or fake implementation that returns
+/// pre-programmed results.
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, RouterInterface};
+///
+/// fn handle(router: &dyn RouterInterface<&'static str>, path: &str) -> Option<&'static str> {
+///     router.find("GET", path, false).ok().map(|matched| matched.data)
+/// }
+///
+/// let router = Router::new();
+/// router.add("GET", "/health", "health").unwrap();
+/// assert_eq!(handle(&router, "/health"), Some("health"));
+/// ```
+pub trait RouterInterface<T: Clone + Eq> {
+    /// Adds a route; see [`add_route`].
+    fn add(&self, method: &str, path: &str, data: T) -> Result<(), RouterError>;
+
+    /// Finds a route; see [`find_route`].
+    fn find(&self, method: &str, path: &str, capture: bool)
+    -> Result<MatchedRoute<T>, RouterError>;
+
+    /// Removes a route; see [`remove_route`].
+    fn remove(&self, method: &str, path: &str) -> Result<bool, RouterError>;
+}
+
+impl<T: Clone + Eq> RouterInterface<T> for Router<T> {
+    fn add(&self, method: &str, path: &str, data: T) -> Result<(), RouterError> {
+        add_route(self, method, path, data)
+    }
+
+    fn find(
+        &self,
+        method: &str,
+        path: &str,
+        capture: bool,
+    ) -> Result<MatchedRoute<T>, RouterError> {
+        find_route(self, method, path, capture)
+    }
+
+    fn remove(&self, method: &str, path: &str) -> Result<bool, RouterError> {
+        remove_route(self, method, path)
+    }
+}
+
+/// Adds routes with a compact `METHOD "pattern" => data` syntax.
+///
+/// Each route expands to an `add_route` call. By default a rejected pattern panics
+/// with the method and pattern in the message; starting the arguments with `?`
+/// propagates the `RouterError` with `?` instead.
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, RouterError, find_route, route};
+///
+/// let router = Router::new();
+/// route!(router, GET "/users/:id" => "get_user", POST "/users" => "create_user");
+///
+/// fn add_health(router: &Router<&'static str>) -> Result<(), RouterError> {
+///     route!(?router, GET "/health" => "health");
+///     Ok(())
+/// }
+/// add_health(&router).unwrap();
+///
+/// assert_eq!(find_route(&router, "POST", "/users", false).unwrap().data, "create_user");
+/// assert_eq!(find_route(&router, "GET", "/health", false).unwrap().data, "health");
+/// ```
+#[macro_export]
+macro_rules! route {
+    (? $router:expr, $($method:ident $path:literal => $data:expr),+ $(,)?) => {{
+        $( $crate::add_route(&$router, stringify!($method), $path, $data)?; )+
+    }};
+    ($router:expr, $($method:ident $path:literal => $data:expr),+ $(,)?) => {{
+        $(
+            $crate::add_route(&$router, stringify!($method), $path, $data)
+                .expect(concat!("invalid route ", stringify!($method), " ", $path));
+        )+
+    }};
+}
+
+/// Adds a block of routes under a shared prefix using a [`RouteGroup`].
+///
+/// Routes use the same `METHOD "pattern" => data` syntax as [`route!`], and the
+/// leading `?` form likewise propagates errors instead of panicking.
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, find_route, route_group};
+///
+/// let router = Router::new();
+/// route_group!(router, "/api/v1", {
+///     GET "/users" => "list_users",
+///     POST "/users" => "create_user",
+/// });
+///
+/// assert_eq!(find_route(&router, "GET", "/api/v1/users", false).unwrap().data, "list_users");
+/// ```
+#[macro_export]
+macro_rules! route_group {
+    (? $router:expr, $prefix:expr, { $($method:ident $path:literal => $data:expr),* $(,)? }) => {{
+        let group = $crate::RouteGroup::new(&$router, $prefix);
+        $( group.add(stringify!($method), $path, $data)?; )*
+    }};
+    ($router:expr, $prefix:expr, { $($method:ident $path:literal => $data:expr),* $(,)? }) => {{
+        let group = $crate::RouteGroup::new(&$router, $prefix);
+        $(
+            group
+                .add(stringify!($method), $path, $data)
+                .expect(concat!("invalid route ", stringify!($method), " ", $path));
+        )*
+    }};
+}
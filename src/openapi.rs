@@ -0,0 +1,62 @@
+//! Building a `Router` from an OpenAPI 3.0 document, enabled by the `openapi` feature.
+
+use crate::{
+    context::Router,
+    error::RouterError,
+    operations::{add_route, util::url_template_to_pattern},
+};
+use serde_json::Value;
+
+/// The path item keys that name an HTTP operation in OpenAPI 3.0.
+const OPERATION_METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Constructs a `Router` from the `paths` object of an OpenAPI 3.0 document.
+///
+/// Every operation (`get`, `post`, ...) of every path item is added as a route with
+/// the method in upper case and `T::default()` as its data; callers replace the data
+/// afterwards. Path templates are converted to rou3 syntax: `{param}` becomes
+/// `:param` and `{+param}` becomes `**:param` (see [`url_template_to_pattern`]).
+/// Other path item fields, such as `parameters` or `summary`, are ignored.
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, find_route, from_openapi_paths};
+///
+/// let paths = serde_json::json!({
+///     "/users/{id}": { "get": {}, "delete": {} },
+///     "/files/{+path}": { "get": {} }
+/// });
+/// let router: Router<String> = from_openapi_paths(&paths).unwrap();
+/// assert!(find_route(&router, "DELETE", "/users/42", false).is_ok());
+/// assert!(find_route(&router, "GET", "/files/a/b.txt", false).is_ok());
+/// ```
+///
+/// # Errors
+/// Returns `RouterError::InvalidPath` if `paths` or a path item is not an object, or
+/// if a path template uses expressions rou3 cannot represent (e.g., `/files/{name}.txt`).
+/// Errors from `add_route` for the converted patterns are returned unchanged.
+pub fn from_openapi_paths<T: Default + Clone>(paths: &Value) -> Result<Router<T>, RouterError> {
+    let paths = paths
+        .as_object()
+        .ok_or_else(|| RouterError::InvalidPath("OpenAPI paths must be an object".to_string()))?;
+    let router = Router::new();
+    for (template, path_item) in paths {
+        let path_item = path_item.as_object().ok_or_else(|| {
+            RouterError::InvalidPath(format!("path item for '{template}' must be an object"))
+        })?;
+        let pattern = url_template_to_pattern(template)?;
+        for method in OPERATION_METHODS {
+            if path_item.contains_key(method) {
+                add_route(
+                    &router,
+                    &method.to_ascii_uppercase(),
+                    &pattern,
+                    T::default(),
+                )?;
+            }
+        }
+    }
+    Ok(router)
+}
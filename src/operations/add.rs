@@ -1,9 +1,23 @@
+use crate::compat::{AHashMap, HashSet};
+#[cfg(feature = "regex")]
+use crate::types::ParamConstraint;
 use crate::{
-    context::{Node, Router},
+    context::{Handlers, Node, Router},
     error::RouterError,
-    operations::util::{normalize, split_path},
-    types::{MethodData, ParamEntry},
+    operations::util::{is_wildcard_segment, render_handler_pattern, split_path},
+    types::{
+        CacheConfig, CorsConfig, DeprecationInfo, IndexParam, MethodData, ParamEntry,
+        ParamTransform, RateLimit, UpgradeInfo,
+    },
 };
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::sync::atomic::Ordering;
 
 /// Parses path segments to identify and map named parameters, wildcards, and optional segments.
 ///
@@ -66,22 +80,56 @@ pub(crate) fn build_param_entries_for_pattern_segments(
                 });
             }
             break;
+        } else if let Some(repeated_name) = segment_str
+            .strip_prefix(':')
+            .and_then(|name| name.strip_suffix('+'))
+        {
+            has_params = true;
+            if repeated_name.is_empty() {
+                return Err(RouterError::InvalidSegment {
+                    segment: segment_str.to_string(),
+                    reason: "repeated parameter must have a name".to_string(),
+                });
+            }
+            if is_segment_optional {
+                return Err(RouterError::InvalidSegment {
+                    segment: format!("{segment_str}?"),
+                    reason: "repeated parameter (:name+) cannot be optional".to_string(),
+                });
+            }
+            if i < segments.len() - 1 {
+                return Err(RouterError::InvalidSegment {
+                    segment: segment_str.to_string(),
+                    reason: "repeated parameter (:name+) must be the last segment".to_string(),
+                });
+            }
+            params_map.push(ParamEntry::Repeated(i, repeated_name.to_string()));
+            break;
         } else if let Some(stripped_name) = segment_str.strip_prefix(':') {
             has_params = true;
+            // The regex of a `:name<regex>` constraint is parsed by `build_param_constraints`.
+            #[cfg(feature = "regex")]
+            let stripped_name = stripped_name
+                .split_once('<')
+                .map_or(stripped_name, |(name, _)| name);
             if stripped_name.is_empty() {
                 return Err(RouterError::InvalidSegment {
                     segment: segment_str.to_string(),
                     reason: "named parameter must have a name".to_string(),
                 });
             }
-            params_map.push(ParamEntry::Index(
+            params_map.push(ParamEntry::Index(IndexParam::new(
                 i,
                 stripped_name.to_string(),
                 is_segment_optional,
-            ));
+            )));
         } else if segment_str == "*" {
             has_params = true;
-            params_map.push(ParamEntry::Index(i, "_".to_string(), is_segment_optional));
+            params_map.push(ParamEntry::Index(IndexParam::new(
+                i,
+                "_".to_string(),
+                is_segment_optional,
+            )));
         } else if segment_str.contains([':', '*'].as_ref()) {
             return Err(RouterError::InvalidSegment {
                 segment: segment_str.to_string(),
@@ -90,6 +138,24 @@ pub(crate) fn build_param_entries_for_pattern_segments(
         }
     }
 
+    {
+        let mut seen_names = HashSet::default();
+        for entry in &params_map {
+            let (name, segment) = match entry {
+                ParamEntry::Index(IndexParam { name, .. }) => (name, format!(":{name}")),
+                ParamEntry::Wildcard(_, name, _) => (name, format!("**:{name}")),
+                ParamEntry::Repeated(_, name) => (name, format!(":{name}+")),
+            };
+            // Unnamed `*` and `**` segments all capture as `_`, so they may repeat.
+            if name != "_" && !seen_names.insert(name.as_str()) {
+                return Err(RouterError::InvalidSegment {
+                    segment,
+                    reason: format!("duplicate parameter name '{name}' in pattern"),
+                });
+            }
+        }
+    }
+
     if has_params {
         Ok(Some(params_map))
     } else {
@@ -97,6 +163,42 @@ pub(crate) fn build_param_entries_for_pattern_segments(
     }
 }
 
+/// Compiles the regex constraints of the `:name<regex>` segments of a route pattern.
+///
+/// Each regex is anchored so it must match a whole segment. Regexes cannot contain
+/// `/`, since patterns are split into segments first.
+#[cfg(feature = "regex")]
+fn build_param_constraints(segments: &[&str]) -> Result<Option<Vec<ParamConstraint>>, RouterError> {
+    let mut constraints = Vec::new();
+    for (segment_idx, segment) in segments.iter().enumerate() {
+        let segment = segment.strip_suffix('?').unwrap_or(segment);
+        let Some((_, constraint)) = segment
+            .strip_prefix(':')
+            .and_then(|name| name.split_once('<'))
+        else {
+            continue;
+        };
+        let Some(source) = constraint.strip_suffix('>') else {
+            return Err(RouterError::InvalidSegment {
+                segment: segment.to_string(),
+                reason: "regex constraint must end with '>'".to_string(),
+            });
+        };
+        let regex = regex::Regex::new(&format!("^(?:{source})$")).map_err(|_| {
+            RouterError::InvalidSegment {
+                segment: segment.to_string(),
+                reason: "invalid regex".to_string(),
+            }
+        })?;
+        constraints.push(ParamConstraint {
+            segment_idx,
+            source: source.to_string(),
+            regex,
+        });
+    }
+    Ok((!constraints.is_empty()).then_some(constraints))
+}
+
 /// Creates a new `Node<T>` instance, boxed for heap allocation.
 /// This is a helper function to reduce boilerplate when creating new nodes,
 /// especially for insertion into `AHashMap` or `Option` fields within another `Node`.
@@ -114,6 +216,9 @@ fn new_node_boxed<T>() -> Box<Node<T>> {
 /// If the path is purely static (no parameters or wildcards), it's also added to a
 /// separate `static_map` in the `Router` for potentially faster lookups.
 ///
+/// If the router was created with `Router::with_global_prefix`, the prefix is
+/// prepended to `path` before it is parsed.
+///
 /// # Arguments
 /// * `router`: A reference to the `Router` instance.
 /// * `method`: The HTTP method (e.g., "GET", "POST") for this route. An empty string `""`
@@ -135,34 +240,691 @@ pub fn add_route<T: Clone>(
     path: &str,
     data: T,
 ) -> Result<(), RouterError> {
-    let normalized_path_string = normalize(path);
+    insert_route(router, method, path, data, |_| {})
+}
+
+/// Adds the same route under several path patterns, e.g. URL aliases such as
+/// `/profile` and `/me`.
+///
+/// Each pattern is added with `add_route` and its own clone of `data`. A pattern
+/// that fails does not stop the others from being added.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for these routes (`""` for any method).
+/// * `paths`: The path patterns to register `data` under.
+/// * `data`: The data or handler to associate with every pattern.
+///
+/// # Returns
+/// * `Result<usize, RouterError>`: `Ok` with the number of routes added when every
+///   pattern was valid. Otherwise `RouterError::PartialSuccess`, carrying how many
+///   patterns were added and the error of the last one that failed.
+pub fn add_route_multi<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    paths: &[&str],
+    data: T,
+) -> Result<usize, RouterError> {
+    let mut count = 0;
+    let mut errors = Vec::new();
+    for path in paths {
+        match add_route(router, method, path, data.clone()) {
+            Ok(()) => count += 1,
+            Err(add_err) => errors.push(add_err),
+        }
+    }
+    match errors.pop() {
+        None => Ok(count),
+        Some(last_error) => Err(RouterError::PartialSuccess {
+            count,
+            last_error: Box::new(last_error),
+        }),
+    }
+}
+
+/// Adds a route to the router, tagged with the names of the middleware that
+/// should wrap its handler.
+///
+/// The names are opaque to the router: they are returned unchanged in
+/// `MatchedRoute::middleware` so framework integrations can assemble the
+/// per-route middleware stack, in the order given.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this route.
+/// * `middleware`: The middleware names to attach to this route.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_route_with_middleware<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    middleware: &[&str],
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| {
+        md.middleware = middleware.iter().map(|name| (*name).to_string()).collect();
+    })
+}
+
+/// Adds a route to the router that requires the given authorization scopes.
+///
+/// The scopes are returned in `MatchedRoute::required_scopes` so OAuth2 or JWT
+/// middleware can compare them against the scopes granted to the caller's token.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this route.
+/// * `scopes`: The scopes a caller must hold to access this route.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_route_with_scopes<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    scopes: &[&str],
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| {
+        md.required_scopes = Some(scopes.iter().map(|scope| (*scope).to_string()).collect());
+    })
+}
+
+/// Adds a route to the router with a request timeout.
+///
+/// The timeout is returned in `MatchedRoute::timeout_ms` so timeout middleware can
+/// enforce a per-route deadline on the handler.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this route.
+/// * `timeout_ms`: The handler's deadline, in milliseconds.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_route_with_timeout<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    timeout_ms: u64,
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| {
+        md.timeout_ms = Some(timeout_ms);
+    })
+}
+
+/// Adds a route to the router and marks it as deprecated.
+///
+/// The deprecation details are returned in `MatchedRoute::deprecated` so
+/// middleware can emit `Deprecation` and `Sunset` response headers (RFC 8594)
+/// without touching the handler.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this route.
+/// * `since`: When the route was deprecated (e.g., a date or version).
+/// * `sunset`: When the route will be removed, if scheduled.
+/// * `replacement`: The route or URL clients should migrate to, if any.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_deprecated_route<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    since: &str,
+    sunset: Option<&str>,
+    replacement: Option<&str>,
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| {
+        md.deprecated = Some(DeprecationInfo {
+            since: since.to_string(),
+            sunset: sunset.map(str::to_string),
+            replacement: replacement.map(str::to_string),
+        });
+    })
+}
+
+/// Adds a route to the router with a rate limit.
+///
+/// The limit is returned in `MatchedRoute::rate_limit` so throttling middleware
+/// (token bucket, sliding window, ...) can apply it per route.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this route.
+/// * `rps`: The sustained number of requests allowed per second.
+/// * `burst`: The number of requests allowed in a burst above `rps`.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_route_with_rate_limit<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    rps: u32,
+    burst: u32,
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| {
+        md.rate_limit = Some(RateLimit {
+            requests_per_second: rps,
+            burst,
+        });
+    })
+}
+
+/// Adds a route to the router with a CORS policy.
+///
+/// The policy is returned in `MatchedRoute::cors` so CORS middleware can emit the
+/// `Access-Control-*` headers for this route without a separate configuration.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this route.
+/// * `cors`: The CORS policy of the route.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_route_with_cors<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    cors: CorsConfig,
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| md.cors = Some(cors))
+}
+
+/// Adds a route to the router with an HTTP caching policy.
+///
+/// The policy is returned in `MatchedRoute::cache` so caching middleware can set the
+/// `Cache-Control` header for this route.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this route.
+/// * `cache`: The caching policy of the route.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_route_with_cache<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    cache: CacheConfig,
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| md.cache = Some(cache))
+}
+
+/// Adds a route to the router with an explicit priority.
+///
+/// Several handlers can be registered for the same method on the same pattern node
+/// (e.g., `/users/:id` and `/users/:name`, or guarded variants of one route). They
+/// are tried from the highest priority down, so a higher-priority handler is chosen
+/// over handlers registered before it. Routes added without a priority have
+/// priority `0`; equal priorities keep registration order.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this route.
+/// * `priority`: The precedence of this handler; higher is tried first.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_route_with_priority<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    priority: i32,
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| md.priority = priority)
+}
+
+/// Adds a route to the router with a human-readable description.
+///
+/// The description is returned in `MatchedRoute::description` and
+/// `RouteEntry::description`, for API documentation, admin dashboards, and other
+/// developer tools.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this route.
+/// * `description`: What the route does (e.g., "Fetches a user by id").
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_route_with_description<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    description: &str,
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| {
+        md.description = Some(description.to_string());
+    })
+}
+
+/// Adds a route to the router with arbitrary JSON attributes.
+///
+/// The attributes are returned in `MatchedRoute::attributes`, for per-route settings
+/// that the typed metadata (`CacheConfig`, `CorsConfig`, ...) does not cover, such
+/// as feature flags, A/B test weights, or log levels. The router never reads them.
+/// Later pairs replace earlier ones with the same key.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this route.
+/// * `attrs`: `(key, value)` pairs of attributes.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+#[cfg(feature = "serde_json")]
+pub fn add_route_with_attrs<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    attrs: &[(&str, serde_json::Value)],
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| {
+        md.attributes = Some(
+            attrs
+                .iter()
+                .map(|(key, value)| ((*key).to_string(), value.clone()))
+                .collect(),
+        );
+    })
+}
+
+/// Adds a route that only serves requests for hosts matching `host_pattern`.
+///
+/// `host_pattern` is matched case-insensitively against the request's host (without
+/// its port) by `find_route_with_host`, and may use `*` to match any run of
+/// characters (e.g., `*.example.com` or `api-*.internal`). Several handlers for
+/// different hosts can share a method and path, so one router can serve several
+/// domains. Lookups that are not given a host, such as `find_route`, ignore host
+/// constraints.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `host_pattern`: The hosts this route serves (e.g., "api.example.com").
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this route.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_route_for_host<T: Clone>(
+    router: &Router<T>,
+    host_pattern: &str,
+    method: &str,
+    path: &str,
+    data: T,
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| {
+        md.host_constraint = Some(host_pattern.to_ascii_lowercase());
+    })
+}
+
+/// Adds a route that responds with one of `content_types` (e.g., `application/json`).
+///
+/// `find_route_accepting` uses these media types to pick among several handlers for
+/// the same method and path by the request's `Accept` header, so one pattern can have
+/// a JSON and an XML handler. Lookups that are not given an `Accept` header, such as
+/// `find_route`, ignore content types.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this route.
+/// * `content_types`: The media types the route can respond with, without parameters.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_route_producing<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    content_types: &[&str],
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| {
+        md.produces = Some(
+            content_types
+                .iter()
+                .map(|content_type| content_type.trim().to_ascii_lowercase())
+                .collect(),
+        );
+    })
+}
+
+/// Adds one weighted variant of a route, for canary deployments and A/B tests.
+///
+/// Several handlers can be added for the same method and pattern with different
+/// weights; `find_route_weighted` then picks one of them at random for each request,
+/// in proportion to their weights (e.g., 95 and 5 send 5% of requests to a canary).
+/// A weight of `0` disables the variant for weighted lookups. Lookups without a random
+/// number generator, such as `find_route`, always return the first variant.
+///
+/// Weighted variants are never reported as shadowing or duplicating each other,
+/// whatever their weights, so 1 and 1 is an even split.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this variant.
+/// * `weight`: The variant's relative share of requests.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_weighted_route<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    weight: u32,
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| md.weight = Some(weight))
+}
+
+/// Adds a route that upgrades the connection to another protocol, such as WebSocket.
+///
+/// The route is registered for `GET`, the only method an HTTP/1.1 upgrade handshake
+/// (RFC 6455 for WebSocket) may use. The protocol details are returned in
+/// `MatchedRoute::upgrade`, so framework integrations can check
+/// `matched.upgrade.is_some()` to perform the handshake instead of sending a
+/// regular response.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `path`: The path pattern for the route (e.g., "/ws/:room").
+/// * `data`: The data or handler to associate with this route.
+/// * `protocol`: The protocol to upgrade to (e.g., "websocket").
+/// * `subprotocols`: The subprotocols the route supports, in order of preference.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_upgrade_route<T: Clone>(
+    router: &Router<T>,
+    path: &str,
+    data: T,
+    protocol: &str,
+    subprotocols: &[&str],
+) -> Result<(), RouterError> {
+    insert_route(router, "GET", path, data, |md| {
+        md.upgrade = Some(UpgradeInfo {
+            protocol: protocol.to_string(),
+            subprotocols: subprotocols.iter().map(|s| s.to_string()).collect(),
+        });
+    })
+}
+
+/// Adds a route to the router that only matches while `guard` accepts the request.
+///
+/// During lookup the guard is called with the request method and the normalized
+/// request path (as in `MatchedRoute::matched_path`). When it returns `false` the
+/// handler is skipped as if it were disabled, so lookup falls through to the next
+/// candidate: another handler for the same pattern, a parametric or wildcard route,
+/// or `RouterError::RouteNotFound`. This allows time-of-day, feature-flag, or A/B
+/// routing without changing the routing tree. Guards run on every lookup that
+/// reaches the route, so they should be cheap.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this route.
+/// * `guard`: The predicate deciding whether the route may match a request.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_route_with_guard<T: Clone, F: Fn(&str, &str) -> bool + Send + Sync + 'static>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    guard: F,
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| {
+        md.guard = Some(Arc::new(guard));
+    })
+}
+
+/// Adds a route to the router that transforms one of its parameter values.
+///
+/// When the route matches with parameter capture enabled, the value captured for
+/// `param` is passed through `transform` and the result is returned in
+/// `MatchedRoute::params`. This allows per-route decoding, case normalization, or
+/// alias expansion without framework middleware. A `param` the pattern does not
+/// define is never captured, so its transform is never called.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `data`: The data or handler to associate with this route.
+/// * `param`: The name of the parameter to transform (e.g., "id").
+/// * `transform`: The function applied to the captured value.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_route_with_transforms<T: Clone, F: Fn(&str) -> String + Send + Sync + 'static>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    param: &str,
+    transform: F,
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| {
+        let transform: ParamTransform = Arc::new(transform);
+        md.transforms = Some(AHashMap::from_iter([(param.to_string(), transform)]));
+    })
+}
+
+/// Adds a route to the router with default values for its optional parameters.
+///
+/// When the route matches with parameter capture enabled and an optional parameter
+/// (e.g., `query` in `/search/:query?`) is absent from the request path, its value
+/// from `defaults` is returned in `MatchedRoute::params`. Defaults for parameters
+/// that are present, required, or not in the pattern are ignored.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/search/:query?").
+/// * `data`: The data or handler to associate with this route.
+/// * `defaults`: `(name, value)` pairs giving the default for each optional parameter.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+pub fn add_route_with_defaults<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    defaults: &[(&str, &str)],
+) -> Result<(), RouterError> {
+    insert_route(router, method, path, data, |md| {
+        md.defaults = Some(
+            defaults
+                .iter()
+                .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+                .collect(),
+        );
+    })
+}
+
+/// Adds a route to the router that only matches when some of its parameters take
+/// one of a fixed set of values.
+///
+/// A request whose segment for an allowlisted parameter is not in that parameter's
+/// list does not match this route, and lookups fall through to the next candidate
+/// route as they do for a guard. Allowlists for parameters that are not single
+/// segment parameters (`:name`) of the pattern are rejected, so a misspelled name
+/// does not register an unrestricted route.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/paint/:color").
+/// * `data`: The data or handler to associate with this route.
+/// * `allowlists`: `(name, allowed_values)` pairs giving the values each parameter
+///   may take.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`, or
+///   `RouterError::InvalidPath` if an allowlist names a parameter the pattern lacks.
+pub fn add_route_with_allowlist<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    allowlists: &[(&str, &[&str])],
+) -> Result<(), RouterError> {
+    let normalized_path_string = router.normalize_pattern(path)?;
+    let segments: Vec<&str> = split_path(&normalized_path_string).collect();
+    let params_map = build_param_entries_for_pattern_segments(&segments)?;
+    for (name, _) in allowlists {
+        let is_param = params_map
+            .iter()
+            .flatten()
+            .any(|entry| matches!(entry, ParamEntry::Index(param) if param.name == *name));
+        if !is_param {
+            return Err(RouterError::InvalidPath(format!(
+                "allowlist for unknown parameter '{name}' in pattern '{path}'"
+            )));
+        }
+    }
+
+    insert_route(router, method, path, data, |md| {
+        for entry in md.params_map.iter_mut().flatten() {
+            let ParamEntry::Index(param) = entry else {
+                continue;
+            };
+            if let Some((_, allowed_values)) =
+                allowlists.iter().find(|(name, _)| *name == param.name)
+            {
+                param.allowlist = Some(
+                    allowed_values
+                        .iter()
+                        .map(|value| (*value).to_string())
+                        .collect(),
+                );
+            }
+        }
+    })
+}
+
+/// Shared implementation of `add_route` and its metadata-carrying variants.
+/// `configure` fills in route metadata on the new `MethodData` before it is stored.
+fn insert_route<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    data: T,
+    configure: impl FnOnce(&mut MethodData<T>),
+) -> Result<(), RouterError> {
+    let normalized_path_string = router.normalize_pattern(path)?;
     let segments: Vec<&str> = split_path(&normalized_path_string).collect();
 
     let params_map_for_route = build_param_entries_for_pattern_segments(&segments)?;
+    let mut method_data = MethodData::new(data, params_map_for_route);
+    #[cfg(feature = "regex")]
+    {
+        method_data.constraints = build_param_constraints(&segments)?;
+    }
+    configure(&mut method_data);
+
+    // The trie is locked before the static map, the same order as `remove_route`.
+    let mut root_lock = router.root.write();
+    if let Some(shadowing_pattern) =
+        shadowing_pattern(&root_lock, method, &segments, method_data.priority)
+    {
+        let pattern = render_handler_pattern(&segments, &method_data);
+        if router.config.strict_shadowing {
+            return Err(RouterError::ShadowedRoute {
+                pattern,
+                shadowed_by: shadowing_pattern,
+            });
+        }
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            shadowed_pattern = %pattern,
+            shadowing_pattern = %shadowing_pattern,
+            method,
+            "new route is shadowed by an existing route"
+        );
+    }
 
-    if params_map_for_route.is_none() {
+    if method_data.params_map.is_none() {
         let is_purely_static_check = !normalized_path_string.contains([':', '*']);
         if is_purely_static_check {
             let mut static_map_lock = router.static_map.write();
-            static_map_lock
+            let handlers = static_map_lock
                 .entry(normalized_path_string.clone())
                 .or_default()
                 .entry(method.to_string())
-                .or_default()
-                .push(MethodData::new(data.clone(), None));
+                .or_default();
+            insert_by_priority(handlers, method_data.clone());
         }
     }
 
-    let mut current_node_mut_ref: &mut Node<T> = &mut router.root.write();
+    let mut current_node_mut_ref: &mut Node<T> = &mut root_lock;
+
+    let mut idx = 0;
+    loop {
+        // Consume a compressed edge label, splitting it where this pattern diverges.
+        let label_matched = current_node_mut_ref
+            .edge_label_segments()
+            .zip(&segments[idx..])
+            .take_while(|(label_segment, segment)| label_segment == *segment)
+            .count();
+        if label_matched < current_node_mut_ref.edge_label_segments().count() {
+            current_node_mut_ref.split_edge_label(label_matched);
+        }
+        idx += label_matched;
 
-    for segment_str_ref in &segments {
-        let segment_for_logic = *segment_str_ref;
+        let Some(segment_for_logic) = segments.get(idx).copied() else {
+            break;
+        };
+        idx += 1;
 
         let temp_segment_for_type_check = segment_for_logic
             .strip_suffix('?')
             .unwrap_or(segment_for_logic);
 
-        if temp_segment_for_type_check.starts_with("**") {
+        if is_wildcard_segment(temp_segment_for_type_check) {
             current_node_mut_ref = &mut **current_node_mut_ref
                 .wildcard_child
                 .get_or_insert_with(new_node_boxed);
@@ -175,16 +937,135 @@ pub fn add_route<T: Clone>(
         } else {
             current_node_mut_ref = &mut **current_node_mut_ref
                 .static_children
-                .entry((*segment_str_ref).to_string())
+                .entry(segment_for_logic.to_string())
                 .or_insert_with(new_node_boxed);
         }
     }
 
-    current_node_mut_ref
+    let handlers = current_node_mut_ref
         .methods
         .entry(method.to_string())
-        .or_default()
-        .push(MethodData::new(data, params_map_for_route));
+        .or_default();
+    insert_by_priority(handlers, method_data);
+    drop(root_lock);
 
+    if let Some(on_add) = &router.on_add {
+        on_add(method, &normalized_path_string);
+    }
     Ok(())
 }
+
+/// Returns the pattern of an existing handler that would always be chosen over a new
+/// `method` handler for `pattern_segments`, if there is one.
+///
+/// Lookups try static, then parametric, then wildcard children, so a route is never
+/// hidden by a wildcard higher in the tree. It is unreachable only when its own node
+/// already has a handler for the same method without a guard (e.g., `/users/:name`
+/// after `/users/:id`, or `/files/**:rest` after `/files/**:path`): the first
+/// registered handler wins, unless the new handler has a higher `priority`.
+fn shadowing_pattern<T>(
+    root: &Node<T>,
+    method: &str,
+    pattern_segments: &[&str],
+    priority: i32,
+) -> Option<String> {
+    find_pattern_node(root, pattern_segments)?
+        .methods
+        .get(method)?
+        .iter()
+        .find(|md| md.is_unconditional() && md.priority >= priority)
+        .map(|md| render_handler_pattern(pattern_segments, md))
+}
+
+/// Inserts `method_data` after every handler with the same or a higher priority,
+/// keeping `handlers` in the order lookups try them.
+fn insert_by_priority<T>(handlers: &mut Handlers<T>, method_data: MethodData<T>) {
+    let position = handlers.partition_point(|md| md.priority >= method_data.priority);
+    handlers.insert(position, method_data);
+}
+
+/// Walks the routing tree along a route pattern (not a request path) and returns
+/// the node the pattern was registered on, if it exists.
+pub(crate) fn find_pattern_node<'a, T>(
+    root: &'a Node<T>,
+    pattern_segments: &[&str],
+) -> Option<&'a Node<T>> {
+    let mut current_node = root;
+    let mut idx = 0;
+    loop {
+        idx = current_node.consume_edge_label(pattern_segments, idx)?;
+        let Some(segment_str) = pattern_segments.get(idx) else {
+            return Some(current_node);
+        };
+        idx += 1;
+        let temp_segment_for_type_check = segment_str.strip_suffix('?').unwrap_or(segment_str);
+        current_node = if is_wildcard_segment(temp_segment_for_type_check) {
+            current_node.wildcard_child.as_deref()?
+        } else if temp_segment_for_type_check.starts_with(':') || temp_segment_for_type_check == "*"
+        {
+            current_node.param_child.as_deref()?
+        } else {
+            current_node.static_children.get(*segment_str)?
+        };
+    }
+}
+
+fn set_route_enabled<T>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    enabled: bool,
+) -> Result<bool, RouterError> {
+    let normalized_path_string = router.normalize_pattern(path)?;
+    let segments: Vec<&str> = split_path(&normalized_path_string).collect();
+    build_param_entries_for_pattern_segments(&segments)?;
+
+    let root_lock = router.root.read();
+    let Some(handlers) =
+        find_pattern_node(&root_lock, &segments).and_then(|node| node.methods.get(method))
+    else {
+        return Ok(false);
+    };
+    for md in handlers {
+        md.enabled.store(enabled, Ordering::Relaxed);
+    }
+    Ok(!handlers.is_empty())
+}
+
+/// Disables a route without removing it from the routing tree.
+///
+/// Disabled handlers are skipped by `find_route` and `find_all_routes` as if they
+/// were not registered, but keep their place in the tree so they can be switched
+/// back on with [`enable_route`] without re-adding them.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method the route was registered with.
+/// * `path`: The path pattern of the route. This must exactly match the pattern used
+///   when the route was added.
+///
+/// # Returns
+/// * `Result<bool, RouterError>`: `Ok(true)` if a matching handler was found and
+///   disabled, `Ok(false)` if no such route exists, or `Err` if `path` is not a
+///   valid pattern.
+pub fn disable_route<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+) -> Result<bool, RouterError> {
+    set_route_enabled(router, method, path, false)
+}
+
+/// Re-enables a route previously disabled with [`disable_route`].
+///
+/// # Returns
+/// * `Result<bool, RouterError>`: `Ok(true)` if a matching handler was found and
+///   enabled, `Ok(false)` if no such route exists, or `Err` if `path` is not a
+///   valid pattern.
+pub fn enable_route<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+) -> Result<bool, RouterError> {
+    set_route_enabled(router, method, path, true)
+}
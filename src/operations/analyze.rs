@@ -0,0 +1,383 @@
+use crate::{
+    context::{NodeRef, Router, iter_nodes},
+    error::RouterError,
+    operations::{
+        find::{accept_any, find_prepared},
+        util::render_handler_pattern,
+    },
+    types::{IndexParam, MethodData, ParamEntry},
+};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+/// Two route patterns that match the same request paths, as reported by
+/// `find_ambiguous_routes`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct AmbiguityReport {
+    /// The pattern registered first (in `list_routes` order).
+    pub pattern_a: String,
+    /// The other pattern, which names its parameters differently.
+    pub pattern_b: String,
+    /// A request path matched by both patterns.
+    pub example_path: String,
+}
+
+/// Finds routes that differ only in the names of their parameters.
+///
+/// Such routes end on the same node of the routing tree (e.g., `/users/:userId` and
+/// `/users/:teamId`, or `/files/**:path` and `/files/**:rest`), so every request
+/// path matched by one is matched by the other. Whichever handler comes first
+/// answers for a method, and handlers for different methods capture the same value
+/// under different names; both are usually a mistake in the API design.
+///
+/// Each pair of distinct patterns is reported once, with an `example_path` that
+/// fills every parameter with `1`. Routes are compared regardless of method.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+///
+/// # Returns
+/// * `Vec<AmbiguityReport>`: The ambiguous pairs, in `list_routes` order.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn find_ambiguous_routes<T: Clone + Eq + core::hash::Hash>(
+    router: &Router<T>,
+) -> Vec<AmbiguityReport> {
+    let mut reports = Vec::new();
+    for NodeRef {
+        path_prefix, node, ..
+    } in iter_nodes(&router.root.read())
+    {
+        let segments: Vec<&str> = path_prefix.split('/').skip(1).collect();
+        let mut methods: Vec<_> = node.methods.iter().collect();
+        methods.sort_by_key(|(method, _)| method.as_str());
+
+        // Every handler on this node has the same shape; only parameter names differ.
+        let mut patterns: Vec<String> = Vec::new();
+        let mut example_path = None;
+        for md in methods.into_iter().flat_map(|(_, handlers)| handlers) {
+            let pattern = render_handler_pattern(&segments, md);
+            if !patterns.contains(&pattern) {
+                patterns.push(pattern);
+            }
+            example_path.get_or_insert_with(|| example_path_for(&segments, &md.params_map));
+        }
+        let Some(example_path) = example_path else {
+            continue;
+        };
+        for (i, pattern_a) in patterns.iter().enumerate() {
+            for pattern_b in &patterns[i + 1..] {
+                reports.push(AmbiguityReport {
+                    pattern_a: pattern_a.clone(),
+                    pattern_b: pattern_b.clone(),
+                    example_path: example_path.clone(),
+                });
+            }
+        }
+    }
+    reports
+}
+
+/// Builds a request path for a pattern by filling each parameter with `1`, or with
+/// the first allowed value of a parameter that has an allowlist.
+fn example_path_for(segments: &[&str], params_map: &Option<Vec<ParamEntry>>) -> String {
+    let mut path_segments: Vec<&str> = segments.to_vec();
+    for entry in params_map.iter().flatten() {
+        let (idx, value) = match entry {
+            ParamEntry::Index(param) => (
+                param.idx,
+                param
+                    .allowlist
+                    .as_ref()
+                    .and_then(|allowlist| allowlist.first())
+                    .map_or("1", String::as_str),
+            ),
+            ParamEntry::Wildcard(idx, ..) | ParamEntry::Repeated(idx, ..) => (*idx, "1"),
+        };
+        match path_segments.get_mut(idx) {
+            Some(segment) => *segment = value,
+            None => path_segments.push(value),
+        }
+    }
+    format!("/{}", path_segments.join("/"))
+}
+
+/// A problem found by `validate` that does not make the router unusable.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum RouterWarning {
+    /// A route that never matches for its method, because an earlier handler on the
+    /// same pattern shape (e.g., `/users/:userId` before `/users/:teamId`, or
+    /// `/files/**:path` before `/files/:parts+`) answers every request it would.
+    ShadowedRoute {
+        /// The HTTP method of both routes (`""` for any method).
+        method: String,
+        /// The pattern that never matches.
+        pattern: String,
+        /// The pattern of the route that answers instead.
+        shadowed_by: String,
+    },
+    /// Routes that name the parameter at the same position differently, such as
+    /// `/users/:id` and `/users/:userId/posts`. They share one parameter node, so
+    /// this is legal, but usually an inconsistency in the API.
+    InconsistentParamNames {
+        /// The path prefix of the parameter, with `:*` for parameters (e.g., `/users/:*`).
+        prefix: String,
+        /// The different names, sorted.
+        names: Vec<String>,
+    },
+}
+
+impl fmt::Display for RouterWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ShadowedRoute {
+                method,
+                pattern,
+                shadowed_by,
+            } => write!(
+                f,
+                "route {method} '{pattern}' never matches: shadowed by '{shadowed_by}'"
+            ),
+            Self::InconsistentParamNames { prefix, names } => write!(
+                f,
+                "parameter at '{prefix}' is named differently across routes: {}",
+                names.join(", ")
+            ),
+        }
+    }
+}
+
+/// The result of `validate`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouterValidation {
+    /// Problems that make some registered routes unusable.
+    pub errors: Vec<RouterError>,
+    /// Suspicious registrations that still route as registered.
+    pub warnings: Vec<RouterWarning>,
+}
+
+impl RouterValidation {
+    /// Returns `true` if neither errors nor warnings were found.
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty() && self.warnings.is_empty()
+    }
+}
+
+/// Checks every route of `router` for registration mistakes in one traversal.
+///
+/// Errors:
+/// - `RouterError::DuplicateRoute` for a method and pattern registered again after
+///   a handler without a guard or constraints, which always answers first.
+/// - `RouterError::InvalidSegment` for a pattern that uses a parameter name twice.
+///
+/// Warnings:
+/// - `RouterWarning::ShadowedRoute` for a route that never matches because an
+///   earlier handler for the same method matches every path it does.
+/// - `RouterWarning::InconsistentParamNames` for parameters at the same position
+///   with different names.
+///
+/// With the `tracing` feature, each finding is also logged (`error!` or `warn!`), so calling this
+/// once at startup surfaces problems in the server logs. `add_route` rejects most of
+/// these mistakes already when `RouterConfig::strict_shadowing` is set.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn validate<T: Clone + Eq + core::hash::Hash>(router: &Router<T>) -> RouterValidation {
+    let mut validation = RouterValidation::default();
+    let mut param_names: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    for NodeRef {
+        path_prefix, node, ..
+    } in iter_nodes(&router.root.read())
+    {
+        let segments: Vec<&str> = path_prefix.split('/').skip(1).collect();
+        let mut methods: Vec<_> = node.methods.iter().collect();
+        methods.sort_by_key(|(method, _)| method.as_str());
+        for (method, handlers) in methods {
+            // Patterns of the unconditional handlers seen so far, which answer first.
+            let mut unconditional: Vec<(String, &MethodData<T>)> = Vec::new();
+            for md in handlers {
+                let pattern = render_handler_pattern(&segments, md);
+                check_param_names(md, &segments, &pattern, &mut validation, &mut param_names);
+                if let Some((earlier, _)) = unconditional
+                    .iter()
+                    .find(|(earlier, earlier_md)| *earlier == pattern || covers(earlier_md, md))
+                {
+                    if *earlier == pattern {
+                        validation.errors.push(RouterError::DuplicateRoute {
+                            method: method.clone(),
+                            pattern,
+                        });
+                    } else {
+                        validation.warnings.push(RouterWarning::ShadowedRoute {
+                            method: method.clone(),
+                            pattern,
+                            shadowed_by: earlier.clone(),
+                        });
+                    }
+                    continue;
+                }
+                if md.is_unconditional() {
+                    unconditional.push((pattern, md));
+                }
+            }
+        }
+    }
+
+    for (prefix, names) in param_names {
+        if names.len() > 1 {
+            validation
+                .warnings
+                .push(RouterWarning::InconsistentParamNames {
+                    prefix,
+                    names: names.into_iter().collect(),
+                });
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    {
+        for error in &validation.errors {
+            tracing::error!(%error, "invalid route registration");
+        }
+        for warning in &validation.warnings {
+            tracing::warn!(%warning, "suspicious route registration");
+        }
+    }
+    validation
+}
+
+/// Returns `true` if `earlier`, a handler on the same node as `later`, matches every
+/// request path `later` matches. Only the end of the path can differ: an optional
+/// last parameter or a `**` wildcard also matches a path that stops before it.
+fn covers<T>(earlier: &MethodData<T>, later: &MethodData<T>) -> bool {
+    fn matches_without_last<T>(md: &MethodData<T>) -> bool {
+        match md.params_map.as_deref().and_then(<[ParamEntry]>::last) {
+            Some(ParamEntry::Index(param)) => param.optional,
+            Some(ParamEntry::Wildcard(..)) => true,
+            Some(ParamEntry::Repeated(..)) | None => false,
+        }
+    }
+    matches_without_last(earlier) || !matches_without_last(later)
+}
+
+/// Reports a parameter name used twice in `md`'s pattern, and records the name of
+/// each named parameter under its path prefix in `param_names`.
+fn check_param_names<T>(
+    md: &MethodData<T>,
+    segments: &[&str],
+    pattern: &str,
+    validation: &mut RouterValidation,
+    param_names: &mut BTreeMap<String, BTreeSet<String>>,
+) {
+    let mut seen = BTreeSet::new();
+    for entry in md.params_map.iter().flatten() {
+        let (ParamEntry::Index(IndexParam { idx, name, .. })
+        | ParamEntry::Wildcard(idx, name, _)
+        | ParamEntry::Repeated(idx, name)) = entry;
+        if name == "_" {
+            continue;
+        }
+        if !seen.insert(name.as_str()) {
+            validation.errors.push(RouterError::InvalidSegment {
+                segment: pattern.to_string(),
+                reason: format!("duplicate parameter name '{name}' in pattern"),
+            });
+        }
+        if let ParamEntry::Index(..) = entry {
+            let prefix = format!("/{}", segments[..=*idx].join("/"));
+            param_names.entry(prefix).or_default().insert(name.clone());
+        }
+    }
+}
+
+/// A parameterized route that an access log shows is always requested with the same
+/// path, as reported by `analyze_hot_paths`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct HotPathSuggestion {
+    /// The pattern of the route the requests matched (e.g., `/users/:id`).
+    pub pattern: String,
+    /// The one request path the route was matched with (e.g., `/users/admin`).
+    pub actual_path: String,
+    /// A human-readable suggestion, naming the method and the static route to add.
+    pub suggestion: String,
+}
+
+/// Finds parameterized routes that an access log only ever requests with one path,
+/// and suggests adding that path as a static route.
+///
+/// Each `(method, path)` of `access_log` is looked up as `find_route` would look it up,
+/// without calling the router's `on_match` hook. Requests that match a static route are
+/// answered by the static map and are ignored, as are requests that match nothing.
+/// A route with a parameter or wildcard in its pattern is reported when it was matched
+/// more than `threshold` times for one method, always with the same normalized path:
+/// a static route for that path would skip the trie lookup. Routes requested with
+/// several different paths are not reported.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `access_log`: The `(method, path)` of each logged request.
+/// * `threshold`: The number of requests a route must exceed to be reported.
+///
+/// # Returns
+/// * `Vec<HotPathSuggestion>`: One suggestion per route and method, sorted by pattern
+///   and then path.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn analyze_hot_paths<T: Clone + Eq + core::hash::Hash>(
+    router: &Router<T>,
+    access_log: &[(&str, &str)],
+    threshold: usize,
+) -> Vec<HotPathSuggestion> {
+    // (pattern, method) -> (request count, the path if it never changed)
+    let mut hits: BTreeMap<(String, String), (usize, Option<String>)> = BTreeMap::new();
+    for &(method, path) in access_log {
+        let Ok(request_path) = router.prepare_request_path(method, path) else {
+            continue;
+        };
+        let Ok(matched) = find_prepared(router, method, &request_path, path, false, &accept_any)
+        else {
+            continue;
+        };
+        if !matched.route_pattern.contains([':', '*']) {
+            continue;
+        }
+        let actual_path = format!("/{}", matched.matched_path);
+        let (count, single_path) = hits
+            .entry((matched.route_pattern, method.to_string()))
+            .or_insert_with(|| (0, Some(actual_path.clone())));
+        *count += 1;
+        if single_path
+            .as_ref()
+            .is_some_and(|seen| *seen != actual_path)
+        {
+            *single_path = None;
+        }
+    }
+
+    let mut suggestions: Vec<HotPathSuggestion> = hits
+        .into_iter()
+        .filter(|(_, (count, _))| *count > threshold)
+        .filter_map(|((pattern, method), (count, single_path))| {
+            let actual_path = single_path?;
+            let suggestion = format!(
+                "add a static route for {method} {actual_path}: all {count} requests matched \
+                 {pattern} with this path"
+            );
+            Some(HotPathSuggestion {
+                pattern,
+                actual_path,
+                suggestion,
+            })
+        })
+        .collect();
+    suggestions.sort_by(|a, b| (&a.pattern, &a.actual_path).cmp(&(&b.pattern, &b.actual_path)));
+    suggestions
+}
@@ -0,0 +1,135 @@
+use crate::{
+    context::{ArcRouter, Router},
+    error::RouterError,
+    operations::{
+        add::add_route,
+        find::{
+            accept_any, match_request_path, match_static, matched_or_redirect,
+            method_not_allowed_or,
+        },
+        remove::remove_route,
+    },
+    types::MatchedRoute,
+};
+use alloc::{boxed::Box, sync::Arc};
+
+/// Adds a route handler to an `ArcRouter`.
+///
+/// The routing tree and static map are copied, the route is added to the copies as
+/// `add_route` would add it to a `Router`, and the copies replace the originals.
+/// Lookups running meanwhile keep using the old tree. Writers wait for each other,
+/// so no write is lost.
+///
+/// # Arguments
+/// * `router`: A reference to the `ArcRouter` instance.
+/// * `method`: The HTTP method for this route (e.g., "GET", "POST"). An empty string means any method.
+/// * `path`: The path pattern for the route.
+/// * `data`: The data to associate with this route.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: Any error `add_route` returns, in which case the
+///   router is unchanged.
+///
+/// # Panics
+/// This function may panic if acquiring the router's writer lock or the static map's locks fails.
+pub fn add_route_arc<T: Clone>(
+    router: &ArcRouter<T>,
+    method: &str,
+    path: &str,
+    data: T,
+) -> Result<(), RouterError> {
+    update(router, |staging| add_route(staging, method, path, data))
+}
+
+/// Removes a route handler from an `ArcRouter`.
+///
+/// Like `add_route_arc`, this replaces the routing tree with an updated copy.
+/// Lookups running meanwhile may still match the removed route.
+///
+/// # Arguments
+/// * `router`: A reference to the `ArcRouter` instance.
+/// * `method`: The HTTP method of the route handler to remove.
+/// * `path`: The path pattern of the route handler to remove, as it was added.
+///
+/// # Returns
+/// * `Result<bool, RouterError>`: Whether a handler was removed, or any error
+///   `remove_route` returns.
+///
+/// # Panics
+/// This function may panic if acquiring the router's writer lock or the static map's locks fails.
+pub fn remove_route_arc<T: Clone>(
+    router: &ArcRouter<T>,
+    method: &str,
+    path: &str,
+) -> Result<bool, RouterError> {
+    update(router, |staging| remove_route(staging, method, path))
+}
+
+/// Finds a route in an `ArcRouter`, as `find_route` does for a `Router`.
+///
+/// The routing tree is loaded without a lock; only a static route lookup takes the
+/// static map's read lock.
+///
+/// # Arguments
+/// * `router`: A reference to the `ArcRouter` instance.
+/// * `method`: The HTTP method (e.g., "GET", "POST") to match.
+/// * `path`: The request path to match against the router's patterns.
+/// * `capture`: A boolean indicating whether path parameters should be extracted.
+///
+/// # Returns
+/// * `Result<MatchedRoute<T>, RouterError>`: The matched route, or the same errors
+///   `find_route` returns.
+///
+/// # Panics
+/// This function may panic if acquiring the read lock on the static map fails.
+pub fn find_route_arc<T: Clone + Eq>(
+    router: &ArcRouter<T>,
+    method: &str,
+    path: &str,
+    capture: bool,
+) -> Result<MatchedRoute<T>, RouterError> {
+    let request_path = router.config.prepare_request_path(method, path)?;
+    if let Some(matched) = match_static(
+        &router.static_map.read(),
+        method,
+        &request_path,
+        &accept_any,
+    ) {
+        return matched_or_redirect(&request_path, matched);
+    }
+    let root = router.root.load();
+    match match_request_path(
+        &root,
+        method,
+        &request_path,
+        capture,
+        router.config.wildcard_preference,
+        &accept_any,
+    ) {
+        Some(matched) => matched_or_redirect(&request_path, matched),
+        None => Err(method_not_allowed_or(
+            &root,
+            method,
+            &request_path,
+            path,
+            RouterError::not_found(method, path),
+        )),
+    }
+}
+
+/// Applies `change` to a copy of `router`'s routes and publishes the copy if it
+/// succeeds. The tree is swapped in before the static map, so a static route being
+/// added is found in the tree until the new static map is in place.
+fn update<T: Clone, R>(
+    router: &ArcRouter<T>,
+    change: impl FnOnce(&Router<T>) -> Result<R, RouterError>,
+) -> Result<R, RouterError> {
+    let _writer = router.writer.lock();
+    let mut staging = Router::with_config(router.config.clone());
+    *staging.root.get_mut() = Box::clone(&router.root.load());
+    *staging.static_map.get_mut() = router.static_map.read().clone();
+    let result = change(&staging)?;
+    router.root.store(Arc::new(staging.root.into_inner()));
+    *router.static_map.write() = staging.static_map.into_inner();
+    Ok(result)
+}
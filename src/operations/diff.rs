@@ -0,0 +1,55 @@
+use crate::{compat::HashSet, context::Router, operations::list_routes, types::RouteEntry};
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+/// The differences between the routes of two routers, as computed by `diff_routers`.
+///
+/// Routes are compared by method, pattern, and data together, so a route whose
+/// data changed appears in both `removed` (old data) and `added` (new data).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RouterDiff<T: Clone + Eq + Hash> {
+    /// Routes present in the new router but not in the old one.
+    pub added: Vec<RouteEntry<T>>,
+    /// Routes present in the old router but not in the new one.
+    pub removed: Vec<RouteEntry<T>>,
+    /// Routes present in both routers.
+    pub unchanged: Vec<RouteEntry<T>>,
+}
+
+/// Compares the routes registered in two routers.
+///
+/// Both routers are listed with `list_routes`, so each list in the returned
+/// `RouterDiff` keeps the same stable order `list_routes` produces.
+///
+/// # Arguments
+/// * `old`: The router before the change.
+/// * `new`: The router after the change.
+///
+/// # Returns
+/// * `RouterDiff<T>`: The added, removed, and unchanged routes.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the routers' internal structures fails.
+pub fn diff_routers<T: Clone + Eq + Hash>(old: &Router<T>, new: &Router<T>) -> RouterDiff<T> {
+    let old_routes = list_routes(old);
+    let new_routes = list_routes(new);
+    let old_set: HashSet<&RouteEntry<T>> = old_routes.iter().collect();
+    let new_set: HashSet<&RouteEntry<T>> = new_routes.iter().collect();
+
+    let removed = old_routes
+        .iter()
+        .filter(|entry| !new_set.contains(entry))
+        .cloned()
+        .collect();
+    // `hashbrown` sets (the `no_std` build) keep their borrows alive until dropped.
+    drop(new_set);
+    let (unchanged, added) = new_routes
+        .into_iter()
+        .partition(|entry| old_set.contains(entry));
+
+    RouterDiff {
+        added,
+        removed,
+        unchanged,
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{
+    context::ExactRouter,
+    error::RouterError,
+    operations::{
+        find::{accept_any, match_static, matched_or_redirect, method_not_allowed_from},
+        options::allowed_static_methods,
+    },
+    types::{MatchedRoute, MethodData},
+};
+use alloc::{format, string::ToString};
+
+/// Adds a static route to an `ExactRouter`.
+///
+/// The route is stored only in the router's `static_map`; the routing tree is
+/// never modified. Patterns are normalized like `add_route` patterns (global
+/// prefix, case folding, trailing slash mode).
+///
+/// # Arguments
+/// * `router`: A reference to the `ExactRouter` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The static path for the route (e.g., "/health").
+/// * `data`: The data or handler to associate with this route.
+///
+/// # Returns
+/// * `Result<(), RouterError>`:
+///   - `Ok(())` if the route was added.
+///   - `Err(RouterError::InvalidPath)` if `path` contains `:` or `*`.
+///   - Any error `add_route` returns for an invalid pattern.
+///
+/// # Panics
+/// This function may panic if acquiring the write lock on the static map fails.
+pub fn add_static<T: Clone>(
+    router: &ExactRouter<T>,
+    method: &str,
+    path: &str,
+    data: T,
+) -> Result<(), RouterError> {
+    if path.contains([':', '*']) {
+        return Err(RouterError::InvalidPath(format!(
+            "'{path}' is not a static path"
+        )));
+    }
+    let router = router.inner();
+    let normalized_path = router.normalize_pattern(path)?;
+    router
+        .static_map
+        .write()
+        .entry(normalized_path)
+        .or_default()
+        .entry(method.to_string())
+        .or_default()
+        .push(MethodData::new(data, None));
+    Ok(())
+}
+
+/// Finds a static route in an `ExactRouter`.
+///
+/// The request path is prepared exactly as `find_route` prepares it, then looked
+/// up in the router's `static_map` alone. `HEAD` requests fall back to `GET`
+/// handlers and "any" (`""`) handlers are used when no handler for `method` exists,
+/// as with `find_route`.
+///
+/// # Arguments
+/// * `router`: A reference to the `ExactRouter` instance.
+/// * `method`: The HTTP method (e.g., "GET", "POST") to match.
+/// * `path`: The request path.
+///
+/// # Returns
+/// * `Result<MatchedRoute<T>, RouterError>`: The matched route, whose `params` are
+///   always `None`, or the error `find_route` would return.
+///
+/// # Panics
+/// This function may panic if acquiring the read lock on the static map fails.
+pub fn find_static<T: Clone + Eq>(
+    router: &ExactRouter<T>,
+    method: &str,
+    path: &str,
+) -> Result<MatchedRoute<T>, RouterError> {
+    let router = router.inner();
+    let request_path = router.prepare_request_path(method, path)?;
+    let static_map = router.static_map.read();
+    match match_static(&static_map, method, &request_path, &accept_any) {
+        Some(matched) => matched_or_redirect(&request_path, matched),
+        None => Err(method_not_allowed_from(
+            method,
+            path,
+            RouterError::not_found(method, path),
+            || {
+                static_map
+                    .get(request_path.lookup())
+                    .map(allowed_static_methods)
+                    .unwrap_or_default()
+            },
+        )),
+    }
+}
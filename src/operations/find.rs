@@ -1,9 +1,22 @@
 use crate::{
-    context::{Node, Router},
+    context::{Node, RequestPath, Router, StaticMap, StaticPathMethods},
     error::RouterError,
-    operations::util::{extract_all_params, normalize, split_path},
-    types::{MatchedRoute, MethodData, ParamEntry},
+    operations::{
+        find_all::find_all_routes,
+        options::{allowed_methods, allowed_methods_for_segments},
+        util::{extract_all_params, extract_borrowed_params, parse_query, split_path},
+    },
+    types::{
+        BorrowedMatchedRoute, MatchedRoute, MethodData, NormalizedPath, ParamEntry,
+        RoutableRequest, WildcardPreference,
+    },
 };
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::cell::OnceCell;
 
 /// Finds a route matching the given HTTP method and path.
 ///
@@ -15,6 +28,12 @@ use crate::{
 /// If a match is found, it returns a `MatchedRoute` containing the data associated
 /// with the route and, if `capture` is true, any extracted parameters.
 ///
+/// A `HEAD` request with no `HEAD` handler on the matched node is answered by its
+/// `GET` handler, with `MatchedRoute::is_head_derived` set to `true`.
+///
+/// If the router has an `on_match` hook (see `Router::set_on_match`), it is called
+/// once the route is found.
+///
 /// # Arguments
 /// * `router`: A reference to the `Router` instance.
 /// * `method`: The HTTP method (e.g., "GET", "POST") to match.
@@ -27,7 +46,11 @@ use crate::{
 /// # Returns
 /// * `Result<MatchedRoute<T>, RouterError>`:
 ///   - `Ok(MatchedRoute<T>)` if a route is successfully found.
+///   - `Err(RouterError::MethodNotAllowed)` if routes match the path, but none for
+///     `method`; its `allowed` list holds their methods.
 ///   - `Err(RouterError::RouteNotFound)` if no route matches the given method and path.
+///   - `Err(RouterError::TrailingSlashRedirect)` if the router uses `TrailingSlashMode::Redirect`
+///     and the path matched only after its trailing slash was removed.
 ///   - Other `RouterError` variants might occur if there's an issue with path processing,
 ///     though `RouteNotFound` is the most common error for this function.
 ///
@@ -40,101 +63,803 @@ pub fn find_route<T: Clone + Eq>(
     path: &str,
     capture: bool,
 ) -> Result<MatchedRoute<T>, RouterError> {
-    let normalized_path_string = normalize(path);
-
-    if !normalized_path_string.contains([':', '*']) {
-        let static_map_read_guard = router.static_map.read();
-        if let Some(methods_for_path) = static_map_read_guard.get(&normalized_path_string) {
-            if let Some(method_data_list) = methods_for_path
-                .get(method)
-                .or_else(|| methods_for_path.get(""))
-            {
-                if let Some(md) = method_data_list.first() {
-                    if md.params_map.is_none() {
-                        return Ok(MatchedRoute {
-                            data: md.data.clone(),
-                            params: None,
-                        });
-                    }
-                }
+    let request_path = router.prepare_request_path(method, path)?;
+    let matched = find_prepared(router, method, &request_path, path, capture, &accept_any)
+        .map_err(|err| {
+            method_not_allowed_or(&router.root.read(), method, &request_path, path, err)
+        })?;
+    if let Some(on_match) = &router.on_match {
+        on_match(method, &matched.matched_path, &matched.route_pattern);
+    }
+    Ok(matched)
+}
+
+/// Finds a route like `find_route`, inside a `tracing` span tagged with the request's id.
+///
+/// The lookup runs in a `find_route` span at `DEBUG` level with `request_id`,
+/// `method`, and `path` fields, and records its outcome as a `DEBUG` event in that
+/// span: the matched pattern, or the error. Events emitted while the span is entered,
+/// including any from the router's `on_match` hook, are children of the span, so
+/// subscribers (e.g., an OpenTelemetry exporter) correlate them with the request. The
+/// span is closed when the lookup returns.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method (e.g., "GET", "POST") to match.
+/// * `path`: The request path to match against the router's patterns.
+/// * `capture`: A boolean indicating whether path parameters should be extracted.
+/// * `request_id`: The id of the request being routed (e.g., an `X-Request-Id` header).
+///
+/// # Returns
+/// * `Result<MatchedRoute<T>, RouterError>`: The same result as `find_route`.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+#[cfg(feature = "tracing")]
+pub fn find_route_traced<T: Clone + Eq>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    capture: bool,
+    request_id: &str,
+) -> Result<MatchedRoute<T>, RouterError> {
+    let span = tracing::span!(
+        tracing::Level::DEBUG,
+        "find_route",
+        request_id,
+        method,
+        path
+    );
+    let _entered = span.enter();
+    let result = find_route(router, method, path, capture);
+    match &result {
+        Ok(matched) => tracing::event!(
+            tracing::Level::DEBUG,
+            route_pattern = %matched.route_pattern,
+            "route matched"
+        ),
+        Err(error) => tracing::event!(tracing::Level::DEBUG, %error, "no route matched"),
+    }
+    result
+}
+
+/// Finds the most specific route matching the given HTTP method and path.
+///
+/// `find_route` returns the first match of a depth-first walk of the routing tree,
+/// which prefers a static segment over a parameter at every level, even when the
+/// static branch only ends in a wildcard. `find_route_best` instead looks at every
+/// route `find_all_routes` returns and picks the one with the highest
+/// `MatchedRoute::specificity`; ties go to the route `find_all_routes` lists first.
+/// This costs a full `find_all_routes` search, so prefer `find_route` when the two
+/// agree for your routes.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method (e.g., "GET", "POST") to match.
+/// * `path`: The request path to match against the router's patterns.
+/// * `capture`: A boolean indicating whether path parameters should be extracted.
+///
+/// # Returns
+/// * `Result<MatchedRoute<T>, RouterError>`: The most specific matching route, or
+///   the error `find_route` returns (`RouterError::RouteNotFound` or
+///   `RouterError::MethodNotAllowed`) if no route matches.
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, add_route, find_route, find_route_best};
+///
+/// let router = Router::new();
+/// add_route(&router, "GET", "/docs/latest/**:page", "latest_docs").unwrap();
+/// add_route(&router, "GET", "/docs/:version/index", "docs_index").unwrap();
+///
+/// assert_eq!(find_route(&router, "GET", "/docs/latest/index", false).unwrap().data, "latest_docs");
+/// assert_eq!(find_route_best(&router, "GET", "/docs/latest/index", false).unwrap().data, "docs_index");
+/// ```
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn find_route_best<T: Clone + Eq>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    capture: bool,
+) -> Result<MatchedRoute<T>, RouterError> {
+    find_all_routes(router, method, path, capture)
+        .into_iter()
+        .reduce(|best, candidate| {
+            if candidate.specificity > best.specificity {
+                candidate
+            } else {
+                best
             }
+        })
+        .ok_or_else(|| route_not_found(router, method, path))
+}
+
+/// Finds a route for a path that is already normalized.
+///
+/// This behaves like `find_route` but skips the `normalize()` call, avoiding an
+/// allocation per request for frameworks whose HTTP parser already guarantees
+/// normalized paths. The router's `RouterConfig` (percent-decoding, case folding,
+/// `max_depth`) still applies.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method (e.g., "GET", "POST") to match.
+/// * `path`: The normalized request path.
+/// * `capture`: A boolean indicating whether path parameters should be extracted.
+///
+/// # Returns
+/// * `Result<MatchedRoute<T>, RouterError>`: The same result `find_route` returns for
+///   `path`. `RouterError::RouteNotFound` reports the normalized path.
+///
+/// # Example
+/// ```rust
+/// use rou3::{NormalizedPath, Router, add_route, find_route_normalized};
+///
+/// let router = Router::new();
+/// add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+///
+/// let path: NormalizedPath = "/users/42/".parse().unwrap();
+/// assert_eq!(find_route_normalized(&router, "GET", &path, false).unwrap().data, "get_user");
+/// ```
+pub fn find_route_normalized<T: Clone + Eq>(
+    router: &Router<T>,
+    method: &str,
+    path: &NormalizedPath,
+    capture: bool,
+) -> Result<MatchedRoute<T>, RouterError> {
+    let request_path = router.prepare_normalized_path(path)?;
+    find_prepared(router, method, &request_path, path, capture, &accept_any)
+        .map_err(|err| method_not_allowed_or(&router.root.read(), method, &request_path, path, err))
+}
+
+/// Turns a `RouteNotFound` error into `RouterError::MethodNotAllowed` when routes for
+/// other methods match the request path. Other errors are returned unchanged.
+pub(crate) fn method_not_allowed_or<T>(
+    root: &Node<T>,
+    method: &str,
+    request_path: &RequestPath<'_>,
+    path: &str,
+    err: RouterError,
+) -> RouterError {
+    method_not_allowed_from(method, path, err, || {
+        allowed_methods(root, request_path.lookup())
+    })
+}
+
+/// The error `find_route` returns for a request to `path` that no route answers:
+/// `RouterError::MethodNotAllowed` if routes match `path` for other methods, and
+/// `RouterError::RouteNotFound` otherwise.
+pub(crate) fn route_not_found<T>(router: &Router<T>, method: &str, path: &str) -> RouterError {
+    let err = RouterError::not_found(method, path);
+    match router.prepare_request_path(method, path) {
+        Ok(request_path) => {
+            method_not_allowed_or(&router.root.read(), method, &request_path, path, err)
         }
+        Err(_) => err,
+    }
+}
+
+/// Like `method_not_allowed_or`, for a lookup path that is already split into segments.
+fn segments_method_not_allowed_or<T>(
+    root: &Node<T>,
+    method: &str,
+    lookup_segments: &[&str],
+    path: &str,
+    err: RouterError,
+) -> RouterError {
+    method_not_allowed_from(method, path, err, || {
+        allowed_methods_for_segments(root, lookup_segments)
+    })
+}
+
+/// Turns a `RouteNotFound` error into `RouterError::MethodNotAllowed` if `allowed`
+/// returns methods other than `method`.
+pub(crate) fn method_not_allowed_from(
+    method: &str,
+    path: &str,
+    err: RouterError,
+    allowed: impl FnOnce() -> BTreeSet<String>,
+) -> RouterError {
+    if !matches!(err, RouterError::RouteNotFound { .. }) {
+        return err;
+    }
+    let allowed = allowed();
+    if allowed.is_empty() || allowed.contains(method) {
+        return err;
     }
+    RouterError::MethodNotAllowed {
+        method: method.to_string(),
+        path: path.to_string(),
+        allowed: allowed.into_iter().collect(),
+    }
+}
 
-    let segments: Vec<&str> = split_path(&normalized_path_string).collect();
+/// Matches a prepared request path, only considering handlers for which `matches`
+/// returns `true`.
+pub(crate) fn find_prepared<T: Clone + Eq>(
+    router: &Router<T>,
+    method: &str,
+    request_path: &RequestPath<'_>,
+    path: &str,
+    capture: bool,
+    matches: &impl Fn(&MethodData<T>) -> bool,
+) -> Result<MatchedRoute<T>, RouterError> {
+    if let Some(matched) = match_static(&router.static_map.read(), method, request_path, matches) {
+        return matched_or_redirect(request_path, matched);
+    }
+    match match_request_path(
+        &router.root.read(),
+        method,
+        request_path,
+        capture,
+        router.config.wildcard_preference,
+        matches,
+    ) {
+        Some(matched) => matched_or_redirect(request_path, matched),
+        None => Err(RouterError::not_found(method, path)),
+    }
+}
+
+/// Finds a route in the first of several routers that has one.
+///
+/// Each router in `routers` is searched with `find_route`, in order, and the first
+/// match is returned. This lets independently built routers (e.g., one per plugin)
+/// be chained, with earlier routers taking precedence and later ones acting as
+/// fallbacks.
+///
+/// # Arguments
+/// * `routers`: The routers to search, highest precedence first.
+/// * `method`: The HTTP method (e.g., "GET", "POST") to match.
+/// * `path`: The request path to match against the routers' patterns.
+/// * `capture`: A boolean indicating whether path parameters should be extracted.
+///
+/// # Returns
+/// * `Result<MatchedRoute<T>, RouterError>`:
+///   - `Ok(MatchedRoute<T>)` from the first router that matches.
+///   - `Err(RouterError::MethodNotAllowed)` if no router matches but some have routes
+///     for the path with other methods, listing the methods allowed by any of them.
+///   - `Err(RouterError::RouteNotFound)` if no router matches, including when
+///     `routers` is empty.
+///   - Any other error from `find_route` (such as `RouterError::TrailingSlashRedirect`)
+///     as soon as a router returns it; later routers are not searched.
+pub fn find_route_chain<T: Clone + Eq>(
+    routers: &[&Router<T>],
+    method: &str,
+    path: &str,
+    capture: bool,
+) -> Result<MatchedRoute<T>, RouterError> {
+    let mut allowed_methods = BTreeSet::new();
+    for router in routers {
+        match find_route(router, method, path, capture) {
+            Err(RouterError::RouteNotFound { .. }) => continue,
+            Err(RouterError::MethodNotAllowed { allowed, .. }) => allowed_methods.extend(allowed),
+            result => return result,
+        }
+    }
+    if allowed_methods.is_empty() {
+        return Err(RouterError::not_found(method, path));
+    }
+    Err(RouterError::MethodNotAllowed {
+        method: method.to_string(),
+        path: path.to_string(),
+        allowed: allowed_methods.into_iter().collect(),
+    })
+}
+
+/// Finds routes for many `(method, path)` requests at once.
+///
+/// Each request is resolved exactly as `find_route` would resolve it, but the
+/// router's read locks are acquired once for the whole batch instead of once per
+/// lookup, which reduces lock traffic when many threads are routing concurrently.
+/// Writers (`add_route`, `remove_route`, ...) are blocked until the batch finishes.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `requests`: The `(method, path)` pairs to look up.
+/// * `capture`: A boolean indicating whether path parameters should be extracted.
+///
+/// # Returns
+/// * `Vec<Result<MatchedRoute<T>, RouterError>>`: One result per request, in the same
+///   order as `requests`.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn find_routes_batch<T: Clone + Eq>(
+    router: &Router<T>,
+    requests: &[(&str, &str)],
+    capture: bool,
+) -> Vec<Result<MatchedRoute<T>, RouterError>> {
+    // Locks are taken in the same order as `remove_route` (trie, then static map),
+    // so a concurrent removal cannot deadlock with the batch.
     let root_lock = router.root.read();
+    let static_map_lock = router.static_map.read();
+    requests
+        .iter()
+        .map(|&(method, path)| {
+            let request_path = router.prepare_request_path(method, path)?;
+            match match_static(&static_map_lock, method, &request_path, &accept_any).or_else(|| {
+                match_request_path(
+                    &root_lock,
+                    method,
+                    &request_path,
+                    capture,
+                    router.config.wildcard_preference,
+                    &accept_any,
+                )
+            }) {
+                Some(matched) => matched_or_redirect(&request_path, matched),
+                None => Err(method_not_allowed_or(
+                    &root_lock,
+                    method,
+                    &request_path,
+                    path,
+                    RouterError::not_found(method, path),
+                )),
+            }
+        })
+        .collect()
+}
 
-    match lookup_node_recursive(&*root_lock, method, &segments, 0) {
-        Some(md) => {
-            let params = if capture {
-                extract_all_params(&segments, &md.params_map)
-            } else {
-                None
-            };
-            Ok(MatchedRoute {
-                data: md.data.clone(),
-                params,
-            })
+/// Looks a request path up in the `static_map`, which only holds purely static routes.
+pub(crate) fn match_static<T: Clone + Eq>(
+    static_map: &StaticMap<T>,
+    method: &str,
+    request_path: &RequestPath<'_>,
+    matches: &impl Fn(&MethodData<T>) -> bool,
+) -> Option<MatchedRoute<T>> {
+    let lookup_path = request_path.lookup();
+    if lookup_path.contains([':', '*']) {
+        return None;
+    }
+    let methods_for_path = static_map.get(lookup_path)?;
+    let (md, is_head_derived) = select_handler_where(
+        methods_for_path,
+        method,
+        &request_path.normalized[..].into(),
+        matches,
+    )?;
+    if md.params_map.is_some() {
+        return None;
+    }
+    let lookup_segments: Vec<&str> = split_path(lookup_path).collect();
+    let mut matched = MatchedRoute::from_method_data(
+        md,
+        None,
+        request_path.normalized.to_string(),
+        &lookup_segments,
+    );
+    matched.is_head_derived = is_head_derived;
+    Some(matched)
+}
+
+/// Splits a prepared request path and matches it against the routing tree.
+pub(crate) fn match_request_path<T: Clone + Eq>(
+    root: &Node<T>,
+    method: &str,
+    request_path: &RequestPath<'_>,
+    capture: bool,
+    wildcard_preference: WildcardPreference,
+    matches: &impl Fn(&MethodData<T>) -> bool,
+) -> Option<MatchedRoute<T>> {
+    let segments: Vec<&str> = split_path(&request_path.normalized).collect();
+    let folded_segments: Vec<&str>;
+    let lookup_segments = if request_path.is_folded() {
+        folded_segments = split_path(request_path.lookup()).collect();
+        &folded_segments
+    } else {
+        &segments
+    };
+    match_segments(
+        root,
+        method,
+        &segments,
+        lookup_segments,
+        MatchPath::Path(&request_path.normalized),
+        capture,
+        wildcard_preference,
+        matches,
+    )
+}
+
+/// Finds a route for a request path that has already been split into segments.
+///
+/// This behaves like `find_route` but skips normalizing and splitting the path, for
+/// callers such as HTTP parsers that tokenize URLs themselves. `segments` must be
+/// the non-empty path segments in order (e.g., `["users", "123"]` for `/users/123`)
+/// and must include any global prefix. They are matched as given: no percent-decoding
+/// or trailing-slash handling is applied, although case-insensitive routers still fold
+/// static segments and `max_depth` is still enforced.
+///
+/// The lookup itself does not allocate unless a case-insensitive router has to fold a
+/// segment with uppercase letters. The segments are joined with `/` only when a guard
+/// needs the whole path or a route matches, and that joined path becomes the
+/// `MatchedRoute::matched_path`, so a match still allocates once for it (besides the
+/// `MatchedRoute` itself).
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method (e.g., "GET", "POST") to match.
+/// * `segments`: The request path's segments.
+/// * `original_path`: The request path the segments came from. It is only used to
+///   build the `RouterError::RouteNotFound` and `RouterError::MethodNotAllowed` errors.
+/// * `capture`: A boolean indicating whether path parameters should be extracted.
+///
+/// # Returns
+/// * `Result<MatchedRoute<T>, RouterError>`: The same result `find_route` returns for
+///   `original_path`.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn find_route_segments<T: Clone + Eq>(
+    router: &Router<T>,
+    method: &str,
+    segments: &[&str],
+    original_path: &str,
+    capture: bool,
+) -> Result<MatchedRoute<T>, RouterError> {
+    check_segment_depth(router, segments)?;
+    let folded: Vec<String>;
+    let folded_segments: Vec<&str>;
+    let lookup_segments = if router.config.case_sensitive || !has_uppercase(segments) {
+        segments
+    } else {
+        folded = segments.iter().map(|s| s.to_ascii_lowercase()).collect();
+        folded_segments = folded.iter().map(String::as_str).collect();
+        &folded_segments
+    };
+
+    let root_lock = router.root.read();
+    match_segments(
+        &root_lock,
+        method,
+        segments,
+        lookup_segments,
+        MatchPath::segments(segments),
+        capture,
+        router.config.wildcard_preference,
+        &accept_any,
+    )
+    .ok_or_else(|| {
+        segments_method_not_allowed_or(
+            &root_lock,
+            method,
+            lookup_segments,
+            original_path,
+            RouterError::not_found(method, original_path),
+        )
+    })
+}
+
+/// Finds a route for pre-split path segments without copying captured parameter values.
+///
+/// This matches exactly like `find_route_segments`, but the returned parameter values
+/// borrow from `path_segments` instead of being copied into new `String`s, which saves
+/// an allocation per captured value on hot paths where the caller owns the request
+/// path. Wildcards spanning several segments are joined into an owned value, and
+/// parameter names are still copied from the route.
+///
+/// Guards are evaluated against `original_path` instead of a path rebuilt from the
+/// segments, so it should be the normalized path the segments came from.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method (e.g., "GET", "POST") to match.
+/// * `path_segments`: The request path's segments, as for `find_route_segments`.
+/// * `original_path`: The request path the segments came from.
+/// * `capture`: A boolean indicating whether path parameters should be extracted.
+///
+/// # Returns
+/// * `Result<BorrowedMatchedRoute<'p, T>, RouterError>`: The matched route's data and
+///   borrowed parameters, or the error `find_route_segments` returns.
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, add_route, find_route_borrowed};
+///
+/// let router = Router::new();
+/// add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+/// let segments = ["users", "42"];
+/// let matched = find_route_borrowed(&router, "GET", &segments, "users/42", true).unwrap();
+/// assert_eq!(matched.data, "get_user");
+/// assert_eq!(matched.params.unwrap()["id"], "42");
+/// ```
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn find_route_borrowed<'p, T: Clone + Eq>(
+    router: &Router<T>,
+    method: &str,
+    path_segments: &'p [&'p str],
+    original_path: &str,
+    capture: bool,
+) -> Result<BorrowedMatchedRoute<'p, T>, RouterError> {
+    check_segment_depth(router, path_segments)?;
+    let folded: Vec<String>;
+    let folded_segments: Vec<&str>;
+    let lookup_segments = if router.config.case_sensitive || !has_uppercase(path_segments) {
+        path_segments
+    } else {
+        folded = path_segments
+            .iter()
+            .map(|s| s.to_ascii_lowercase())
+            .collect();
+        folded_segments = folded.iter().map(String::as_str).collect();
+        &folded_segments
+    };
+
+    let root_lock = router.root.read();
+    let (md, _) = select_route(
+        &root_lock,
+        method,
+        lookup_segments,
+        &original_path.into(),
+        router.config.wildcard_preference,
+        &accept_any,
+    )
+    .ok_or_else(|| {
+        segments_method_not_allowed_or(
+            &root_lock,
+            method,
+            lookup_segments,
+            original_path,
+            RouterError::not_found(method, original_path),
+        )
+    })?;
+    Ok(BorrowedMatchedRoute {
+        data: md.data.clone(),
+        params: if capture {
+            extract_borrowed_params(path_segments, &md.params_map, &md.transforms, &md.defaults)
+        } else {
+            None
+        },
+    })
+}
+
+/// Whether any of `segments` has an ASCII uppercase letter, i.e. folding them for a
+/// case-insensitive router would change them.
+fn has_uppercase(segments: &[&str]) -> bool {
+    segments
+        .iter()
+        .any(|segment| segment.bytes().any(|b| b.is_ascii_uppercase()))
+}
+
+/// Rejects request paths with more segments than the router's `max_depth`.
+fn check_segment_depth<T>(router: &Router<T>, segments: &[&str]) -> Result<(), RouterError> {
+    if router
+        .config
+        .max_depth
+        .is_some_and(|max_depth| segments.len() > max_depth)
+    {
+        return Err(RouterError::InvalidPath(
+            "exceeds maximum path depth".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Matches `lookup_segments` against the routing tree and builds the `MatchedRoute`,
+/// extracting parameters from `segments` (the unfolded request segments).
+#[allow(clippy::too_many_arguments)]
+fn match_segments<T: Clone + Eq>(
+    root: &Node<T>,
+    method: &str,
+    segments: &[&str],
+    lookup_segments: &[&str],
+    matched_path: MatchPath<'_>,
+    capture: bool,
+    wildcard_preference: WildcardPreference,
+    matches: &impl Fn(&MethodData<T>) -> bool,
+) -> Option<MatchedRoute<T>> {
+    let (md, is_head_derived) = select_route(
+        root,
+        method,
+        lookup_segments,
+        &matched_path,
+        wildcard_preference,
+        matches,
+    )?;
+    let params = if capture {
+        extract_all_params(segments, &md.params_map, &md.transforms, &md.defaults)
+    } else {
+        None
+    };
+    let mut matched =
+        MatchedRoute::from_method_data(md, params, matched_path.into_string(), lookup_segments);
+    matched.is_head_derived = is_head_derived;
+    Some(matched)
+}
+
+/// Picks the handler that answers `lookup_segments`, applying `wildcard_preference`
+/// when the normal lookup lands on a wildcard route.
+pub(crate) fn select_route<'a, T>(
+    root: &'a Node<T>,
+    method: &str,
+    lookup_segments: &[&str],
+    matched_path: &MatchPath<'_>,
+    wildcard_preference: WildcardPreference,
+    matches: &impl Fn(&MethodData<T>) -> bool,
+) -> Option<(&'a MethodData<T>, bool)> {
+    let (mut md, mut is_head_derived) =
+        lookup_node_recursive(root, method, lookup_segments, 0, matched_path, matches)?;
+    if wildcard_preference == WildcardPreference::LongestStaticPrefix
+        && matches!(
+            md.params_map.as_deref().and_then(<[ParamEntry]>::last),
+            Some(ParamEntry::Wildcard(..) | ParamEntry::Repeated(..))
+        )
+    {
+        let mut best = None;
+        collect_longest_static_prefix_wildcard(
+            root,
+            method,
+            lookup_segments,
+            0,
+            0,
+            matched_path,
+            matches,
+            &mut best,
+        );
+        if let Some((_, (best_md, best_head_derived))) = best {
+            (md, is_head_derived) = (best_md, best_head_derived);
         }
-        None => Err(RouterError::RouteNotFound {
-            method: method.to_string(),
-            path: path.to_string(),
+    }
+    Some((md, is_head_derived))
+}
+
+/// Turns a successful match into `RouterError::TrailingSlashRedirect` when the request
+/// path only matched after its trailing slash was removed under `TrailingSlashMode::Redirect`.
+pub(crate) fn matched_or_redirect<T: Eq>(
+    request_path: &RequestPath<'_>,
+    matched: MatchedRoute<T>,
+) -> Result<MatchedRoute<T>, RouterError> {
+    match &request_path.redirect_to {
+        Some(canonical) => Err(RouterError::TrailingSlashRedirect {
+            canonical: canonical.clone(),
         }),
+        None => Ok(matched),
     }
 }
 
+/// A handler filter that accepts every handler, for lookups without extra conditions.
+pub(crate) fn accept_any<T>(_: &MethodData<T>) -> bool {
+    true
+}
+
+/// The request path that guards see and that becomes `MatchedRoute::matched_path`.
+///
+/// `find_route_segments` receives the path already split, so it joins the segments
+/// only when a guard or a match needs the whole path; parameter allowlists and
+/// constraints read single segments either way.
+pub(crate) enum MatchPath<'a> {
+    /// A normalized request path.
+    Path(&'a str),
+    /// Request segments, joined with `/` on first use.
+    Segments(&'a [&'a str], OnceCell<String>),
+}
+
+impl<'a> MatchPath<'a> {
+    pub(crate) fn segments(segments: &'a [&'a str]) -> Self {
+        Self::Segments(segments, OnceCell::new())
+    }
+
+    /// The whole path, joining the segments if that has not happened yet.
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            Self::Path(path) => path,
+            Self::Segments(segments, joined) => joined.get_or_init(|| segments.join("/")),
+        }
+    }
+
+    /// The path segment at `idx`, if the path has that many segments.
+    pub(crate) fn segment(&self, idx: usize) -> Option<&str> {
+        match self {
+            Self::Path(path) => split_path(path).nth(idx),
+            Self::Segments(segments, _) => segments.get(idx).copied(),
+        }
+    }
+
+    fn into_string(self) -> String {
+        match self {
+            Self::Path(path) => path.to_string(),
+            Self::Segments(segments, joined) => {
+                joined.into_inner().unwrap_or_else(|| segments.join("/"))
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a str> for MatchPath<'a> {
+    fn from(path: &'a str) -> Self {
+        Self::Path(path)
+    }
+}
+
+/// Picks the handler for `method` from a node's method map, falling back to the
+/// "any" (`""`) handlers. Disabled handlers, handlers whose guard rejects the
+/// request for the normalized `path`, and handlers for which `matches` returns
+/// `false` are skipped.
+///
+/// A `HEAD` request without a `HEAD` handler falls back to the node's `GET` handler
+/// before the "any" handlers; the returned flag is `true` when that happened.
+pub(crate) fn select_handler_where<'a, T>(
+    methods: &'a StaticPathMethods<T>,
+    method: &str,
+    path: &MatchPath<'_>,
+    matches: &impl Fn(&MethodData<T>) -> bool,
+) -> Option<(&'a MethodData<T>, bool)> {
+    let first_enabled = |key: &str| {
+        methods.get(key).and_then(|handlers| {
+            handlers
+                .iter()
+                .find(|md| md.accepts(method, path) && matches(md))
+        })
+    };
+    if let Some(md) = first_enabled(method) {
+        return Some((md, false));
+    }
+    if method == "HEAD" {
+        if let Some(md) = first_enabled("GET") {
+            return Some((md, true));
+        }
+    }
+    first_enabled("").map(|md| (md, false))
+}
+
 fn is_handler_for_optional_pattern<T>(md: &MethodData<T>) -> bool {
     md.params_map.as_ref().is_some_and(|pm| {
         pm.last().is_some_and(|p_entry| match p_entry {
-            ParamEntry::Index(_, _, is_opt) => *is_opt,
+            ParamEntry::Index(param) => param.optional,
             ParamEntry::Wildcard(_, _, is_opt) => *is_opt,
+            ParamEntry::Repeated(..) => false,
         })
     })
 }
 
-fn lookup_node_recursive<'a, T: Clone + Eq>(
+fn lookup_node_recursive<'a, T>(
     node: &'a Node<T>,
     method: &str,
     segments: &[&str],
     idx: usize,
-) -> Option<&'a MethodData<T>> {
+    path: &MatchPath<'_>,
+    matches: &impl Fn(&MethodData<T>) -> bool,
+) -> Option<(&'a MethodData<T>, bool)> {
+    // Segments merged into this node by `compress` must match before anything else.
+    let idx = node.consume_edge_label(segments, idx)?;
+
     // Base case: All segments of the input path have been consumed
     if idx == segments.len() {
         // 1. Check for a handler on the current node
-        if let Some(handlers) = node.methods.get(method).or_else(|| node.methods.get("")) {
-            if let Some(md) = handlers.first() {
-                // Assuming first is highest precedence if multiple
-                return Some(md);
-            }
+        // Assuming first is highest precedence if multiple
+        if let Some(selected) = select_handler_where(&node.methods, method, path, matches) {
+            return Some(selected);
         }
 
         // 2. If no handler on current node, check if an optional parameter child can match "empty"
-        if let Some(param_child_node) = &node.param_child {
-            if let Some(handlers) = param_child_node
-                .methods
-                .get(method)
-                .or_else(|| param_child_node.methods.get(""))
+        if let Some(param_child_node) = node
+            .param_child
+            .as_ref()
+            .filter(|param_child_node| param_child_node.edge_label.is_empty())
+        {
+            if let Some(selected) =
+                select_handler_where(&param_child_node.methods, method, path, matches)
             {
-                if handlers.iter().any(is_handler_for_optional_pattern) {
-                    if let Some(md) = handlers.first() {
-                        return Some(md);
-                    }
+                if is_handler_for_optional_pattern(selected.0) {
+                    return Some(selected);
                 }
             }
         }
 
         // 3. If still no match, check if a wildcard child can match "empty"
-        // A wildcard (e.g., /foo/**:name) inherently matches an empty sequence of segments.
+        // A wildcard (e.g., /foo/**:name) inherently matches an empty sequence of segments;
+        // a repeated parameter (e.g., /foo/:tags+) needs at least one.
         if let Some(wildcard_child_node) = &node.wildcard_child {
-            if let Some(handlers) = wildcard_child_node
-                .methods
-                .get(method)
-                .or_else(|| wildcard_child_node.methods.get(""))
+            if let Some(selected) =
+                select_handler_where(&wildcard_child_node.methods, method, path, &|md| {
+                    matches(md) && !md.needs_trailing_segment()
+                })
             {
-                // If there's any handler on the wildcard child, it implies it can match an empty suffix.
-                if let Some(md) = handlers.first() {
-                    return Some(md);
-                }
+                return Some(selected);
             }
         }
         return None;
@@ -145,30 +870,157 @@ fn lookup_node_recursive<'a, T: Clone + Eq>(
 
     // 1. Try static child match
     if let Some(static_child_node) = node.static_children.get(current_segment_value) {
-        if let Some(found_md) = lookup_node_recursive(static_child_node, method, segments, idx + 1)
+        if let Some(found_md) =
+            lookup_node_recursive(static_child_node, method, segments, idx + 1, path, matches)
         {
             return Some(found_md);
         }
     }
 
-    // 2. Try parametric child match
-    if let Some(param_child_node) = &node.param_child {
-        if let Some(found_md) = lookup_node_recursive(param_child_node, method, segments, idx + 1) {
+    // 2. Try parametric child match. The empty trailing segment of a
+    // `TrailingSlashMode::Strict` path is never a parameter value.
+    if let Some(param_child_node) = node
+        .param_child
+        .as_ref()
+        .filter(|_| !current_segment_value.is_empty())
+    {
+        if let Some(found_md) =
+            lookup_node_recursive(param_child_node, method, segments, idx + 1, path, matches)
+        {
             return Some(found_md);
         }
     }
 
     // 3. Try wildcard child match (consumes all remaining segments from this point)
     if let Some(wildcard_child_node) = &node.wildcard_child {
-        if let Some(handlers) = wildcard_child_node
-            .methods
-            .get(method)
-            .or_else(|| wildcard_child_node.methods.get(""))
+        if let Some(selected) =
+            select_handler_where(&wildcard_child_node.methods, method, path, matches)
         {
-            if let Some(md) = handlers.first() {
-                return Some(md);
-            }
+            return Some(selected);
         }
     }
     None
 }
+
+/// Searches every branch of the routing tree that `segments` can follow for wildcard
+/// handlers, keeping in `best` the one with the most static segments before its `**`
+/// (`static_depth` counts the static segments on the way to `node`).
+///
+/// Branches are visited in the same order as `lookup_node_recursive` (static, then
+/// parametric, then the node's own wildcard) and only a strictly deeper static prefix
+/// replaces `best`, so ties keep the handler the normal lookup would pick.
+#[allow(clippy::too_many_arguments)]
+fn collect_longest_static_prefix_wildcard<'a, T>(
+    node: &'a Node<T>,
+    method: &str,
+    segments: &[&str],
+    idx: usize,
+    static_depth: usize,
+    path: &MatchPath<'_>,
+    matches: &impl Fn(&MethodData<T>) -> bool,
+    best: &mut Option<(usize, (&'a MethodData<T>, bool))>,
+) {
+    let Some(idx) = node.consume_edge_label(segments, idx) else {
+        return;
+    };
+    let static_depth = static_depth + node.edge_label_segments().count();
+
+    if let Some(&segment) = segments.get(idx) {
+        if let Some(static_child_node) = node.static_children.get(segment) {
+            collect_longest_static_prefix_wildcard(
+                static_child_node,
+                method,
+                segments,
+                idx + 1,
+                static_depth + 1,
+                path,
+                matches,
+                best,
+            );
+        }
+        if let Some(param_child_node) = node.param_child.as_ref().filter(|_| !segment.is_empty()) {
+            collect_longest_static_prefix_wildcard(
+                param_child_node,
+                method,
+                segments,
+                idx + 1,
+                static_depth,
+                path,
+                matches,
+                best,
+            );
+        }
+    }
+
+    if let Some(wildcard_child_node) = &node.wildcard_child {
+        if let Some(selected) =
+            select_handler_where(&wildcard_child_node.methods, method, path, &|md| {
+                matches(md) && (idx < segments.len() || !md.needs_trailing_segment())
+            })
+        {
+            if best.is_none_or(|(best_depth, _)| static_depth > best_depth) {
+                *best = Some((static_depth, selected));
+            }
+        }
+    }
+}
+
+/// Finds a route for a URL that may include a query string, capturing query parameters.
+///
+/// `full_url_path` is split at the first `?`; the part before it is matched exactly as
+/// `find_route` would match it, and the part after it is parsed as `key=value` pairs
+/// separated by `&`. A key without `=` is captured with an empty value.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method (e.g., "GET", "POST") to match.
+/// * `full_url_path`: The request path, optionally followed by `?` and a query string
+///   (e.g., `/search?q=rust&page=2`).
+/// * `capture`: Whether path parameters and query parameters should be extracted.
+///   If `false`, both `params` and `query` in the returned `MatchedRoute` are `None`.
+///
+/// # Returns
+/// * `Result<MatchedRoute<T>, RouterError>`: The same result as `find_route` for the
+///   path portion, with `query` populated when `capture` is `true` and the query
+///   string is non-empty.
+pub fn find_route_with_query<T: Clone + Eq>(
+    router: &Router<T>,
+    method: &str,
+    full_url_path: &str,
+    capture: bool,
+) -> Result<MatchedRoute<T>, RouterError> {
+    let (path, query_string) = full_url_path.split_once('?').unwrap_or((full_url_path, ""));
+    let mut matched = find_route(router, method, path, capture)?;
+    if capture {
+        matched.query = parse_query(query_string);
+    }
+    Ok(matched)
+}
+
+/// Finds the route for a request described by a `RoutableRequest`.
+///
+/// The request's method and path are matched as `find_route` would match them, and
+/// its query string, if any, is parsed as `find_route_with_query` does. This is the
+/// single entry point for routing on every part of a request; constraints on other
+/// parts of the request are applied here as they are added to the router.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `req`: The request to route.
+/// * `capture`: Whether path parameters and query parameters should be extracted.
+///
+/// # Returns
+/// * `Result<MatchedRoute<T>, RouterError>`: The same result as `find_route` for the
+///   request's path, with `query` populated when `capture` is `true` and the request
+///   has a non-empty query string.
+pub fn find_route_for_request<T: Clone + Eq>(
+    router: &Router<T>,
+    req: &RoutableRequest<'_>,
+    capture: bool,
+) -> Result<MatchedRoute<T>, RouterError> {
+    let mut matched = find_route(router, req.method, req.path, capture)?;
+    if capture {
+        matched.query = req.query.and_then(parse_query);
+    }
+    Ok(matched)
+}
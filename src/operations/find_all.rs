@@ -1,15 +1,21 @@
+use crate::compat::HashSet;
 use crate::{
     context::{Node, Router},
-    operations::util::{extract_all_params, normalize, split_path},
-    types::{MatchedRoute, MethodData, ParamEntry},
+    error::RouterError,
+    operations::{
+        find::{find_route, find_route_best, route_not_found},
+        util::{extract_all_params, split_path},
+    },
+    types::{MatchMode, MatchResult, MatchedRoute, MethodData, ParamEntry},
 };
-use std::collections::HashSet;
+use alloc::{string::ToString, vec::Vec};
 
 fn is_last_param_optional_for_find_all<T>(md: &MethodData<T>) -> bool {
     md.params_map.as_ref().is_some_and(|pm| {
         pm.last().is_some_and(|p_entry| match p_entry {
-            ParamEntry::Index(_, _, is_opt) => *is_opt,
+            ParamEntry::Index(param) => param.optional,
             ParamEntry::Wildcard(_, _, is_opt) => *is_opt,
+            ParamEntry::Repeated(..) => false,
         })
     })
 }
@@ -24,9 +30,10 @@ fn is_last_param_optional_for_find_all<T>(md: &MethodData<T>) -> bool {
 /// - If `method_filter` is an empty string `""`, it *only* returns routes that
 ///   were explicitly registered with an empty method string `""`.
 ///
-/// To avoid duplicates if the same data payload `T` is registered for multiple
-/// route patterns (which is possible), this function uses a `HashSet` to track
-/// seen data payloads, ensuring each unique `T` is returned only once.
+/// Each route pattern is returned at most once, so several handlers registered on
+/// the same pattern (e.g., with different priorities) yield only the first that
+/// accepts the request. Routes on different patterns are all returned, even when they
+/// carry equal data.
 /// The `params` field in the returned `MatchedRoute` instances will be `None`,
 /// as `find_all_routes` does not perform path matching or parameter extraction.
 ///
@@ -41,18 +48,26 @@ fn is_last_param_optional_for_find_all<T>(md: &MethodData<T>) -> bool {
 /// # Returns
 /// * `Vec<MatchedRoute<T>>`: A vector of `MatchedRoute` instances. The `data` field
 ///   contains the user-provided data, and `params` is always `None`.
-///   `T` must implement `Clone + Eq + std::hash::Hash` for deduplication.
 ///
 /// # Panics
 /// This function may panic if acquiring read locks on the router's internal structures fails.
-pub fn find_all_routes<T: Clone + Eq + std::hash::Hash>(
+pub fn find_all_routes<T: Clone + Eq>(
     router: &Router<T>,
     method: &str,
     path: &str,
     capture_params: bool,
 ) -> Vec<MatchedRoute<T>> {
-    let normalized_path_string = normalize(path);
-    let segments: Vec<&str> = split_path(&normalized_path_string).collect();
+    let Ok(request_path) = router.prepare_request_path(method, path) else {
+        return Vec::new();
+    };
+    let segments: Vec<&str> = split_path(&request_path.normalized).collect();
+    let folded_segments: Vec<&str>;
+    let lookup_segments = if request_path.is_folded() {
+        folded_segments = split_path(request_path.lookup()).collect();
+        &folded_segments
+    } else {
+        &segments
+    };
 
     let mut collected_method_data_refs: Vec<&MethodData<T>> = Vec::new();
     let root_lock = router.root.read();
@@ -60,38 +75,173 @@ pub fn find_all_routes<T: Clone + Eq + std::hash::Hash>(
     find_all_recursive_ordered(
         &*root_lock,
         method,
-        &segments,
+        lookup_segments,
         0,
         &mut collected_method_data_refs,
     );
 
-    let mut results = Vec::new();
-    let mut seen_t_values = HashSet::new();
+    build_matches(
+        collected_method_data_refs,
+        method,
+        &request_path.normalized,
+        &segments,
+        lookup_segments,
+        capture_params,
+    )
+}
+
+/// Like `find_all_routes`, but looks the path up in the static path map and walks
+/// the routing tree as two parallel `rayon` tasks.
+///
+/// The results are the same as `find_all_routes`, in the same order: static handlers
+/// are also stored in the trie, so the static map's matches are merged after the
+/// trie's and dropped by the same deduplication. The split only pays off when the
+/// trie walk is expensive (e.g., thousands of wildcard routes); for small routers the
+/// cost of handing work to the thread pool outweighs it.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+#[cfg(feature = "rayon")]
+pub fn find_all_routes_parallel<T: Clone + Eq + Send + Sync>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    capture: bool,
+) -> Vec<MatchedRoute<T>> {
+    let Ok(request_path) = router.prepare_request_path(method, path) else {
+        return Vec::new();
+    };
+    let segments: Vec<&str> = split_path(&request_path.normalized).collect();
+    let folded_segments: Vec<&str>;
+    let lookup_segments = if request_path.is_folded() {
+        folded_segments = split_path(request_path.lookup()).collect();
+        &folded_segments
+    } else {
+        &segments
+    };
+
+    let root_lock = router.root.read();
+    let static_map_lock = router.static_map.read();
+    let (mut collected_method_data_refs, static_method_data_refs) = rayon::join(
+        || {
+            let mut trie_refs = Vec::new();
+            find_all_recursive_ordered(&*root_lock, method, lookup_segments, 0, &mut trie_refs);
+            trie_refs
+        },
+        || {
+            static_map_lock
+                .get(request_path.lookup())
+                .and_then(|methods| methods.get(method).or_else(|| methods.get("")))
+                .map_or_else(Vec::new, |handlers| handlers.iter().collect::<Vec<_>>())
+        },
+    );
+    collected_method_data_refs.extend(static_method_data_refs);
+
+    build_matches(
+        collected_method_data_refs,
+        method,
+        &request_path.normalized,
+        &segments,
+        lookup_segments,
+        capture,
+    )
+}
+
+/// Turns the handlers collected for a path into `MatchedRoute`s, skipping handlers
+/// that do not accept the request and handlers whose route pattern was already returned.
+fn build_matches<T: Clone + Eq>(
+    method_data_refs: Vec<&MethodData<T>>,
+    method: &str,
+    normalized_path: &str,
+    segments: &[&str],
+    lookup_segments: &[&str],
+    capture_params: bool,
+) -> Vec<MatchedRoute<T>> {
+    let mut results: Vec<MatchedRoute<T>> = Vec::new();
+    let mut seen_patterns = HashSet::default();
 
-    for md_ref in collected_method_data_refs {
-        if seen_t_values.insert(md_ref.data.clone()) {
-            // Deduplicate by T value
+    for md_ref in method_data_refs {
+        if !md_ref.accepts(method, &normalized_path.into()) {
+            continue;
+        }
+        let matched = MatchedRoute::from_method_data(
+            md_ref,
+            None,
+            normalized_path.to_string(),
+            lookup_segments,
+        );
+        if seen_patterns.insert(matched.route_pattern.clone()) {
             let params = if capture_params {
-                extract_all_params(&segments, &md_ref.params_map)
+                extract_all_params(
+                    segments,
+                    &md_ref.params_map,
+                    &md_ref.transforms,
+                    &md_ref.defaults,
+                )
             } else {
                 None
             };
-            results.push(MatchedRoute {
-                data: md_ref.data.clone(),
-                params,
-            });
+            results.push(MatchedRoute { params, ..matched });
         }
     }
     results
 }
 
-fn find_all_recursive_ordered<'a, T: Clone + Eq + std::hash::Hash>(
+/// Finds routes matching the given HTTP method and path, selected by `mode`.
+///
+/// This puts `find_route` and `find_all_routes` behind one function:
+/// - `MatchMode::First` returns what `find_route` returns.
+/// - `MatchMode::Best` returns what `find_route_best` returns.
+/// - `MatchMode::All` returns everything `find_all_routes` returns.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method (e.g., "GET", "POST") to match.
+/// * `path`: The request path to match against the router's patterns.
+/// * `capture`: A boolean indicating whether path parameters should be extracted.
+/// * `mode`: Which matches to return.
+///
+/// # Returns
+/// * `Result<MatchResult<T>, RouterError>`:
+///   - `Ok(MatchResult::Single(_))` for `MatchMode::First` and `MatchMode::Best`.
+///   - `Ok(MatchResult::Multiple(_))` for `MatchMode::All`, never empty.
+///   - `Err(RouterError::RouteNotFound)` if no route matches, in every mode, or
+///     `Err(RouterError::MethodNotAllowed)` if routes match only for other methods.
+///     For `MatchMode::First`, any other error from `find_route` is returned as is.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn find_route_with_mode<T: Clone + Eq>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    capture: bool,
+    mode: MatchMode,
+) -> Result<MatchResult<T>, RouterError> {
+    match mode {
+        MatchMode::First => find_route(router, method, path, capture).map(MatchResult::Single),
+        MatchMode::Best => find_route_best(router, method, path, capture).map(MatchResult::Single),
+        MatchMode::All => {
+            let matches = find_all_routes(router, method, path, capture);
+            if matches.is_empty() {
+                return Err(route_not_found(router, method, path));
+            }
+            Ok(MatchResult::Multiple(matches))
+        }
+    }
+}
+
+fn find_all_recursive_ordered<'a, T: Clone + Eq>(
     node: &'a Node<T>,
     method: &str,
     segments: &[&str],
     idx: usize,
     matches: &mut Vec<&'a MethodData<T>>,
 ) {
+    let Some(idx) = node.consume_edge_label(segments, idx) else {
+        return;
+    };
+
     // 1. Wildcard child of current node (matches remaining segments from this point)
     if let Some(wildcard_child_node) = &node.wildcard_child {
         if let Some(handlers) = wildcard_child_node
@@ -99,7 +249,11 @@ fn find_all_recursive_ordered<'a, T: Clone + Eq + std::hash::Hash>(
             .get(method)
             .or_else(|| wildcard_child_node.methods.get(""))
         {
-            matches.extend(handlers.iter());
+            matches.extend(
+                handlers
+                    .iter()
+                    .filter(|md| idx < segments.len() || !md.needs_trailing_segment()),
+            );
         }
     }
 
@@ -111,10 +265,11 @@ fn find_all_recursive_ordered<'a, T: Clone + Eq + std::hash::Hash>(
 
     // 2. Parametric child
     if let Some(param_child_node) = &node.param_child {
-        if current_segment_val.is_some() {
+        // The empty trailing segment of a `TrailingSlashMode::Strict` path is never a value.
+        if current_segment_val.is_some_and(|segment| !segment.is_empty()) {
             find_all_recursive_ordered(param_child_node, method, segments, idx + 1, matches);
         }
-        if idx == segments.len() {
+        if idx == segments.len() && param_child_node.edge_label.is_empty() {
             // Path ends here, check if param child can match optionally
             if let Some(handlers) = param_child_node
                 .methods
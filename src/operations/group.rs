@@ -0,0 +1,73 @@
+use crate::{
+    context::{NodeRef, RouteGroup, Router, iter_nodes},
+    error::RouterError,
+};
+use alloc::{collections::BTreeSet, format, vec::Vec};
+
+/// Adds the routes of one version of a REST API under a `/v{version}` prefix.
+///
+/// `builder` is called with a `RouteGroup` for `/v{version}`, so `/users` added to
+/// it for version 2 registers `/v2/users`. The builder returns the first error from
+/// `RouteGroup::add` with `?`, and that error is returned from this function.
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, add_versioned_routes, find_route, list_api_versions};
+///
+/// let router = Router::new();
+/// add_versioned_routes(&router, 1, |v1| v1.add("GET", "/users", "list_users_v1")).unwrap();
+/// add_versioned_routes(&router, 2, |v2| {
+///     v2.add("GET", "/users", "list_users_v2")?;
+///     v2.add("GET", "/users/:id", "get_user_v2")
+/// })
+/// .unwrap();
+///
+/// assert_eq!(find_route(&router, "GET", "/v2/users", false).unwrap().data, "list_users_v2");
+/// assert_eq!(list_api_versions(&router), [1, 2]);
+/// ```
+///
+/// # Errors
+/// Returns the error `builder` returns, typically from a rejected route pattern.
+pub fn add_versioned_routes<T, F>(
+    router: &Router<T>,
+    version: u32,
+    builder: F,
+) -> Result<(), RouterError>
+where
+    T: Clone,
+    F: FnOnce(&RouteGroup<'_, T>) -> Result<(), RouterError>,
+{
+    builder(&RouteGroup::new(router, &format!("/v{version}")))
+}
+
+/// Lists the API versions the router has routes for, in ascending order.
+///
+/// A route belongs to version `N` when the first segment of its pattern, after the
+/// router's global prefix, is `vN` (as registered by `add_versioned_routes`).
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn list_api_versions<T: Clone + Eq + core::hash::Hash>(router: &Router<T>) -> Vec<u32> {
+    let prefix_depth = router
+        .config
+        .global_prefix
+        .as_deref()
+        .map_or(0, |prefix| prefix.split('/').count());
+    let mut versions = BTreeSet::new();
+    for NodeRef {
+        path_prefix, node, ..
+    } in iter_nodes(&router.root.read())
+    {
+        if node.methods.is_empty() {
+            continue;
+        }
+        let version = path_prefix
+            .split('/')
+            .nth(1 + prefix_depth)
+            .and_then(|segment| segment.strip_prefix('v'))
+            .filter(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|digits| digits.parse::<u32>().ok());
+        versions.extend(version);
+    }
+    versions.into_iter().collect()
+}
@@ -0,0 +1,130 @@
+use crate::{
+    context::{NodeRef, Router, iter_nodes},
+    operations::util::render_handler_pattern,
+    types::{IndexParam, MethodData, ParamEntry, ParamKind, ParamSchema, RouteEntry, RouteSchema},
+};
+use alloc::{string::ToString, vec::Vec};
+
+/// Lists every route registered in the router.
+///
+/// The routing tree is walked depth-first and each handler's pattern is rebuilt
+/// from the static segments along its branch and the `ParamEntry` list it was
+/// registered with, so the returned patterns can be passed straight back to
+/// `add_route`.
+///
+/// Routes are returned in a stable order: handlers on a node come before its
+/// children, methods are sorted, and children are visited static (sorted by
+/// segment), then parametric, then wildcard.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+///
+/// # Returns
+/// * `Vec<RouteEntry<T>>`: One entry per registered `(method, pattern)` handler.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn list_routes<T: Clone>(router: &Router<T>) -> Vec<RouteEntry<T>> {
+    let mut entries = Vec::new();
+    for_each_handler(router, |method, segments, md| {
+        entries.push(RouteEntry {
+            method: method.to_string(),
+            pattern: render_handler_pattern(segments, md),
+            data: md.data.clone(),
+            description: md.description.clone(),
+        });
+    });
+    entries
+}
+
+/// Describes the pattern and parameters of every route registered in the router.
+///
+/// Routes are returned in the same order as `list_routes`. Each route's parameters
+/// are taken from the `ParamEntry` list it was registered with, in pattern order, so
+/// OpenAPI generators, client SDK generators, and documentation tools don't have to
+/// parse patterns themselves.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+///
+/// # Returns
+/// * `Vec<RouteSchema>`: One schema per registered `(method, pattern)` handler.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn route_schemas<T: Clone + Eq + core::hash::Hash>(router: &Router<T>) -> Vec<RouteSchema> {
+    let mut schemas = Vec::new();
+    for_each_handler(router, |method, segments, md| {
+        let params = md
+            .params_map
+            .iter()
+            .flatten()
+            .map(|entry| {
+                let (kind, position, name, optional) = match entry {
+                    ParamEntry::Index(IndexParam {
+                        idx,
+                        name,
+                        optional,
+                        ..
+                    }) => (ParamKind::Index, idx, name, optional),
+                    ParamEntry::Wildcard(idx, name, optional) => {
+                        (ParamKind::Wildcard, idx, name, optional)
+                    }
+                    ParamEntry::Repeated(idx, name) => (ParamKind::Repeated, idx, name, &false),
+                };
+                ParamSchema {
+                    name: name.clone(),
+                    kind,
+                    optional: *optional,
+                    position: *position,
+                }
+            })
+            .collect();
+        schemas.push(RouteSchema {
+            method: method.to_string(),
+            pattern: render_handler_pattern(segments, md),
+            params,
+        });
+    });
+    schemas
+}
+
+/// Calls `visit` with the method, pattern segments, and `MethodData` of every handler,
+/// in `list_routes` order.
+///
+/// Placeholder segments (`:*`, `**`) are passed as-is; `render_pattern` replaces them
+/// using the handler's `ParamEntry` list.
+fn for_each_handler<T>(router: &Router<T>, mut visit: impl FnMut(&str, &[&str], &MethodData<T>)) {
+    let root_lock = router.root.read();
+    for NodeRef {
+        path_prefix, node, ..
+    } in iter_nodes(&root_lock)
+    {
+        let segments: Vec<&str> = path_prefix.split('/').skip(1).collect();
+        let mut methods: Vec<_> = node.methods.iter().collect();
+        methods.sort_by_key(|(method, _)| method.as_str());
+        for (method, handlers) in methods {
+            for md in handlers {
+                visit(method, &segments, md);
+            }
+        }
+    }
+}
+
+/// Counts the routes registered in the router.
+///
+/// This is the number of entries `list_routes` would return (one per registered
+/// `(method, pattern)` handler), without cloning any route data.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn route_count<T>(router: &Router<T>) -> usize {
+    iter_nodes(&router.root.read())
+        .map(|NodeRef { node, .. }| {
+            node.methods
+                .values()
+                .map(|handlers| handlers.len())
+                .sum::<usize>()
+        })
+        .sum()
+}
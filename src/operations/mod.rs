@@ -1,10 +1,57 @@
 pub mod add;
+pub mod analyze;
+#[cfg(feature = "arc-swap")]
+pub mod arc_ops;
+pub mod diff;
+pub mod exact;
 pub mod find;
 pub mod find_all;
+pub mod group;
+pub mod list;
+pub mod namespace;
+pub mod negotiate;
+pub mod options;
 pub mod remove;
+pub mod shared;
 pub mod util;
+pub mod vhost;
+#[cfg(feature = "rand")]
+pub mod weighted;
 
-pub use add::add_route;
-pub use find::find_route;
-pub use find_all::find_all_routes;
-pub use remove::remove_route;
+#[cfg(feature = "serde_json")]
+pub use add::add_route_with_attrs;
+pub use add::{
+    add_deprecated_route, add_route, add_route_for_host, add_route_multi, add_route_producing,
+    add_route_with_allowlist, add_route_with_cache, add_route_with_cors, add_route_with_defaults,
+    add_route_with_description, add_route_with_guard, add_route_with_middleware,
+    add_route_with_priority, add_route_with_rate_limit, add_route_with_scopes,
+    add_route_with_timeout, add_route_with_transforms, add_upgrade_route, add_weighted_route,
+    disable_route, enable_route,
+};
+pub use analyze::{
+    AmbiguityReport, HotPathSuggestion, RouterValidation, RouterWarning, analyze_hot_paths,
+    find_ambiguous_routes, validate,
+};
+#[cfg(feature = "arc-swap")]
+pub use arc_ops::{add_route_arc, find_route_arc, remove_route_arc};
+pub use diff::{RouterDiff, diff_routers};
+pub use exact::{add_static, find_static};
+#[cfg(feature = "tracing")]
+pub use find::find_route_traced;
+pub use find::{
+    find_route, find_route_best, find_route_borrowed, find_route_chain, find_route_for_request,
+    find_route_normalized, find_route_segments, find_route_with_query, find_routes_batch,
+};
+#[cfg(feature = "rayon")]
+pub use find_all::find_all_routes_parallel;
+pub use find_all::{find_all_routes, find_route_with_mode};
+pub use group::{add_versioned_routes, list_api_versions};
+pub use list::{list_routes, route_count, route_schemas};
+pub use namespace::{add_namespace, find_in_namespace, remove_namespace};
+pub use negotiate::find_route_accepting;
+pub use options::get_options_response;
+pub use remove::{drain_method_routes, remove_route};
+pub use shared::{add_route_shared, get_route_handle};
+pub use vhost::{add_host, find_in_host, find_route_with_host};
+#[cfg(feature = "rand")]
+pub use weighted::find_route_weighted;
@@ -0,0 +1,71 @@
+use crate::{
+    context::{NamespaceRouter, Router},
+    error::RouterError,
+    operations::find::find_route,
+    types::MatchedRoute,
+};
+use alloc::{string::ToString, sync::Arc};
+
+/// Registers `router` to serve requests for the namespace `name`.
+///
+/// Namespace names are matched exactly. Registering the same name twice replaces
+/// the previous router.
+///
+/// # Arguments
+/// * `ns`: A reference to the `NamespaceRouter` instance.
+/// * `name`: The namespace name (e.g., a tenant id).
+/// * `router`: The router handling requests for this namespace.
+///
+/// # Panics
+/// This function may panic if acquiring the write lock on the namespace map fails.
+pub fn add_namespace<T>(ns: &NamespaceRouter<T>, name: &str, router: Arc<Router<T>>) {
+    ns.namespaces.write().insert(name.to_string(), router);
+}
+
+/// Unregisters the namespace `name`.
+///
+/// Lookups already in progress keep using the router until they finish.
+///
+/// # Arguments
+/// * `ns`: A reference to the `NamespaceRouter` instance.
+/// * `name`: The namespace name to remove.
+///
+/// # Returns
+/// * `Option<Arc<Router<T>>>`: The namespace's router, or `None` if `name` was not
+///   registered.
+///
+/// # Panics
+/// This function may panic if acquiring the write lock on the namespace map fails.
+pub fn remove_namespace<T>(ns: &NamespaceRouter<T>, name: &str) -> Option<Arc<Router<T>>> {
+    ns.namespaces.write().remove(name)
+}
+
+/// Finds a route in the router registered for `namespace`.
+///
+/// # Arguments
+/// * `ns`: A reference to the `NamespaceRouter` instance.
+/// * `namespace`: The namespace name whose router is searched.
+/// * `method`, `path`, `capture`: Passed through to `find_route` on that router.
+///
+/// # Returns
+/// * `Result<MatchedRoute<T>, RouterError>`: The result of `find_route` on the
+///   namespace's router, or `Err(RouterError::RouteNotFound)` if no router is
+///   registered for `namespace`.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the namespace map or the
+/// namespace's router fails.
+pub fn find_in_namespace<T: Clone + Eq>(
+    ns: &NamespaceRouter<T>,
+    namespace: &str,
+    method: &str,
+    path: &str,
+    capture: bool,
+) -> Result<MatchedRoute<T>, RouterError> {
+    // Clone the `Arc` so the namespace map is not locked during the lookup.
+    let router = ns.namespaces.read().get(namespace).cloned();
+    match router {
+        Some(router) => find_route(&router, method, path, capture),
+        None => Err(RouterError::not_found(method, path)),
+    }
+}
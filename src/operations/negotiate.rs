@@ -0,0 +1,184 @@
+use crate::{
+    context::Router,
+    error::RouterError,
+    operations::find::{accept_any, find_prepared, method_not_allowed_or},
+    types::{MatchedRoute, MethodData},
+};
+use alloc::vec::Vec;
+
+/// A media range of an `Accept` header (e.g., `text/*;q=0.5`).
+struct MediaRange<'a> {
+    type_: &'a str,
+    subtype: &'a str,
+    /// The quality factor, in thousandths.
+    quality: u16,
+}
+
+impl MediaRange<'_> {
+    /// How specific the range is: 2 for `type/subtype`, 1 for `type/*`, 0 for `*/*`.
+    fn precedence(&self) -> u8 {
+        match (self.type_, self.subtype) {
+            ("*", _) => 0,
+            (_, "*") => 1,
+            _ => 2,
+        }
+    }
+
+    fn matches(&self, type_: &str, subtype: &str) -> bool {
+        (self.type_ == "*" || self.type_.eq_ignore_ascii_case(type_))
+            && (self.subtype == "*" || self.subtype.eq_ignore_ascii_case(subtype))
+    }
+}
+
+/// Parses an `Accept` header into its media ranges, skipping malformed entries.
+/// A missing or malformed `q` parameter counts as `q=1`.
+fn parse_accept(accept: &str) -> Vec<MediaRange<'_>> {
+    accept
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let (type_, subtype) = parts.next()?.trim().split_once('/')?;
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(parse_quality)
+                .unwrap_or(1000);
+            Some(MediaRange {
+                type_: type_.trim(),
+                subtype: subtype.trim(),
+                quality,
+            })
+        })
+        .collect()
+}
+
+/// Parses a quality value (`0` to `1` with up to three decimals) into thousandths.
+fn parse_quality(value: &str) -> Option<u16> {
+    let quality = value.trim().parse::<f32>().ok()?;
+    (0.0..=1.0)
+        .contains(&quality)
+        // Non-negative, so adding one half before truncating rounds to nearest
+        // without `f32::round`, which `core` lacks.
+        .then_some((quality * 1000.0 + 0.5) as u16)
+}
+
+/// The quality the `Accept` header gives `content_type`: that of the most specific
+/// matching media range, or 0 if none matches.
+fn quality_of(ranges: &[MediaRange<'_>], content_type: &str) -> u16 {
+    let essence = content_type.split(';').next().unwrap_or_default().trim();
+    let Some((type_, subtype)) = essence.split_once('/') else {
+        return 0;
+    };
+    ranges
+        .iter()
+        .filter(|range| range.matches(type_, subtype))
+        .max_by_key(|range| range.precedence())
+        .map_or(0, |range| range.quality)
+}
+
+/// The highest quality among the content types `md` produces. Handlers without
+/// content types are not negotiated and have quality 0.
+fn handler_quality<T>(ranges: &[MediaRange<'_>], md: &MethodData<T>) -> u16 {
+    md.produces
+        .iter()
+        .flatten()
+        .map(|content_type| quality_of(ranges, content_type))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Finds the route for a request whose content types best match its `Accept` header.
+///
+/// Among the handlers matching `method` and `path`, the one producing the content type
+/// with the highest quality factor (`q`) in `accept` is returned; a media type's
+/// quality is that of the most specific range matching it, so
+/// `*/*, application/xml;q=0` rules out XML. Ties go to the handler `find_route` would
+/// try first. Handlers added without content types (e.g., with `add_route`) are only
+/// used when no handler producing an acceptable type matches. An empty `accept`
+/// behaves like `*/*`.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method (e.g., "GET", "POST") to match.
+/// * `path`: The request path to match against the router's patterns.
+/// * `accept`: The request's `Accept` header value (e.g., `application/json, */*;q=0.1`).
+/// * `capture`: A boolean indicating whether path parameters should be extracted.
+///
+/// # Returns
+/// * `Result<MatchedRoute<T>, RouterError>`: The best matching route, or the error
+///   `find_route` returns if no route matches at all, or
+///   `Err(RouterError::NotAcceptable)` if routes match but none produces an
+///   acceptable content type (answer `406 Not Acceptable`).
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, add_route_producing, find_route_accepting};
+///
+/// let router = Router::new();
+/// add_route_producing(&router, "GET", "/report", "xml", &["application/xml"]).unwrap();
+/// add_route_producing(&router, "GET", "/report", "json", &["application/json"]).unwrap();
+///
+/// let accept = "application/xml;q=0.5, application/json";
+/// assert_eq!(find_route_accepting(&router, "GET", "/report", accept, false).unwrap().data, "json");
+/// ```
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn find_route_accepting<T: Clone + Eq>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    accept: &str,
+    capture: bool,
+) -> Result<MatchedRoute<T>, RouterError> {
+    let accept = if accept.trim().is_empty() {
+        "*/*"
+    } else {
+        accept
+    };
+    let ranges = parse_accept(accept);
+    let request_path = router.prepare_request_path(method, path)?;
+
+    let mut qualities: Vec<u16> = ranges
+        .iter()
+        .map(|range| range.quality)
+        .filter(|&q| q > 0)
+        .collect();
+    qualities.sort_unstable_by(|a, b| b.cmp(a));
+    qualities.dedup();
+    // The first handler reaching the highest quality any handler can reach wins.
+    for quality in qualities {
+        match find_prepared(router, method, &request_path, path, capture, &|md| {
+            handler_quality(&ranges, md) >= quality
+        }) {
+            Err(RouterError::RouteNotFound { .. }) => continue,
+            result => return notify_match(router, method, result),
+        }
+    }
+    match find_prepared(router, method, &request_path, path, capture, &|md| {
+        md.produces.is_none()
+    }) {
+        Err(RouterError::RouteNotFound { .. }) => {}
+        result => return notify_match(router, method, result),
+    }
+
+    // Tell "nothing matches" apart from "nothing acceptable matches".
+    Err(
+        match find_prepared(router, method, &request_path, path, false, &accept_any) {
+            Ok(_) => RouterError::not_acceptable(method, path),
+            Err(err) => {
+                method_not_allowed_or(&router.root.read(), method, &request_path, path, err)
+            }
+        },
+    )
+}
+
+fn notify_match<T: Eq>(
+    router: &Router<T>,
+    method: &str,
+    result: Result<MatchedRoute<T>, RouterError>,
+) -> Result<MatchedRoute<T>, RouterError> {
+    if let (Ok(matched), Some(on_match)) = (&result, &router.on_match) {
+        on_match(method, &matched.matched_path, &matched.route_pattern);
+    }
+    result
+}
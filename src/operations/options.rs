@@ -0,0 +1,138 @@
+use crate::{
+    context::{Node, Router, StaticPathMethods},
+    operations::util::split_path,
+    types::{MethodData, OptionsResponse, ParamEntry},
+};
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Builds the response to an `OPTIONS` request for `path`.
+///
+/// Every route pattern that matches `path` is considered, regardless of method, and
+/// the methods registered on them are collected. `OPTIONS` is always added, and
+/// `HEAD` is added when `GET` is present since `find_route` answers `HEAD` with the
+/// `GET` handler. Disabled routes and "any" (`""`) handlers are not listed, as the
+/// latter have no single method name to advertise.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `path`: The request path the `OPTIONS` request was made for.
+///
+/// # Returns
+/// * `OptionsResponse`: The sorted allowed methods and the matching `Allow` header
+///   value. Both are empty if no route matches `path`.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn get_options_response<T: Clone>(router: &Router<T>, path: &str) -> OptionsResponse {
+    let Ok(request_path) = router.prepare_request_path("OPTIONS", path) else {
+        return OptionsResponse::default();
+    };
+    let mut methods = allowed_methods(&router.root.read(), request_path.lookup());
+    if methods.is_empty() {
+        return OptionsResponse::default();
+    }
+
+    methods.insert("OPTIONS".to_string());
+    let allowed_methods: Vec<String> = methods.into_iter().collect();
+    OptionsResponse {
+        allow_header: allowed_methods.join(", "),
+        allowed_methods,
+    }
+}
+
+/// Collects the methods of the enabled handlers on every route pattern that matches
+/// the (prepared) `lookup_path`, plus `HEAD` when `GET` is present. "Any" (`""`)
+/// handlers are not included.
+pub(crate) fn allowed_methods<T>(root: &Node<T>, lookup_path: &str) -> BTreeSet<String> {
+    let segments: Vec<&str> = split_path(lookup_path).collect();
+    allowed_methods_for_segments(root, &segments)
+}
+
+/// Like `allowed_methods`, for a lookup path that is already split into segments.
+pub(crate) fn allowed_methods_for_segments<T>(
+    root: &Node<T>,
+    segments: &[&str],
+) -> BTreeSet<String> {
+    let mut methods = BTreeSet::new();
+    collect_methods_recursive(root, segments, 0, &mut methods);
+    with_head_for_get(methods)
+}
+
+/// Like `allowed_methods`, for the handlers a static map holds for a single path.
+pub(crate) fn allowed_static_methods<T>(handlers: &StaticPathMethods<T>) -> BTreeSet<String> {
+    let mut methods = BTreeSet::new();
+    insert_enabled_methods(handlers, &mut methods, |_| true);
+    with_head_for_get(methods)
+}
+
+fn with_head_for_get(mut methods: BTreeSet<String>) -> BTreeSet<String> {
+    if methods.contains("GET") {
+        methods.insert("HEAD".to_string());
+    }
+    methods
+}
+
+fn insert_enabled_methods<T>(
+    handlers: &StaticPathMethods<T>,
+    methods: &mut BTreeSet<String>,
+    accept: impl Fn(&MethodData<T>) -> bool,
+) {
+    for (method, handlers) in handlers {
+        if !method.is_empty() && handlers.iter().any(|md| md.is_enabled() && accept(md)) {
+            methods.insert(method.clone());
+        }
+    }
+}
+
+fn is_last_param_optional<T>(md: &MethodData<T>) -> bool {
+    md.params_map.as_ref().is_some_and(|pm| {
+        pm.last().is_some_and(|p_entry| match p_entry {
+            ParamEntry::Index(param) => param.optional,
+            ParamEntry::Wildcard(_, _, is_opt) => *is_opt,
+            ParamEntry::Repeated(..) => false,
+        })
+    })
+}
+
+fn collect_methods_recursive<T>(
+    node: &Node<T>,
+    segments: &[&str],
+    idx: usize,
+    methods: &mut BTreeSet<String>,
+) {
+    let Some(idx) = node.consume_edge_label(segments, idx) else {
+        return;
+    };
+
+    if let Some(wildcard_child_node) = &node.wildcard_child {
+        insert_enabled_methods(&wildcard_child_node.methods, methods, |md| {
+            idx < segments.len() || !md.needs_trailing_segment()
+        });
+    }
+
+    if idx == segments.len() {
+        insert_enabled_methods(&node.methods, methods, |_| true);
+        if let Some(param_child_node) = node
+            .param_child
+            .as_ref()
+            .filter(|param_child_node| param_child_node.edge_label.is_empty())
+        {
+            insert_enabled_methods(&param_child_node.methods, methods, is_last_param_optional);
+        }
+        return;
+    }
+
+    let current_segment_value = segments[idx];
+    if let Some(static_child_node) = node.static_children.get(current_segment_value) {
+        collect_methods_recursive(static_child_node, segments, idx + 1, methods);
+    }
+    if let Some(param_child_node) = &node.param_child {
+        if !current_segment_value.is_empty() {
+            collect_methods_recursive(param_child_node, segments, idx + 1, methods);
+        }
+    }
+}
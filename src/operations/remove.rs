@@ -1,8 +1,9 @@
 use crate::{
     context::{Node, Router},
     error::RouterError,
-    operations::util::{normalize, split_path},
+    operations::util::{is_wildcard_segment, split_path},
 };
+use alloc::vec::Vec;
 
 /// Removes a route handler.
 ///
@@ -21,7 +22,8 @@ use crate::{
 /// * `router`: A reference to the `Router` instance.
 /// * `method`: The HTTP method of the route handler to remove.
 /// * `path_pattern_to_remove`: The path pattern of the route handler to remove.
-///   This must exactly match the pattern used when the route was added.
+///   This must exactly match the pattern used when the route was added (without
+///   the router's global prefix, if one is set).
 ///
 /// # Returns
 /// * `Result<usize, RouterError>`:
@@ -38,7 +40,7 @@ pub fn remove_route<T>(
     method: &str,
     path_pattern_to_remove: &str,
 ) -> Result<bool, RouterError> {
-    let normalized_path_string = normalize(path_pattern_to_remove);
+    let normalized_path_string = router.normalize_pattern(path_pattern_to_remove)?;
     let segments: Vec<&str> = split_path(&normalized_path_string).collect();
 
     let mut root_lock = router.root.write();
@@ -58,6 +60,8 @@ pub fn remove_route<T>(
         modified_in_trie = recurse_remove(&mut *root_lock, method, &segments, 0);
     }
 
+    drop(root_lock);
+
     let mut modified_in_static_map = false;
     if !normalized_path_string.contains([':', '*']) {
         let mut static_map_lock = router.static_map.write();
@@ -71,7 +75,13 @@ pub fn remove_route<T>(
         }
     }
 
-    Ok(modified_in_trie || modified_in_static_map)
+    let removed = modified_in_trie || modified_in_static_map;
+    if removed {
+        if let Some(on_remove) = &router.on_remove {
+            on_remove(method, path_pattern_to_remove);
+        }
+    }
+    Ok(removed)
 }
 
 /// Recursively traverses and removes handlers. Returns true if modification happened in the subtree.
@@ -81,6 +91,10 @@ fn recurse_remove<T>(
     pattern_segments: &[&str],
     idx: usize,
 ) -> bool {
+    let Some(idx) = current_node.consume_edge_label(pattern_segments, idx) else {
+        return false;
+    };
+
     if idx >= pattern_segments.len() {
         let mut handler_removed_at_this_node = false;
         if let Some(handlers) = current_node.methods.get_mut(method) {
@@ -106,7 +120,7 @@ fn recurse_remove<T>(
         .strip_suffix('?')
         .unwrap_or(segment_str_of_pattern);
 
-    if temp_segment_for_type_check.starts_with("**") {
+    if is_wildcard_segment(temp_segment_for_type_check) {
         if let Some(wc_child_box) = current_node.wildcard_child.as_mut() {
             if recurse_remove(wc_child_box, method, pattern_segments, idx + 1) {
                 modified_in_child_branch = true;
@@ -130,9 +144,69 @@ fn recurse_remove<T>(
         if recurse_remove(static_child_box, method, pattern_segments, idx + 1) {
             modified_in_child_branch = true;
             if static_child_box.as_ref().is_empty_recursive() {
-                current_node.static_children.remove(segment_str_of_pattern);
+                current_node
+                    .static_children
+                    .shift_remove(segment_str_of_pattern);
             }
         }
     }
     modified_in_child_branch
 }
+
+/// Removes every handler registered for `method`, whatever its path.
+///
+/// Both the routing tree and the `static_map` are locked for the whole operation, so
+/// concurrent lookups see either all of the method's routes or none of them. Nodes
+/// left without handlers or children are pruned, as in `remove_route`. This is
+/// useful for switching off a whole class of requests at once, such as every
+/// `POST` route during a maintenance window.
+///
+/// The router's `on_remove` hook is not called, since no single pattern is removed.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method whose handlers are removed. `""` removes only the
+///   "any" method handlers.
+///
+/// # Returns
+/// * `Result<usize, RouterError>`: The number of handlers removed.
+///
+/// # Panics
+/// This function may panic if acquiring write locks on the router's internal structures fails.
+pub fn drain_method_routes<T: Clone>(
+    router: &Router<T>,
+    method: &str,
+) -> Result<usize, RouterError> {
+    // The trie is locked before the static map, the same order as `remove_route`.
+    let mut root_lock = router.root.write();
+    let mut static_map_lock = router.static_map.write();
+
+    let removed = drain_method_recursive(&mut root_lock, method);
+    static_map_lock.retain(|_, methods_for_path| {
+        methods_for_path.remove(method);
+        !methods_for_path.is_empty()
+    });
+    Ok(removed)
+}
+
+/// Removes the handlers for `method` from `node` and its subtree, pruning children
+/// that become empty. Returns the number of handlers removed.
+fn drain_method_recursive<T>(node: &mut Node<T>, method: &str) -> usize {
+    let mut removed = node
+        .methods
+        .remove(method)
+        .map_or(0, |handlers| handlers.len());
+    node.static_children.retain(|_, child| {
+        removed += drain_method_recursive(child, method);
+        !child.is_empty_recursive()
+    });
+    for child_slot in [&mut node.param_child, &mut node.wildcard_child] {
+        if let Some(child) = child_slot {
+            removed += drain_method_recursive(child, method);
+            if child.is_empty_recursive() {
+                *child_slot = None;
+            }
+        }
+    }
+    removed
+}
@@ -0,0 +1,76 @@
+use crate::compat::RwLock;
+use crate::{
+    context::Router,
+    error::RouterError,
+    operations::{
+        add_route,
+        find::{accept_any, select_route},
+        util::split_path,
+    },
+};
+use alloc::{sync::Arc, vec::Vec};
+
+/// Adds a route whose data is a shared, mutable handle.
+///
+/// The router stores a clone of `shared`, so the handler can later be replaced by
+/// writing through any clone of the `Arc` (for example one returned by
+/// `get_route_handle`) without taking the router's locks or re-adding the route.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method for this route (`""` for any method).
+/// * `path`: The path pattern for the route (e.g., "/users/:id").
+/// * `shared`: The handle to associate with this route.
+///
+/// # Returns
+/// * `Result<(), RouterError>`: The same result as `add_route`.
+///
+/// # Example
+/// ```rust
+/// use parking_lot::RwLock;
+/// use rou3::{Router, add_route_shared, get_route_handle};
+/// use std::sync::Arc;
+///
+/// let router = Router::new();
+/// let handler = Arc::new(RwLock::new("v1"));
+/// add_route_shared(&router, "GET", "/users/:id", Arc::clone(&handler)).unwrap();
+///
+/// *handler.write() = "v2";
+/// let handle = get_route_handle(&router, "GET", "/users/7").unwrap();
+/// assert_eq!(*handle.read(), "v2");
+/// ```
+pub fn add_route_shared<T: Clone>(
+    router: &Router<Arc<RwLock<T>>>,
+    method: &str,
+    path: &str,
+    shared: Arc<RwLock<T>>,
+) -> Result<(), RouterError> {
+    add_route(router, method, path, shared)
+}
+
+/// Returns the handle of the route that `find_route` would match for `method` and
+/// the request `path`, or `None` if no route matches.
+///
+/// `Arc<RwLock<T>>` does not implement `Eq`, so a router of shared handles is
+/// looked up with this function rather than `find_route`. Parameters are not
+/// extracted and the router's `on_match` hook is not called. Under
+/// `TrailingSlashMode::Redirect`, the handle of the route the canonical path
+/// matches is returned instead of a redirect.
+pub fn get_route_handle<T: Clone + Eq>(
+    router: &Router<Arc<RwLock<T>>>,
+    method: &str,
+    path: &str,
+) -> Option<Arc<RwLock<T>>> {
+    let request_path = router.prepare_request_path(method, path).ok()?;
+    let lookup_segments: Vec<&str> = split_path(request_path.lookup()).collect();
+    let root_lock = router.root.read();
+    let (md, _) = select_route(
+        &root_lock,
+        method,
+        &lookup_segments,
+        &request_path.normalized[..].into(),
+        router.config.wildcard_preference,
+        &accept_any,
+    )?;
+    Some(Arc::clone(&md.data))
+}
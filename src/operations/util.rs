@@ -1,5 +1,14 @@
-use crate::types::ParamEntry;
-use ahash::AHashMap;
+use crate::compat::AHashMap;
+use crate::{
+    error::RouterError,
+    types::{IndexParam, MethodData, ParamEntry, ParamTransform},
+};
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
 
 /// Normalizes a path string by removing leading/trailing slashes and collapsing multiple internal slashes.
 ///
@@ -42,10 +51,14 @@ pub fn normalize(path: &str) -> String {
 /// any empty segments that might result from multiple consecutive slashes (though
 /// `normalize` should handle most of this).
 ///
+/// A trailing slash, which only survives normalization under
+/// `TrailingSlashMode::Strict`, yields a final empty segment so that `users/`
+/// and `users` resolve to different nodes.
+///
 /// # Example
 /// ```rust
 /// // Assuming split_path is publicly accessible or tested internally.
-/// // let segments: Vec<&str> = rou3::operations::util::split_path("/foo//bar/").collect();
+/// // let segments: Vec<&str> = rou3::operations::util::split_path("/foo//bar").collect();
 /// // assert_eq!(segments, vec!["foo", "bar"]);
 /// // let root_segments: Vec<&str> = rou3::operations::util::split_path("/").collect();
 /// // assert_eq!(root_segments, Vec::<&str>::new());
@@ -58,23 +71,281 @@ pub fn normalize(path: &str) -> String {
 /// * An iterator over the string slices representing the path segments.
 #[inline]
 pub fn split_path(normalized_path: &str) -> impl Iterator<Item = &str> {
-    normalized_path.split('/').filter(|s| !s.is_empty())
+    let trailing_empty_segment =
+        (normalized_path.len() > 1 && normalized_path.ends_with('/')).then_some("");
+    normalized_path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .chain(trailing_empty_segment)
+}
+
+/// Renders the pattern a handler was registered with, like `render_pattern`, but
+/// including its parameter constraints (e.g., `/users/:id<\d+>`).
+pub(crate) fn render_handler_pattern<T>(segments: &[&str], md: &MethodData<T>) -> String {
+    let pattern = render_pattern(segments, &md.params_map);
+    #[cfg(feature = "regex")]
+    if let Some(constraints) = &md.constraints {
+        let mut rendered: Vec<String> = pattern.split('/').skip(1).map(String::from).collect();
+        for constraint in constraints {
+            if let Some(segment) = rendered.get_mut(constraint.segment_idx) {
+                let is_optional = segment.ends_with('?');
+                segment.truncate(segment.len() - usize::from(is_optional));
+                segment.push('<');
+                segment.push_str(&constraint.source);
+                segment.push('>');
+                if is_optional {
+                    segment.push('?');
+                }
+            }
+        }
+        return format!("/{}", rendered.join("/"));
+    }
+    pattern
+}
+
+/// Returns `true` if a pattern segment (without its optional `?` suffix) is stored
+/// as a node's wildcard child: a wildcard (`**`, `**:name`) or a repeated
+/// parameter (`:name+`), both of which consume the rest of the path.
+pub(crate) fn is_wildcard_segment(segment: &str) -> bool {
+    segment.starts_with("**") || (segment.starts_with(':') && segment.ends_with('+'))
+}
+
+/// Renders a route pattern from its segments, substituting parameter syntax wherever
+/// `params_map` has an entry for a segment index.
+///
+/// Segments without a `ParamEntry` are emitted verbatim. Parameters named `_` are
+/// rendered as their unnamed forms (`*` and `**`). Entries whose index lies past the
+/// end of `segments` (e.g., an absent optional parameter) are appended.
+pub(crate) fn render_pattern(segments: &[&str], params_map: &Option<Vec<ParamEntry>>) -> String {
+    let mut rendered: Vec<String> = segments.iter().map(|s| (*s).to_string()).collect();
+    for entry in params_map.iter().flatten() {
+        let (idx, text) = match entry {
+            ParamEntry::Index(IndexParam {
+                idx,
+                name,
+                optional: is_optional,
+                ..
+            }) => {
+                let base = if name == "_" {
+                    "*".to_string()
+                } else {
+                    format!(":{name}")
+                };
+                (
+                    *idx,
+                    if *is_optional {
+                        format!("{base}?")
+                    } else {
+                        base
+                    },
+                )
+            }
+            ParamEntry::Wildcard(idx, name, is_optional) => {
+                let base = if name == "_" {
+                    "**".to_string()
+                } else {
+                    format!("**:{name}")
+                };
+                (
+                    *idx,
+                    if *is_optional {
+                        format!("{base}?")
+                    } else {
+                        base
+                    },
+                )
+            }
+            ParamEntry::Repeated(idx, name) => (*idx, format!(":{name}+")),
+        };
+        if idx < rendered.len() {
+            rendered[idx] = text;
+        } else {
+            rendered.push(text);
+        }
+    }
+    format!("/{}", rendered.join("/"))
+}
+
+/// Checks a route pattern for syntax errors in a `const` context.
+///
+/// This mirrors the validation `add_route` performs, so statically defined routes
+/// can be checked at compile time instead of failing on startup. A pattern is valid when:
+///
+/// - it is non-empty,
+/// - no segment is empty (a single leading and a single trailing `/` are allowed),
+/// - `**` wildcards (`**` or `**:name`) only appear in the last segment,
+/// - `:` and `*` only appear as segment prefixes (`:name`, `*`), never mid-segment.
+///
+/// Any segment may carry a trailing `?` to mark it optional.
+///
+/// # Examples
+/// ```rust
+/// use rou3::operations::util::validate_route_pattern;
+///
+/// assert!(validate_route_pattern("/users/:id"));
+/// assert!(validate_route_pattern("/files/**:path?"));
+/// assert!(!validate_route_pattern("/users//:id"));
+/// assert!(!validate_route_pattern("/files/**/extra"));
+/// assert!(!validate_route_pattern("/users/id:name"));
+///
+/// // Fails the build if the pattern is malformed.
+/// const _: () = assert!(validate_route_pattern("/users/:id/posts/:post_id?"));
+/// ```
+///
+/// ```compile_fail
+/// use rou3::operations::util::validate_route_pattern;
+///
+/// const _: () = assert!(validate_route_pattern("/users/:/posts"));
+/// ```
+///
+/// # Arguments
+/// * `pattern`: The route pattern to check.
+///
+/// # Returns
+/// * `true` if `add_route` would accept the pattern's syntax, `false` otherwise.
+pub const fn validate_route_pattern(pattern: &str) -> bool {
+    let bytes = pattern.as_bytes();
+    let len = bytes.len();
+    if len == 0 {
+        return false;
+    }
+    if len == 1 && bytes[0] == b'/' {
+        return true;
+    }
+    let mut start = if bytes[0] == b'/' { 1 } else { 0 };
+    let end = if len > start && bytes[len - 1] == b'/' {
+        len - 1
+    } else {
+        len
+    };
+    if start >= end {
+        return false;
+    }
+
+    loop {
+        let mut seg_end = start;
+        while seg_end < end && bytes[seg_end] != b'/' {
+            seg_end += 1;
+        }
+        if !validate_pattern_segment(bytes, start, seg_end, seg_end == end) {
+            return false;
+        }
+        if seg_end == end {
+            return true;
+        }
+        start = seg_end + 1;
+    }
+}
+
+/// Validates `bytes[start..end]` as a single pattern segment. See [`validate_route_pattern`].
+const fn validate_pattern_segment(bytes: &[u8], start: usize, end: usize, is_last: bool) -> bool {
+    let end = if end > start && bytes[end - 1] == b'?' {
+        end - 1
+    } else {
+        end
+    };
+    let seg_len = end - start;
+    if seg_len == 0 {
+        return false;
+    }
+
+    if seg_len >= 2 && bytes[start] == b'*' && bytes[start + 1] == b'*' {
+        return is_last && (seg_len == 2 || (bytes[start + 2] == b':' && seg_len > 3));
+    }
+    if bytes[start] == b':' {
+        return seg_len > 1;
+    }
+    if bytes[start] == b'*' {
+        return seg_len == 1;
+    }
+
+    let mut i = start;
+    while i < end {
+        if bytes[i] == b':' || bytes[i] == b'*' {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Percent-decodes a single path segment.
+///
+/// Encoded slashes (`%2F`/`%2f`) are left as-is so decoding never changes how a path
+/// splits into segments. Malformed escapes are kept verbatim, and if the decoded bytes
+/// are not valid UTF-8 the segment is returned undecoded.
+pub(crate) fn percent_decode_segment(segment: &str) -> String {
+    fn hex_value(byte: u8) -> Option<u8> {
+        (byte as char).to_digit(16).map(|v| v as u8)
+    }
+
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let (Some(hi), Some(lo)) = (hex_value(bytes[i + 1]), hex_value(bytes[i + 2])) {
+                let value = hi << 4 | lo;
+                if value != b'/' {
+                    decoded.push(value);
+                    i += 3;
+                    continue;
+                }
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| segment.to_string())
+}
+
+/// Parses a URL query string (without the leading `?`) into a map.
+///
+/// Pairs are separated by `&` and split at the first `=`; a key without `=` maps
+/// to an empty value. Empty pairs are skipped, and later duplicates overwrite
+/// earlier ones. Returns `None` if the query string has no pairs.
+pub(crate) fn parse_query(query: &str) -> Option<AHashMap<String, String>> {
+    let parsed: AHashMap<String, String> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key.to_string(), value.to_string())
+        })
+        .collect();
+    if parsed.is_empty() {
+        None
+    } else {
+        Some(parsed)
+    }
 }
 
 /// Extracts parameters from path segments based on a list of `ParamEntry` definitions.
+///
+/// A value whose parameter name has an entry in `transforms` is passed through
+/// that function before it is stored. An optional `Index` parameter absent from
+/// the path takes its value from `defaults`, if it has one there; defaults are not
+/// transformed.
 pub(crate) fn extract_all_params(
     path_segments: &[&str],
     param_entries_opt: &Option<Vec<ParamEntry>>,
+    transforms: &Option<AHashMap<String, ParamTransform>>,
+    defaults: &Option<AHashMap<String, String>>,
 ) -> Option<AHashMap<String, String>> {
     let entries = param_entries_opt.as_ref()?;
     if entries.is_empty() {
         return None;
     }
 
-    let mut extracted_params = AHashMap::new();
+    let mut extracted_params = AHashMap::default();
     for entry in entries {
         match entry {
-            ParamEntry::Index(segment_idx, param_name, is_optional) => {
+            ParamEntry::Index(IndexParam {
+                idx: segment_idx,
+                name: param_name,
+                optional: is_optional,
+                ..
+            }) => {
                 if *segment_idx < path_segments.len() {
                     let value = path_segments[*segment_idx].to_string();
                     extracted_params.insert(param_name.clone(), value);
@@ -92,6 +363,38 @@ pub(crate) fn extract_all_params(
                 };
                 extracted_params.insert(param_name.clone(), value);
             }
+            ParamEntry::Repeated(start_idx, param_name) => {
+                if let Some(repeated) = path_segments.get(*start_idx..) {
+                    extracted_params.insert(param_name.clone(), repeated.join(","));
+                }
+            }
+        }
+    }
+
+    if let Some(transforms) = transforms {
+        for (param_name, value) in &mut extracted_params {
+            if let Some(transform) = transforms.get(param_name) {
+                *value = transform(value);
+            }
+        }
+    }
+
+    // Defaults are inserted after transforms run, so they are returned verbatim.
+    if let Some(defaults) = defaults {
+        for entry in entries {
+            if let ParamEntry::Index(IndexParam {
+                idx: segment_idx,
+                name: param_name,
+                optional: true,
+                ..
+            }) = entry
+            {
+                if *segment_idx >= path_segments.len() {
+                    if let Some(default) = defaults.get(param_name) {
+                        extracted_params.insert(param_name.clone(), default.clone());
+                    }
+                }
+            }
         }
     }
 
@@ -102,6 +405,211 @@ pub(crate) fn extract_all_params(
     }
 }
 
+/// Like `extract_all_params`, but values borrow from `path_segments` wherever a
+/// single segment is captured unchanged.
+pub(crate) fn extract_borrowed_params<'p>(
+    path_segments: &[&'p str],
+    param_entries_opt: &Option<Vec<ParamEntry>>,
+    transforms: &Option<AHashMap<String, ParamTransform>>,
+    defaults: &Option<AHashMap<String, String>>,
+) -> Option<AHashMap<String, Cow<'p, str>>> {
+    let entries = param_entries_opt.as_ref()?;
+    let mut extracted_params = AHashMap::default();
+    for entry in entries {
+        match entry {
+            ParamEntry::Index(IndexParam {
+                idx: segment_idx,
+                name: param_name,
+                ..
+            }) => {
+                if let Some(&value) = path_segments.get(*segment_idx) {
+                    extracted_params.insert(param_name.clone(), Cow::Borrowed(value));
+                }
+            }
+            ParamEntry::Wildcard(start_idx, param_name, _is_optional) => {
+                let value = match path_segments.get(*start_idx..).unwrap_or_default() {
+                    [] => Cow::Borrowed(""),
+                    [single] => Cow::Borrowed(*single),
+                    several => Cow::Owned(several.join("/")),
+                };
+                extracted_params.insert(param_name.clone(), value);
+            }
+            ParamEntry::Repeated(start_idx, param_name) => {
+                match path_segments.get(*start_idx..).unwrap_or_default() {
+                    [] => {}
+                    [single] => {
+                        extracted_params.insert(param_name.clone(), Cow::Borrowed(*single));
+                    }
+                    several => {
+                        extracted_params.insert(param_name.clone(), Cow::Owned(several.join(",")));
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(transforms) = transforms {
+        for (param_name, value) in &mut extracted_params {
+            if let Some(transform) = transforms.get(param_name) {
+                *value = Cow::Owned(transform(value));
+            }
+        }
+    }
+
+    if let Some(defaults) = defaults {
+        for entry in entries {
+            if let ParamEntry::Index(IndexParam {
+                idx: segment_idx,
+                name: param_name,
+                optional: true,
+                ..
+            }) = entry
+            {
+                if *segment_idx >= path_segments.len() {
+                    if let Some(default) = defaults.get(param_name) {
+                        extracted_params.insert(param_name.clone(), Cow::Owned(default.clone()));
+                    }
+                }
+            }
+        }
+    }
+
+    (!extracted_params.is_empty()).then_some(extracted_params)
+}
+
+/// Returns `true` if `name` can be used both as a rou3 parameter name and as an
+/// RFC 6570 variable name.
+fn is_template_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// Converts a rou3 route pattern into an RFC 6570 URI template: `:id` becomes `{id}`
+/// and `**:filepath` becomes `{+filepath}`, whose `+` operator allows slashes in the value.
+///
+/// # Example
+/// ```rust
+/// use rou3::operations::util::pattern_to_url_template;
+///
+/// assert_eq!(pattern_to_url_template("/users/:id").unwrap(), "/users/{id}");
+/// assert_eq!(pattern_to_url_template("/files/**:path").unwrap(), "/files/{+path}");
+/// ```
+///
+/// # Errors
+/// Returns `RouterError::InvalidPath` for syntax URI templates cannot express, such
+/// as unnamed (`*`, `**`) or optional (`:id?`) parameters, or static segments
+/// containing `{` or `}`.
+pub fn pattern_to_url_template(pattern: &str) -> Result<String, RouterError> {
+    let invalid =
+        || RouterError::InvalidPath(format!("cannot convert '{pattern}' to a URL template"));
+    let segments = pattern
+        .split('/')
+        .map(|segment| {
+            let (operator, name) = if let Some(name) = segment.strip_prefix("**:") {
+                ("+", name)
+            } else if let Some(name) = segment.strip_prefix(':') {
+                ("", name)
+            } else if segment.contains(['{', '}', ':', '*', '?']) {
+                return Err(invalid());
+            } else {
+                return Ok(segment.to_string());
+            };
+            if !is_template_variable_name(name) {
+                return Err(invalid());
+            }
+            Ok(format!("{{{operator}{name}}}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(segments.join("/"))
+}
+
+/// Converts an RFC 6570 URI template into a rou3 route pattern: `{id}` becomes `:id`
+/// and `{+wildcard}` becomes `**:wildcard`. The reverse of [`pattern_to_url_template`].
+///
+/// # Example
+/// ```rust
+/// use rou3::operations::util::url_template_to_pattern;
+///
+/// assert_eq!(url_template_to_pattern("/users/{id}").unwrap(), "/users/:id");
+/// assert_eq!(url_template_to_pattern("/files/{+path}").unwrap(), "/files/**:path");
+/// ```
+///
+/// # Errors
+/// Returns `RouterError::InvalidPath` for expressions that do not fill a whole
+/// segment (e.g., `/files/{name}.txt`), use other operators or several variables
+/// (e.g., `{?q}`, `{a,b}`), or for segments containing rou3 pattern syntax.
+pub fn url_template_to_pattern(template: &str) -> Result<String, RouterError> {
+    let invalid = || RouterError::InvalidPath(format!("unsupported URL template '{template}'"));
+    let segments = template
+        .split('/')
+        .map(
+            |segment| match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                Some(expression) => {
+                    let (prefix, name) = match expression.strip_prefix('+') {
+                        Some(name) => ("**:", name),
+                        None => (":", expression),
+                    };
+                    if !is_template_variable_name(name) {
+                        return Err(invalid());
+                    }
+                    Ok(format!("{prefix}{name}"))
+                }
+                None if segment.contains(['{', '}', ':', '*', '?']) => Err(invalid()),
+                None => Ok(segment.to_string()),
+            },
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(segments.join("/"))
+}
+
+/// Scores how specific a route pattern is: 3 points per static segment, 2 per named
+/// parameter (`:id`), and 1 per wildcard (`*` or `**`), summed over the pattern's
+/// `depth` segments.
+///
+/// # Example
+/// ```rust
+/// use rou3::{
+///     operations::util::route_specificity_score,
+///     types::{IndexParam, ParamEntry},
+/// };
+///
+/// // `/users/:id` is one static segment and one named parameter.
+/// let params_map = Some(vec![ParamEntry::Index(IndexParam::new(1, "id".to_string(), false))]);
+/// assert_eq!(route_specificity_score(&params_map, 2), 5);
+/// assert_eq!(route_specificity_score(&None, 2), 6);
+/// ```
+pub fn route_specificity_score(params_map: &Option<Vec<ParamEntry>>, depth: usize) -> u32 {
+    let (mut named_params, mut wildcards) = (0, 0);
+    for entry in params_map.iter().flatten() {
+        match entry {
+            ParamEntry::Index(param) if param.name != "_" => named_params += 1,
+            ParamEntry::Index(..) | ParamEntry::Wildcard(..) | ParamEntry::Repeated(..) => {
+                wildcards += 1
+            }
+        }
+    }
+    let static_segments = depth.saturating_sub(named_params + wildcards);
+    (3 * static_segments + 2 * named_params + wildcards) as u32
+}
+
+/// Returns the number of segments in the pattern of a route that matched a request
+/// path of `path_len` segments. This exceeds `path_len` when an optional parameter
+/// or a wildcard matched nothing, and is smaller when a wildcard matched several.
+pub(crate) fn matched_pattern_depth(
+    params_map: &Option<Vec<ParamEntry>>,
+    path_len: usize,
+) -> usize {
+    match params_map.as_ref().and_then(|entries| entries.last()) {
+        Some(ParamEntry::Wildcard(start_idx, _, _) | ParamEntry::Repeated(start_idx, _)) => {
+            start_idx + 1
+        }
+        Some(ParamEntry::Index(param)) => path_len.max(param.idx + 1),
+        None => path_len,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,25 +635,104 @@ mod tests {
         // Test with pre-normalized paths as `split_path` expects normalized input
         assert_eq!(split_path("").collect::<Vec<&str>>(), Vec::<&str>::new());
         assert_eq!(split_path("path").collect::<Vec<&str>>(), vec!["path"]);
+        assert_eq!(split_path("path/").collect::<Vec<&str>>(), vec!["path", ""]);
+        assert_eq!(split_path("/").collect::<Vec<&str>>(), Vec::<&str>::new());
         assert_eq!(
             split_path("path/to/resource").collect::<Vec<&str>>(),
             vec!["path", "to", "resource"]
         );
     }
 
+    #[test]
+    fn test_validate_route_pattern() {
+        for valid in [
+            "/",
+            "users",
+            "/users/",
+            "/users/:id",
+            "/users/:id?",
+            "/files/*",
+            "/files/**",
+            "/files/**:path",
+            "/files/**:path?",
+            "/a/:b/c/*/d",
+        ] {
+            assert!(validate_route_pattern(valid), "{valid} should be valid");
+        }
+        for invalid in [
+            "",
+            "//",
+            "/users//:id",
+            "/users/:",
+            "/files/**:",
+            "/files/**/extra",
+            "/files/**:path/extra",
+            "/files/***",
+            "/files/*x",
+            "/users/id:name",
+            "/users/?",
+        ] {
+            assert!(
+                !validate_route_pattern(invalid),
+                "{invalid} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_percent_decode_segment() {
+        assert_eq!(percent_decode_segment("plain"), "plain");
+        assert_eq!(percent_decode_segment("hello%20world"), "hello world");
+        assert_eq!(percent_decode_segment("caf%C3%A9"), "café");
+        assert_eq!(percent_decode_segment("a%2Fb"), "a%2Fb");
+        assert_eq!(percent_decode_segment("100%"), "100%");
+        assert_eq!(percent_decode_segment("%zz%4"), "%zz%4");
+        assert_eq!(percent_decode_segment("%FF"), "%FF");
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert!(parse_query("").is_none());
+        assert!(parse_query("&&").is_none());
+        let parsed = parse_query("q=rust&page=2&flag&empty=").unwrap();
+        assert_eq!(parsed.get("q").unwrap(), "rust");
+        assert_eq!(parsed.get("page").unwrap(), "2");
+        assert_eq!(parsed.get("flag").unwrap(), "");
+        assert_eq!(parsed.get("empty").unwrap(), "");
+        assert_eq!(parse_query("a=1&a=2").unwrap().get("a").unwrap(), "2");
+    }
+
+    #[test]
+    fn test_render_pattern() {
+        assert_eq!(render_pattern(&[], &None), "/");
+        assert_eq!(render_pattern(&["users", "list"], &None), "/users/list");
+        let params = Some(vec![
+            ParamEntry::Index(IndexParam::new(1, "id".to_string(), false)),
+            ParamEntry::Index(IndexParam::new(2, "_".to_string(), true)),
+        ]);
+        assert_eq!(
+            render_pattern(&["users", "42", "x"], &params),
+            "/users/:id/*?"
+        );
+        assert_eq!(render_pattern(&["users", "42"], &params), "/users/:id/*?");
+        let wildcard = Some(vec![ParamEntry::Wildcard(1, "path".to_string(), false)]);
+        assert_eq!(render_pattern(&["files", "*"], &wildcard), "/files/**:path");
+    }
+
     #[test]
     fn test_extract_all_params_basic() {
         let segments = vec!["users", "123", "posts"];
         let param_entries = Some(vec![
-            ParamEntry::Index(1, "userId".to_string(), false),
-            ParamEntry::Index(2, "type".to_string(), false),
+            ParamEntry::Index(IndexParam::new(1, "userId".to_string(), false)),
+            ParamEntry::Index(IndexParam::new(2, "type".to_string(), false)),
         ]);
-        let params = extract_all_params(&segments, &param_entries).unwrap();
+        let params = extract_all_params(&segments, &param_entries, &None, &None).unwrap();
         assert_eq!(params.get("userId").unwrap(), "123");
         assert_eq!(params.get("type").unwrap(), "posts");
 
         let param_entries_wildcard = Some(vec![ParamEntry::Wildcard(1, "rest".to_string(), false)]);
-        let params_wild = extract_all_params(&segments, &param_entries_wildcard).unwrap();
+        let params_wild =
+            extract_all_params(&segments, &param_entries_wildcard, &None, &None).unwrap();
         assert_eq!(params_wild.get("rest").unwrap(), "123/posts");
     }
 
@@ -153,26 +740,33 @@ mod tests {
     fn test_extract_all_params_optional() {
         let segments_full = vec!["search", "rust"];
         let param_entries_opt = Some(vec![
-            ParamEntry::Index(0, "verb".to_string(), false),
-            ParamEntry::Index(1, "query".to_string(), true),
+            ParamEntry::Index(IndexParam::new(0, "verb".to_string(), false)),
+            ParamEntry::Index(IndexParam::new(1, "query".to_string(), true)),
         ]);
-        let params_full = extract_all_params(&segments_full, &param_entries_opt).unwrap();
+        let params_full =
+            extract_all_params(&segments_full, &param_entries_opt, &None, &None).unwrap();
         assert_eq!(params_full.get("verb").unwrap(), "search");
         assert_eq!(params_full.get("query").unwrap(), "rust");
 
         let segments_partial = vec!["search"];
-        let params_partial = extract_all_params(&segments_partial, &param_entries_opt).unwrap();
+        let params_partial =
+            extract_all_params(&segments_partial, &param_entries_opt, &None, &None).unwrap();
         assert_eq!(params_partial.get("verb").unwrap(), "search");
         assert!(params_partial.get("query").is_none());
 
-        let param_entries_only_opt = Some(vec![ParamEntry::Index(0, "maybe".to_string(), true)]);
+        let param_entries_only_opt = Some(vec![ParamEntry::Index(IndexParam::new(
+            0,
+            "maybe".to_string(),
+            true,
+        ))]);
         let segments_empty: Vec<&str> = vec![];
-        let params_none = extract_all_params(&segments_empty, &param_entries_only_opt);
+        let params_none =
+            extract_all_params(&segments_empty, &param_entries_only_opt, &None, &None);
         assert!(params_none.is_none());
 
         let segments_present = vec!["value"];
         let params_opt_present =
-            extract_all_params(&segments_present, &param_entries_only_opt).unwrap();
+            extract_all_params(&segments_present, &param_entries_only_opt, &None, &None).unwrap();
         assert_eq!(params_opt_present.get("maybe").unwrap(), "value");
     }
 
@@ -182,7 +776,7 @@ mod tests {
         // Wildcard is /**:path at index 1 (after "files")
         let segments: Vec<&str> = vec!["files"];
         let param_entries = Some(vec![ParamEntry::Wildcard(1, "path".to_string(), true)]);
-        let params = extract_all_params(&segments, &param_entries).unwrap();
+        let params = extract_all_params(&segments, &param_entries, &None, &None).unwrap();
         assert_eq!(
             params.get("path").unwrap(),
             "",
@@ -194,11 +788,91 @@ mod tests {
         let segments_root_wild: Vec<&str> = vec![];
         let param_entries_root_wild = Some(vec![ParamEntry::Wildcard(0, "all".to_string(), true)]);
         let params_root =
-            extract_all_params(&segments_root_wild, &param_entries_root_wild).unwrap();
+            extract_all_params(&segments_root_wild, &param_entries_root_wild, &None, &None)
+                .unwrap();
         assert_eq!(
             params_root.get("all").unwrap(),
             "",
             "Wildcard at root for empty path should capture empty string"
         );
     }
+
+    #[test]
+    fn test_extract_all_params_transforms() {
+        let segments: Vec<&str> = vec!["users", "ALICE", "a/b"];
+        let param_entries = Some(vec![
+            ParamEntry::Index(IndexParam::new(1, "name".to_string(), false)),
+            ParamEntry::Wildcard(2, "rest".to_string(), false),
+        ]);
+        let lowercase: ParamTransform = alloc::sync::Arc::new(|value| value.to_ascii_lowercase());
+        let transforms = Some(AHashMap::from_iter([("name".to_string(), lowercase)]));
+        let params = extract_all_params(&segments, &param_entries, &transforms, &None).unwrap();
+        assert_eq!(params.get("name").unwrap(), "alice");
+        assert_eq!(params.get("rest").unwrap(), "a/b");
+    }
+
+    #[test]
+    fn test_url_template_round_trip() {
+        for (pattern, template) in [
+            ("/", "/"),
+            ("/health", "/health"),
+            ("/users/:id", "/users/{id}"),
+            ("/orgs/:org/repos/:repo", "/orgs/{org}/repos/{repo}"),
+            ("/files/:bucket/**:path", "/files/{bucket}/{+path}"),
+        ] {
+            assert_eq!(pattern_to_url_template(pattern).unwrap(), template);
+            assert_eq!(url_template_to_pattern(template).unwrap(), pattern);
+        }
+    }
+
+    #[test]
+    fn test_url_template_invalid_syntax() {
+        for pattern in ["/users/*", "/files/**", "/search/:q?", "/a/{b}", "/users/:"] {
+            assert!(
+                matches!(
+                    pattern_to_url_template(pattern),
+                    Err(RouterError::InvalidPath(_))
+                ),
+                "{pattern} should be rejected"
+            );
+        }
+        for template in [
+            "/files/{name}.txt",
+            "/search{?q}",
+            "/a/{b,c}",
+            "/a/{}",
+            "/a/:b",
+            "/a/{#b}",
+        ] {
+            assert!(
+                matches!(
+                    url_template_to_pattern(template),
+                    Err(RouterError::InvalidPath(_))
+                ),
+                "{template} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_route_specificity_score() {
+        let id = ParamEntry::Index(IndexParam::new(1, "id".to_string(), false));
+        let star = ParamEntry::Index(IndexParam::new(1, "_".to_string(), false));
+        let rest = ParamEntry::Wildcard(2, "rest".to_string(), false);
+        assert_eq!(route_specificity_score(&None, 0), 0);
+        assert_eq!(route_specificity_score(&None, 3), 9);
+        assert_eq!(route_specificity_score(&Some(vec![id.clone()]), 2), 5);
+        assert_eq!(route_specificity_score(&Some(vec![star]), 2), 4);
+        assert_eq!(
+            route_specificity_score(&Some(vec![id.clone(), rest.clone()]), 3),
+            6
+        );
+
+        assert_eq!(matched_pattern_depth(&None, 2), 2);
+        assert_eq!(matched_pattern_depth(&Some(vec![rest.clone()]), 5), 3);
+        assert_eq!(matched_pattern_depth(&Some(vec![rest]), 2), 3);
+        let optional = ParamEntry::Index(IndexParam::new(1, "q".to_string(), true));
+        assert_eq!(matched_pattern_depth(&Some(vec![optional]), 1), 2);
+        assert_eq!(matched_pattern_depth(&Some(vec![id]), 2), 2);
+    }
 }
@@ -0,0 +1,156 @@
+use crate::{
+    context::{Router, VHostRouter},
+    error::RouterError,
+    operations::find::{find_prepared, find_route, method_not_allowed_or},
+    types::MatchedRoute,
+};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Strips the port (if any) from a `Host` header value and lowercases it.
+///
+/// Bracketed IPv6 literals (e.g., `[::1]:8080`) keep their brackets.
+fn strip_port(host: &str) -> String {
+    let without_port = if host.starts_with('[') {
+        host.find(']').map_or(host, |end| &host[..=end])
+    } else {
+        host.rsplit_once(':').map_or(host, |(name, _port)| name)
+    };
+    without_port.to_ascii_lowercase()
+}
+
+/// Returns `true` if `host` matches the glob `pattern`, where `*` matches any run of
+/// characters (including none). Both are expected to be lowercase.
+fn host_glob_matches(pattern: &str, host: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == host;
+    };
+    let Some(mut remaining) = host.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = rest.split('*').collect();
+    let last = parts.pop().unwrap_or_default();
+    for part in parts {
+        match remaining.find(part) {
+            Some(pos) => remaining = &remaining[pos + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.len() >= last.len() && remaining.ends_with(last)
+}
+
+/// Registers `router` to serve requests for `host_pattern`.
+///
+/// `host_pattern` is matched case-insensitively and may be an exact host
+/// (`api.example.com`), a subdomain wildcard (`*.example.com`), or `*` for any host.
+/// Registering the same pattern twice replaces the previous router.
+///
+/// # Arguments
+/// * `vhost`: A reference to the `VHostRouter` instance.
+/// * `host_pattern`: The host pattern this router serves.
+/// * `router`: The router handling requests for matching hosts.
+///
+/// # Panics
+/// This function may panic if acquiring the write lock on the host map fails.
+pub fn add_host<T>(vhost: &VHostRouter<T>, host_pattern: &str, router: Router<T>) {
+    vhost
+        .hosts
+        .write()
+        .insert(host_pattern.to_ascii_lowercase(), router);
+}
+
+/// Finds a route by first selecting a sub-router by host, then matching `method` and `path`.
+///
+/// The port is stripped from `host` before matching. An exact host pattern takes
+/// precedence over wildcard patterns; among `*.suffix` patterns the longest suffix
+/// wins, and `*` is only used when nothing else matches.
+///
+/// # Arguments
+/// * `vhost`: A reference to the `VHostRouter` instance.
+/// * `host`: The request's `Host` header value (e.g., `api.example.com:8080`).
+/// * `method`, `path`, `capture`: Passed through to `find_route` on the selected router.
+///
+/// # Returns
+/// * `Result<MatchedRoute<T>, RouterError>`: The result of `find_route` on the selected
+///   router, or `Err(RouterError::RouteNotFound)` if no host pattern matches.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the host map or the selected
+/// router fails.
+pub fn find_in_host<T: Clone + Eq>(
+    vhost: &VHostRouter<T>,
+    host: &str,
+    method: &str,
+    path: &str,
+    capture: bool,
+) -> Result<MatchedRoute<T>, RouterError> {
+    let hostname = strip_port(host);
+    let hosts_lock = vhost.hosts.read();
+
+    let router = hosts_lock.get(&hostname).or_else(|| {
+        hosts_lock
+            .iter()
+            .filter_map(|(pattern, router)| {
+                let suffix = pattern.strip_prefix('*')?;
+                let matches = suffix.is_empty()
+                    || (suffix.starts_with('.')
+                        && hostname.len() > suffix.len()
+                        && hostname.ends_with(suffix));
+                matches.then_some((suffix.len(), router))
+            })
+            .max_by_key(|(suffix_len, _)| *suffix_len)
+            .map(|(_, router)| router)
+    });
+
+    match router {
+        Some(router) => find_route(router, method, path, capture),
+        None => Err(RouterError::RouteNotFound {
+            method: method.to_string(),
+            path: path.to_string(),
+        }),
+    }
+}
+
+/// Finds a route for a request to `host`, skipping handlers added with
+/// `add_route_for_host` for other hosts.
+///
+/// The port is stripped from `host` before it is matched against each handler's host
+/// pattern. Matching otherwise works like `find_route`: the first handler in the usual
+/// precedence order whose host pattern matches (or that has none) is returned, so a
+/// route restricted to another host falls through to less specific routes.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `host`: The request's `Host` header value (e.g., `api.example.com:8080`).
+/// * `method`: The HTTP method (e.g., "GET", "POST") to match.
+/// * `path`: The request path to match against the router's patterns.
+/// * `capture`: A boolean indicating whether path parameters should be extracted.
+///
+/// # Returns
+/// * `Result<MatchedRoute<T>, RouterError>`: The same result as `find_route`, with
+///   `Err(RouterError::RouteNotFound)` if no handler serves `host`.
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn find_route_with_host<T: Clone + Eq>(
+    router: &Router<T>,
+    host: &str,
+    method: &str,
+    path: &str,
+    capture: bool,
+) -> Result<MatchedRoute<T>, RouterError> {
+    let hostname = strip_port(host);
+    let request_path = router.prepare_request_path(method, path)?;
+    let matched = find_prepared(router, method, &request_path, path, capture, &|md| {
+        md.host_constraint
+            .as_deref()
+            .is_none_or(|pattern| host_glob_matches(pattern, &hostname))
+    })
+    .map_err(|err| method_not_allowed_or(&router.root.read(), method, &request_path, path, err))?;
+    if let Some(on_match) = &router.on_match {
+        on_match(method, &matched.matched_path, &matched.route_pattern);
+    }
+    Ok(matched)
+}
@@ -0,0 +1,132 @@
+use crate::{
+    context::Router,
+    error::RouterError,
+    operations::{
+        add::find_pattern_node,
+        find::{accept_any, find_prepared, method_not_allowed_or},
+        util::{extract_all_params, render_handler_pattern, split_path},
+    },
+    types::{MatchedRoute, MethodData},
+};
+use alloc::vec::Vec;
+use rand::RngExt;
+
+/// Finds a route and picks one of its weighted variants at random.
+///
+/// The route is matched as `find_route` matches it. Every enabled handler for the same
+/// method and pattern whose guard accepts the request is a variant, and one is drawn
+/// with probability proportional to its `weight` (see `add_weighted_route`). Routes
+/// with a single handler always return it, and if every variant has weight `0`, the
+/// one `find_route` returns is used.
+///
+/// # Arguments
+/// * `router`: A reference to the `Router` instance.
+/// * `method`: The HTTP method (e.g., "GET", "POST") to match.
+/// * `path`: The request path to match against the router's patterns.
+/// * `capture`: A boolean indicating whether path parameters should be extracted.
+/// * `rng`: The random number generator used to draw the variant.
+///
+/// # Returns
+/// * `Result<MatchedRoute<T>, RouterError>`: The drawn variant, or the same errors
+///   `find_route` returns.
+///
+/// # Example
+/// ```rust
+/// use rand::SeedableRng;
+/// use rou3::{Router, add_weighted_route, find_route_weighted};
+///
+/// let router = Router::new();
+/// add_weighted_route(&router, "GET", "/checkout", "stable", 95).unwrap();
+/// add_weighted_route(&router, "GET", "/checkout", "canary", 5).unwrap();
+///
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+/// let matched = find_route_weighted(&router, "GET", "/checkout", false, &mut rng).unwrap();
+/// assert!(["stable", "canary"].contains(&matched.data));
+/// ```
+///
+/// # Panics
+/// This function may panic if acquiring read locks on the router's internal structures fails.
+pub fn find_route_weighted<T: Clone + Eq>(
+    router: &Router<T>,
+    method: &str,
+    path: &str,
+    capture: bool,
+    rng: &mut impl rand::Rng,
+) -> Result<MatchedRoute<T>, RouterError> {
+    let request_path = router.prepare_request_path(method, path)?;
+    let first = find_prepared(router, method, &request_path, path, capture, &accept_any).map_err(
+        |err| method_not_allowed_or(&router.root.read(), method, &request_path, path, err),
+    )?;
+
+    let root = router.root.read();
+    let pattern_segments: Vec<&str> = split_path(&first.route_pattern).collect();
+    let key = if first.is_head_derived { "GET" } else { method };
+    let variants: Vec<&MethodData<T>> = find_pattern_node(&root, &pattern_segments)
+        .and_then(|node| {
+            [key, ""]
+                .into_iter()
+                .filter_map(|key| node.methods.get(key))
+                .map(|handlers| {
+                    handlers
+                        .iter()
+                        .filter(|md| {
+                            md.accepts(method, &request_path.lookup().into())
+                                && render_handler_pattern(&pattern_segments, md)
+                                    == first.route_pattern
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .find(|variants| !variants.is_empty())
+        })
+        .unwrap_or_default();
+
+    let total: u64 = variants
+        .iter()
+        .map(|md| u64::from(md.weight.unwrap_or(1)))
+        .sum();
+    if variants.len() < 2 || total == 0 {
+        drop(root);
+        if let Some(on_match) = &router.on_match {
+            on_match(method, &first.matched_path, &first.route_pattern);
+        }
+        return Ok(first);
+    }
+    let mut ticket = rng.random_range(0..total);
+    let chosen = variants
+        .into_iter()
+        .find(|md| {
+            let weight = u64::from(md.weight.unwrap_or(1));
+            ticket = match ticket.checked_sub(weight) {
+                Some(rest) => rest,
+                None => return true,
+            };
+            false
+        })
+        .expect("the ticket is below the total weight");
+
+    // Every variant has the pattern of `first`, so it matches the request the same way.
+    let segments: Vec<&str> = split_path(&request_path.normalized).collect();
+    let lookup_segments: Vec<&str> = split_path(request_path.lookup()).collect();
+    let params = if capture {
+        extract_all_params(
+            &segments,
+            &chosen.params_map,
+            &chosen.transforms,
+            &chosen.defaults,
+        )
+    } else {
+        None
+    };
+    let mut matched = MatchedRoute::from_method_data(
+        chosen,
+        params,
+        request_path.normalized.to_string(),
+        &lookup_segments,
+    );
+    matched.is_head_derived = first.is_head_derived;
+    drop(root);
+    if let Some(on_match) = &router.on_match {
+        on_match(method, &matched.matched_path, &matched.route_pattern);
+    }
+    Ok(matched)
+}
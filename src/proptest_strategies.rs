@@ -0,0 +1,96 @@
+//! `proptest` strategies for generating routes, request paths, and methods.
+//!
+//! These are used by the property tests in `tests/routing_properties.rs` and
+//! are exported (behind the `proptest` feature) so downstream crates can
+//! property-test their own routing setups.
+
+use proptest::prelude::*;
+
+/// A single segment of a generated route pattern.
+#[derive(Debug, Clone)]
+enum PatternSegment {
+    Static(String),
+    Param { optional: bool },
+    Wildcard,
+}
+
+/// Generates a lowercase static segment such as `users` or `v1`.
+pub fn static_segment() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9_-]{0,7}"
+}
+
+/// Generates a purely static path such as `/users/v1/list`.
+pub fn static_path() -> impl Strategy<Value = String> {
+    prop::collection::vec(static_segment(), 0..6).prop_map(|segs| format!("/{}", segs.join("/")))
+}
+
+/// Generates an HTTP method string, including `""` for the "any" method.
+pub fn method() -> impl Strategy<Value = String> {
+    prop::sample::select(vec!["GET", "POST", "PUT", "PATCH", "DELETE", ""]).prop_map(String::from)
+}
+
+fn pattern_segments() -> impl Strategy<Value = Vec<PatternSegment>> {
+    let segment = prop_oneof![
+        3 => static_segment().prop_map(PatternSegment::Static),
+        1 => Just(PatternSegment::Param { optional: false }),
+    ];
+    (
+        prop::collection::vec(segment, 0..5),
+        prop_oneof![
+            4 => Just(None),
+            1 => Just(Some(PatternSegment::Param { optional: true })),
+            1 => Just(Some(PatternSegment::Wildcard)),
+        ],
+    )
+        .prop_map(|(mut segs, tail)| {
+            segs.extend(tail);
+            segs
+        })
+}
+
+fn render_pattern(segments: &[PatternSegment]) -> String {
+    let rendered: Vec<String> = segments
+        .iter()
+        .enumerate()
+        .map(|(i, seg)| match seg {
+            PatternSegment::Static(s) => s.clone(),
+            PatternSegment::Param { optional: false } => format!(":p{i}"),
+            PatternSegment::Param { optional: true } => format!(":p{i}?"),
+            PatternSegment::Wildcard => format!("**:w{i}"),
+        })
+        .collect();
+    format!("/{}", rendered.join("/"))
+}
+
+/// Generates a valid route pattern mixing static segments, named parameters,
+/// an optional trailing parameter, and a trailing named wildcard.
+///
+/// Parameter names are unique within a pattern.
+pub fn route_pattern() -> impl Strategy<Value = String> {
+    pattern_segments().prop_map(|segs| render_pattern(&segs))
+}
+
+/// Generates a `(pattern, path)` pair where `path` is a concrete request path
+/// that the pattern matches.
+pub fn route_with_path() -> impl Strategy<Value = (String, String)> {
+    pattern_segments().prop_flat_map(|segs| {
+        let values: Vec<BoxedStrategy<Vec<String>>> = segs
+            .iter()
+            .map(|seg| match seg {
+                PatternSegment::Static(s) => Just(vec![s.clone()]).boxed(),
+                PatternSegment::Param { optional: false } => {
+                    static_segment().prop_map(|v| vec![v]).boxed()
+                }
+                PatternSegment::Param { optional: true } => {
+                    prop::collection::vec(static_segment(), 0..=1).boxed()
+                }
+                PatternSegment::Wildcard => prop::collection::vec(static_segment(), 0..4).boxed(),
+            })
+            .collect();
+        let pattern = render_pattern(&segs);
+        values.prop_map(move |parts| {
+            let path = parts.concat().join("/");
+            (pattern.clone(), format!("/{path}"))
+        })
+    })
+}
@@ -0,0 +1,112 @@
+//! Converting a `MatchedRoute` to and from JSON, enabled by the `serde_json` feature.
+
+use crate::{
+    operations::{
+        add::build_param_entries_for_pattern_segments,
+        util::{route_specificity_score, split_path},
+    },
+    types::MatchedRoute,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// The JSON shape written by `to_json_value`.
+#[derive(serde::Serialize)]
+struct MatchedRouteJsonRef<'a, T> {
+    data: &'a T,
+    params: BTreeMap<&'a str, &'a str>,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    route_pattern: &'a str,
+    matched_path: &'a str,
+}
+
+/// The JSON shape read by `from_json_value`.
+#[derive(Deserialize)]
+struct MatchedRouteJson<T> {
+    data: T,
+    #[serde(default)]
+    params: Option<HashMap<String, String>>,
+    #[serde(default)]
+    route_pattern: String,
+    matched_path: String,
+}
+
+/// Converts a matched route into a JSON object, e.g. to echo the routing decision in
+/// an API response or a debug endpoint.
+///
+/// The object has the keys `"data"`, `"params"` (an object of parameter names to
+/// values, empty when nothing was captured), `"route_pattern"` (omitted when empty),
+/// and `"matched_path"`. Other `MatchedRoute` fields are not included.
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, add_route, find_route, to_json_value};
+///
+/// let router = Router::new();
+/// add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+///
+/// let matched = find_route(&router, "GET", "/users/42", true).unwrap();
+/// assert_eq!(
+///     to_json_value(&matched),
+///     serde_json::json!({
+///         "data": "get_user",
+///         "params": { "id": "42" },
+///         "route_pattern": "/users/:id",
+///         "matched_path": "users/42"
+///     })
+/// );
+/// ```
+///
+/// # Panics
+/// Panics if `T`'s `Serialize` implementation fails, as `serde_json::json!` does.
+pub fn to_json_value<T: serde::Serialize + Eq>(matched: &MatchedRoute<T>) -> Value {
+    let json = MatchedRouteJsonRef {
+        data: &matched.data,
+        params: matched.iter_params().collect(),
+        route_pattern: &matched.route_pattern,
+        matched_path: &matched.matched_path,
+    };
+    serde_json::to_value(json).expect("route data serializes to JSON")
+}
+
+/// Reads a matched route back from the JSON object `to_json_value` produces.
+///
+/// An empty or missing `"params"` object becomes `params: None`, and a missing
+/// `"route_pattern"` an empty pattern. `specificity` is recomputed from the pattern;
+/// every other field gets its default, as it is not part of the JSON.
+///
+/// # Errors
+/// Returns a `serde_json::Error` if `value` is not an object with `"data"` and
+/// `"matched_path"` keys, or if a value has the wrong type.
+pub fn from_json_value<T: serde::de::DeserializeOwned + Eq>(
+    value: &Value,
+) -> Result<MatchedRoute<T>, serde_json::Error> {
+    let json = MatchedRouteJson::<T>::deserialize(value)?;
+    let segments: Vec<&str> = split_path(json.route_pattern.trim_start_matches('/')).collect();
+    let specificity = build_param_entries_for_pattern_segments(&segments).map_or(0, |params_map| {
+        route_specificity_score(&params_map, segments.len())
+    });
+    Ok(MatchedRoute {
+        data: json.data,
+        matched_path: json.matched_path,
+        params: json
+            .params
+            .filter(|params| !params.is_empty())
+            .map(|params| params.into_iter().collect()),
+        route_pattern: json.route_pattern,
+        query: None,
+        is_head_derived: false,
+        middleware: Vec::new(),
+        required_scopes: None,
+        timeout_ms: None,
+        deprecated: None,
+        rate_limit: None,
+        upgrade: None,
+        cors: None,
+        cache: None,
+        description: None,
+        attributes: None,
+        specificity,
+    })
+}
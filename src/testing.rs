@@ -0,0 +1,559 @@
+//! Helpers for exercising a router outside of hand-written unit tests.
+//!
+//! With the `testing` feature enabled, this module provides assertion macros
+//! ([`assert_route_matches!`](crate::assert_route_matches) and
+//! [`assert_route_not_found!`](crate::assert_route_not_found)) for tests of code
+//! that builds a `Router`, along with [`TestRouterBuilder`], a
+//! [`standard_api_router`] fixture, [`generate_test_paths`] for route coverage tests,
+//! and a [`MockRouter`] for code written against
+//! [`RouterInterface`](crate::RouterInterface).
+//!
+//! With the `fuzzing` feature enabled, [`NormalizedPath`](crate::types::NormalizedPath)
+//! implements [`arbitrary::Arbitrary`] so fuzz targets can feed randomly generated,
+//! well-formed request paths into the lookup functions.
+
+#[cfg(feature = "fuzzing")]
+use crate::types::NormalizedPath;
+#[cfg(feature = "testing")]
+use crate::{
+    RouterInterface,
+    context::Router,
+    error::RouterError,
+    operations::{add_route, find_route, list_routes},
+    types::MatchedRoute,
+};
+#[cfg(feature = "testing")]
+use parking_lot::Mutex;
+#[cfg(feature = "testing")]
+use std::collections::HashSet;
+
+/// Builds a `Router` for a test with one call per route.
+///
+/// Each method panics if the route cannot be added, so fixtures fail loudly.
+///
+/// # Example
+/// ```rust
+/// use rou3::testing::TestRouterBuilder;
+///
+/// let router = TestRouterBuilder::new()
+///     .get("/users", "list_users")
+///     .post("/users", "create_user")
+///     .build();
+/// # let _ = router;
+/// ```
+#[cfg(feature = "testing")]
+#[derive(Debug)]
+pub struct TestRouterBuilder<T> {
+    router: Router<T>,
+}
+
+#[cfg(feature = "testing")]
+impl<T: Clone> Default for TestRouterBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "testing")]
+impl<T: Clone> TestRouterBuilder<T> {
+    /// Starts building an empty router.
+    pub fn new() -> Self {
+        Self {
+            router: Router::new(),
+        }
+    }
+
+    /// Adds a route for `method`, panicking if `path` is not a valid pattern.
+    pub fn route(self, method: &str, path: &str, data: T) -> Self {
+        if let Err(err) = add_route(&self.router, method, path, data) {
+            panic!("failed to add test route {method} {path}: {err}");
+        }
+        self
+    }
+
+    /// Adds a `GET` route.
+    pub fn get(self, path: &str, data: T) -> Self {
+        self.route("GET", path, data)
+    }
+
+    /// Adds a `POST` route.
+    pub fn post(self, path: &str, data: T) -> Self {
+        self.route("POST", path, data)
+    }
+
+    /// Adds a `PUT` route.
+    pub fn put(self, path: &str, data: T) -> Self {
+        self.route("PUT", path, data)
+    }
+
+    /// Adds a `DELETE` route.
+    pub fn delete(self, path: &str, data: T) -> Self {
+        self.route("DELETE", path, data)
+    }
+
+    /// Finishes building the router.
+    pub fn build(self) -> Router<T> {
+        self.router
+    }
+}
+
+/// Returns a router with a typical REST API layout, for use as a test fixture.
+///
+/// It covers static routes (`/`, `/health`, `/users`), parameters
+/// (`/users/:id`, `/users/:id/posts/:post_id`), an optional parameter
+/// (`/search/:query?`), and a catch-all wildcard (`/static/**:path`). Each
+/// route's data is a short name such as `"get_user"`.
+#[cfg(feature = "testing")]
+pub fn standard_api_router() -> Router<&'static str> {
+    TestRouterBuilder::new()
+        .get("/", "index")
+        .get("/health", "health")
+        .get("/users", "list_users")
+        .post("/users", "create_user")
+        .get("/users/:id", "get_user")
+        .put("/users/:id", "update_user")
+        .delete("/users/:id", "delete_user")
+        .get("/users/:id/posts/:post_id", "get_user_post")
+        .get("/search/:query?", "search")
+        .get("/static/**:path", "static_files")
+        .build()
+}
+
+/// Which registered route patterns a set of test requests exercised, as computed
+/// by [`coverage_report`].
+#[cfg(feature = "testing")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageReport {
+    /// Registered patterns matched by at least one tested request, in `list_routes` order.
+    pub covered: Vec<String>,
+    /// Registered patterns no tested request matched, in `list_routes` order.
+    pub uncovered: Vec<String>,
+    /// The share of registered patterns that were covered, from `0.0` to `100.0`.
+    /// A router with no routes is fully covered.
+    pub coverage_percent: f64,
+}
+
+/// Runs each `(method, path)` in `tested_paths` through `find_route` and reports
+/// which of the router's route patterns were matched.
+///
+/// Coverage is tracked per pattern, so a pattern registered for several methods
+/// counts as covered once any of them is matched. Requests that match no route
+/// are ignored.
+///
+/// # Example
+/// ```rust
+/// use rou3::testing::{coverage_report, standard_api_router};
+///
+/// let report = coverage_report(&standard_api_router(), &[("GET", "/users/1")]);
+/// assert_eq!(report.covered, vec!["/users/:id"]);
+/// assert!(report.coverage_percent < 50.0);
+/// ```
+#[cfg(feature = "testing")]
+pub fn coverage_report<T: Clone + Eq + std::hash::Hash>(
+    router: &Router<T>,
+    tested_paths: &[(&str, &str)],
+) -> CoverageReport {
+    let hit: HashSet<String> = tested_paths
+        .iter()
+        .filter_map(|(method, path)| find_route(router, method, path, true).ok())
+        .map(|matched| matched.route_pattern)
+        .collect();
+
+    let mut registered: Vec<String> = Vec::new();
+    for entry in list_routes(router) {
+        if !registered.contains(&entry.pattern) {
+            registered.push(entry.pattern);
+        }
+    }
+    let total = registered.len();
+    let (covered, uncovered): (Vec<String>, Vec<String>) = registered
+        .into_iter()
+        .partition(|pattern| hit.contains(pattern));
+    let coverage_percent = if total == 0 {
+        100.0
+    } else {
+        covered.len() as f64 * 100.0 / total as f64
+    };
+
+    CoverageReport {
+        covered,
+        uncovered,
+        coverage_percent,
+    }
+}
+
+/// Asserts that every static route in `router` is found by `find_route` with
+/// its own pattern as the request path and returns that route's data.
+///
+/// # Panics
+/// Panics if a static route is not found or a different route's data is returned,
+/// e.g., because the route was disabled or is shadowed by an earlier handler.
+#[cfg(feature = "testing")]
+pub fn assert_all_routes_findable<T: Clone + Eq>(router: &Router<T>) {
+    for entry in list_routes(router) {
+        if entry.pattern.contains([':', '*']) {
+            continue;
+        }
+        match find_route(router, &entry.method, &entry.pattern, false) {
+            Ok(matched) if matched.data == entry.data => {}
+            Ok(_) => panic!(
+                "{} {} matched a different route's data",
+                entry.method, entry.pattern
+            ),
+            Err(err) => panic!("{} {} is not findable: {err}", entry.method, entry.pattern),
+        }
+    }
+}
+
+/// Generates a `(method, path)` request for every registered route, to bootstrap route
+/// coverage tests without writing the paths by hand.
+///
+/// Static routes are requested with their pattern as-is. Each parameter (`:id`, `:id?`,
+/// `:id+`) is filled with `{name}_test_value` (e.g., `id_test_value`), and an unnamed
+/// `*` with `param_test_value`. A wildcard (`**` or `**:path`) becomes
+/// `wildcard/test/value`. With the `regex` feature, a parameter whose constraint
+/// rejects `{name}_test_value` gets `1` or `a` instead when the constraint accepts
+/// one of them. Routes for any method keep the empty method, which `find_route` also
+/// accepts.
+///
+/// Pairs are returned in `list_routes` order, without duplicates. Each path is found
+/// by `find_route` for its method, though not necessarily answered by the route it was
+/// generated from (a more specific route may take precedence), and disabled or guarded
+/// routes may not be found at all.
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, add_route, find_route, testing::generate_test_paths};
+///
+/// let router = Router::new();
+/// add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+/// add_route(&router, "GET", "/files/**:path", "files").unwrap();
+///
+/// let paths = generate_test_paths(&router);
+/// assert!(paths.contains(&("GET".to_string(), "/users/id_test_value".to_string())));
+/// for (method, path) in &paths {
+///     assert!(find_route(&router, method, path, false).is_ok());
+/// }
+/// ```
+#[cfg(feature = "testing")]
+pub fn generate_test_paths<T: Clone + Eq + std::hash::Hash>(
+    router: &Router<T>,
+) -> Vec<(String, String)> {
+    let mut paths: Vec<(String, String)> = Vec::new();
+    for entry in list_routes(router) {
+        let path = entry
+            .pattern
+            .split('/')
+            .map(test_path_segment)
+            .collect::<Vec<_>>()
+            .join("/");
+        let pair = (entry.method, path);
+        if !paths.contains(&pair) {
+            paths.push(pair);
+        }
+    }
+    paths
+}
+
+/// Fills in one segment of a route pattern for `generate_test_paths`.
+#[cfg(feature = "testing")]
+fn test_path_segment(segment: &str) -> String {
+    let segment = segment.strip_suffix('?').unwrap_or(segment);
+    if segment == "**" || segment.starts_with("**:") {
+        return "wildcard/test/value".to_string();
+    }
+    if segment == "*" {
+        return "param_test_value".to_string();
+    }
+    let Some(param) = segment.strip_prefix(':') else {
+        return segment.to_string();
+    };
+    let param = param.strip_suffix('+').unwrap_or(param);
+    let (name, constraint) = match param.split_once('<') {
+        Some((name, constraint)) => (name, constraint.strip_suffix('>')),
+        None => (param, None),
+    };
+    let value = format!("{name}_test_value");
+    #[cfg(feature = "regex")]
+    if let Some(regex) =
+        constraint.and_then(|source| regex::Regex::new(&format!("^(?:{source})$")).ok())
+    {
+        return [value.as_str(), "1", "a"]
+            .into_iter()
+            .find(|candidate| regex.is_match(candidate))
+            .unwrap_or(&value)
+            .to_string();
+    }
+    #[cfg(not(feature = "regex"))]
+    let _ = constraint;
+    value
+}
+
+/// Asserts that `find_route` matches `path` for `method` and returns `expected_data`.
+///
+/// The extended form `params: { "name" => "value", ... }` also captures parameters
+/// and asserts that exactly the listed parameters were extracted.
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, add_route, assert_route_matches};
+///
+/// let router = Router::new();
+/// add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+///
+/// assert_route_matches!(&router, "GET", "/users/123", "get_user");
+/// assert_route_matches!(&router, "GET", "/users/123", "get_user", params: { "id" => "123" });
+/// ```
+#[cfg(feature = "testing")]
+#[macro_export]
+macro_rules! assert_route_matches {
+    ($router:expr, $method:expr, $path:expr, $expected_data:expr, params: { $($key:expr => $value:expr),* $(,)? } $(,)?) => {{
+        match $crate::find_route($router, $method, $path, true) {
+            Ok(matched) => {
+                assert_eq!(matched.data, $expected_data, "{} {} matched the wrong route", $method, $path);
+                let params = matched.params.unwrap_or_default();
+                $(
+                    assert_eq!(
+                        params.get($key).map(String::as_str),
+                        Some($value),
+                        "{} {}: unexpected value for parameter '{}'", $method, $path, $key
+                    );
+                )*
+                let expected_param_count: usize = 0 $(+ { let _ = $key; 1 })*;
+                assert_eq!(
+                    params.len(),
+                    expected_param_count,
+                    "{} {}: unexpected parameters {:?}", $method, $path, params
+                );
+            }
+            Err(err) => panic!("expected {} {} to match a route, got {:?}", $method, $path, err),
+        }
+    }};
+    ($router:expr, $method:expr, $path:expr, $expected_data:expr $(,)?) => {{
+        match $crate::find_route($router, $method, $path, false) {
+            Ok(matched) => {
+                assert_eq!(matched.data, $expected_data, "{} {} matched the wrong route", $method, $path);
+            }
+            Err(err) => panic!("expected {} {} to match a route, got {:?}", $method, $path, err),
+        }
+    }};
+}
+
+/// Asserts that `find_route` finds no route for `path` and `method`, i.e. returns
+/// `RouterError::RouteNotFound` or `RouterError::MethodNotAllowed`.
+///
+/// # Example
+/// ```rust
+/// use rou3::{Router, add_route, assert_route_not_found};
+///
+/// let router = Router::new();
+/// add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+///
+/// assert_route_not_found!(&router, "POST", "/users/123");
+/// assert_route_not_found!(&router, "GET", "/posts");
+/// ```
+#[cfg(feature = "testing")]
+#[macro_export]
+macro_rules! assert_route_not_found {
+    ($router:expr, $method:expr, $path:expr $(,)?) => {{
+        match $crate::find_route($router, $method, $path, false) {
+            Err(
+                $crate::RouterError::RouteNotFound { .. }
+                | $crate::RouterError::MethodNotAllowed { .. },
+            ) => {}
+            other => panic!(
+                "expected {} {} to find no route, got {:?}",
+                $method, $path, other
+            ),
+        }
+    }};
+}
+
+/// Builds a `MatchedRoute` for `data` with no parameters or metadata, for use as a
+/// programmed [`MockRouter`] response. `route_pattern` is stored as given and
+/// `matched_path` is left empty.
+#[cfg(feature = "testing")]
+pub fn mock_route<T: Eq>(data: T, route_pattern: &str) -> MatchedRoute<T> {
+    MatchedRoute {
+        data,
+        matched_path: String::new(),
+        route_pattern: route_pattern.to_string(),
+        params: None,
+        query: None,
+        is_head_derived: false,
+        middleware: Vec::new(),
+        required_scopes: None,
+        timeout_ms: None,
+        deprecated: None,
+        rate_limit: None,
+        upgrade: None,
+        cors: None,
+        cache: None,
+        description: None,
+        #[cfg(feature = "serde_json")]
+        attributes: None,
+        specificity: 0,
+    }
+}
+
+/// A programmed expectation of a [`MockRouter`].
+#[cfg(feature = "testing")]
+#[derive(Debug)]
+struct MockExpectation<T: Eq> {
+    /// Matches request paths against the expected pattern.
+    matcher: Router<()>,
+    response: Result<MatchedRoute<T>, RouterError>,
+}
+
+/// A [`RouterInterface`] with pre-programmed lookup results, for unit-testing code
+/// that routes requests without building a real routing tree.
+///
+/// Expectations registered with [`on_find`](MockRouter::on_find) are checked in
+/// order; the first whose method and pattern match a `find` call supplies its
+/// result, and calls no expectation matches return `RouterError::RouteNotFound`.
+/// Every `find` call is recorded for
+/// [`assert_find_called_with`](MockRouter::assert_find_called_with). `add` and
+/// `remove` do nothing and report success and `false`.
+///
+/// # Example
+/// ```rust
+/// use rou3::{RouterInterface, testing::{MockRouter, mock_route}};
+///
+/// let mut mock = MockRouter::new();
+/// mock.on_find("GET", "/users/:id", Ok(mock_route("get_user", "/users/:id")));
+///
+/// let router: &dyn RouterInterface<&str> = &mock;
+/// assert_eq!(router.find("GET", "/users/123", false).unwrap().data, "get_user");
+/// assert!(router.find("POST", "/users/123", false).is_err());
+/// mock.assert_find_called_with("GET", "/users/123");
+/// ```
+#[cfg(feature = "testing")]
+#[derive(Debug)]
+pub struct MockRouter<T: Clone + Eq> {
+    expectations: Vec<MockExpectation<T>>,
+    find_calls: Mutex<Vec<(String, String)>>,
+}
+
+#[cfg(feature = "testing")]
+impl<T: Clone + Eq> Default for MockRouter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "testing")]
+impl<T: Clone + Eq> MockRouter<T> {
+    /// Constructs a `MockRouter` with no expectations.
+    pub fn new() -> Self {
+        Self {
+            expectations: Vec::new(),
+            find_calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Programs `find` to return `response` for requests matching `method` and
+    /// `pattern`, which are matched as `find_route` would match a route added with them.
+    ///
+    /// # Panics
+    /// Panics if `pattern` is not a valid route pattern.
+    pub fn on_find(
+        &mut self,
+        method: &str,
+        pattern: &str,
+        response: Result<MatchedRoute<T>, RouterError>,
+    ) -> &mut Self {
+        let matcher = Router::new();
+        if let Err(err) = add_route(&matcher, method, pattern, ()) {
+            panic!("failed to add mock expectation {method} {pattern}: {err}");
+        }
+        self.expectations
+            .push(MockExpectation { matcher, response });
+        self
+    }
+
+    /// Returns the `(method, path)` of every `find` call, in call order.
+    pub fn find_calls(&self) -> Vec<(String, String)> {
+        self.find_calls.lock().clone()
+    }
+
+    /// Panics unless `find` was called with exactly `method` and `path`.
+    pub fn assert_find_called_with(&self, method: &str, path: &str) {
+        let calls = self.find_calls.lock();
+        if !calls.iter().any(|(m, p)| m == method && p == path) {
+            panic!("expected find to be called with {method} {path}, got {calls:?}");
+        }
+    }
+}
+
+#[cfg(feature = "testing")]
+impl<T: Clone + Eq> RouterInterface<T> for MockRouter<T> {
+    fn add(&self, _method: &str, _path: &str, _data: T) -> Result<(), RouterError> {
+        Ok(())
+    }
+
+    fn find(
+        &self,
+        method: &str,
+        path: &str,
+        _capture: bool,
+    ) -> Result<MatchedRoute<T>, RouterError> {
+        self.find_calls
+            .lock()
+            .push((method.to_string(), path.to_string()));
+        self.expectations
+            .iter()
+            .find(|expectation| find_route(&expectation.matcher, method, path, false).is_ok())
+            .map_or_else(
+                || Err(RouterError::not_found(method, path)),
+                |expectation| expectation.response.clone(),
+            )
+    }
+
+    fn remove(&self, _method: &str, _path: &str) -> Result<bool, RouterError> {
+        Ok(false)
+    }
+}
+
+/// Characters a generated path is built from. Includes the pattern metacharacters
+/// (`:`, `*`, `?`) so fuzzed request paths also probe the static-map bypass in `find_route`.
+#[cfg(feature = "fuzzing")]
+const PATH_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789-._~%/:*?";
+
+#[cfg(feature = "fuzzing")]
+impl<'a> arbitrary::Arbitrary<'a> for NormalizedPath {
+    /// Maps each input byte onto a fixed URL-safe alphabet, so the result never contains
+    /// null bytes and is always valid UTF-8, then normalizes it.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let raw: &[u8] = u.arbitrary()?;
+        let path: String = raw
+            .iter()
+            .map(|b| PATH_ALPHABET[*b as usize % PATH_ALPHABET.len()] as char)
+            .collect();
+        Ok(NormalizedPath::new(&path))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <&[u8] as arbitrary::Arbitrary>::size_hint(depth)
+    }
+}
+
+#[cfg(all(test, feature = "fuzzing"))]
+mod tests {
+    use super::*;
+    use arbitrary::{Arbitrary, Unstructured};
+
+    #[test]
+    fn test_arbitrary_path_is_normalized() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let mut u = Unstructured::new(&bytes);
+        while let Ok(path) = NormalizedPath::arbitrary(&mut u) {
+            assert!(!path.as_str().starts_with('/'));
+            assert!(!path.as_str().ends_with('/'));
+            assert!(!path.as_str().contains("//"));
+            assert!(!path.as_str().contains('\0'));
+            if u.is_empty() {
+                break;
+            }
+        }
+    }
+}
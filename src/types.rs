@@ -3,11 +3,33 @@
 //! This module defines structures for storing method-specific data,
 //! parameter information, and the result of a route match.
 
-use ahash::AHashMap;
+use crate::compat::AHashMap;
+use crate::operations::find::MatchPath;
+use crate::operations::util::{
+    matched_pattern_depth, render_handler_pattern, route_specificity_score,
+};
+use alloc::{borrow::Cow, string::String, sync::Arc, vec::Vec};
+use core::{
+    convert::Infallible,
+    fmt,
+    ops::Deref,
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// A predicate deciding at lookup time whether a route may match a request.
+///
+/// It is called with the request method and the normalized request path (as in
+/// `MatchedRoute::matched_path`) and must return `true` for the route to match.
+pub type RouteGuard = Arc<dyn Fn(&str, &str) -> bool + Send + Sync>;
+
+/// A function applied to a captured parameter value before it is returned in
+/// `MatchedRoute::params`.
+pub type ParamTransform = Arc<dyn Fn(&str) -> String + Send + Sync>;
 
 /// Stores the data associated with a specific HTTP method on a route,
 /// along with information about any parameters defined in the route's path.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Clone)]
 pub struct MethodData<T> {
     /// The actual data or handler associated with this route and method.
     pub data: T,
@@ -15,15 +37,359 @@ pub struct MethodData<T> {
     /// `None` if the route has no parameters. Otherwise, `Some(Vec<ParamEntry>)`
     /// detailing how to extract parameters from a matched path.
     pub params_map: Option<Vec<ParamEntry>>,
+    /// Whether lookups may match this handler. Shared between the trie and the
+    /// `static_map` copy of a route, so toggling it affects both.
+    pub enabled: Arc<AtomicBool>,
+    /// Names of the middleware to apply to this route, in order.
+    /// Set with `add_route_with_middleware`; empty otherwise.
+    pub middleware: Vec<String>,
+    /// Authorization scopes a caller must hold to access this route.
+    /// Set with `add_route_with_scopes`; `None` otherwise.
+    pub required_scopes: Option<Vec<String>>,
+    /// Request timeout for this route, in milliseconds.
+    /// Set with `add_route_with_timeout`; `None` otherwise.
+    pub timeout_ms: Option<u64>,
+    /// Deprecation details for this route.
+    /// Set with `add_deprecated_route`; `None` otherwise.
+    pub deprecated: Option<DeprecationInfo>,
+    /// Rate limit for this route.
+    /// Set with `add_route_with_rate_limit`; `None` otherwise.
+    pub rate_limit: Option<RateLimit>,
+    /// Protocol upgrade this route expects (e.g., WebSocket).
+    /// Set with `add_upgrade_route`; `None` otherwise.
+    pub upgrade: Option<UpgradeInfo>,
+    /// CORS policy for this route.
+    /// Set with `add_route_with_cors`; `None` otherwise.
+    pub cors: Option<CorsConfig>,
+    /// HTTP caching policy for this route's responses.
+    /// Set with `add_route_with_cache`; `None` otherwise.
+    pub cache: Option<CacheConfig>,
+    /// A human-readable description of the route, for documentation and tooling.
+    /// Set with `add_route_with_description`; `None` otherwise.
+    pub description: Option<String>,
+    /// Precedence among the handlers for the same method on the same pattern node:
+    /// they are tried in descending priority order, and in registration order among
+    /// equal priorities. Set with `add_route_with_priority`; `0` otherwise.
+    pub priority: i32,
+    /// A predicate that must accept the request for this handler to match.
+    /// Set with `add_route_with_guard`; `None` otherwise.
+    pub guard: Option<RouteGuard>,
+    /// Functions applied to captured parameter values, keyed by parameter name.
+    /// Set with `add_route_with_transforms`; `None` otherwise.
+    pub transforms: Option<AHashMap<String, ParamTransform>>,
+    /// Values returned for optional parameters absent from the request path,
+    /// keyed by parameter name. Set with `add_route_with_defaults`; `None` otherwise.
+    pub defaults: Option<AHashMap<String, String>>,
+    /// The lowercase host pattern (e.g., `*.example.com`) this handler is limited to.
+    /// Set with `add_route_for_host`; `None` for handlers that serve every host.
+    pub host_constraint: Option<String>,
+    /// The media types (e.g., `application/json`) this handler responds with, for
+    /// `find_route_accepting`. Set with `add_route_producing`; `None` otherwise.
+    pub produces: Option<Vec<String>>,
+    /// The relative share of requests `find_route_weighted` sends to this handler
+    /// among the handlers for the same method and pattern. Set with
+    /// `add_weighted_route`; `None` for handlers that are not weighted variants, which
+    /// count as weight `1`.
+    pub weight: Option<u32>,
+    /// Arbitrary attributes for framework integrations, set with `add_route_with_attrs`.
+    #[cfg(feature = "serde_json")]
+    pub attributes: Option<serde_json::Map<String, serde_json::Value>>,
+    /// Regex constraints on parameters, parsed from `:name<regex>` segments of the
+    /// route pattern. `None` if the pattern has none.
+    #[cfg(feature = "regex")]
+    pub constraints: Option<Vec<ParamConstraint>>,
+}
+
+/// A regex that the value of a parameter must fully match, written as
+/// `:name<regex>` in a route pattern (e.g., `/users/:id<\d+>`).
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone)]
+pub struct ParamConstraint {
+    /// The index of the constrained segment in the route pattern.
+    pub segment_idx: usize,
+    /// The regex as written in the pattern.
+    pub source: String,
+    /// `source`, compiled and anchored to match the whole segment.
+    pub regex: regex::Regex,
+}
+
+#[cfg(feature = "regex")]
+impl PartialEq for ParamConstraint {
+    fn eq(&self, other: &Self) -> bool {
+        self.segment_idx == other.segment_idx && self.source == other.source
+    }
 }
 
+#[cfg(feature = "regex")]
+impl Eq for ParamConstraint {}
+
 impl<T: Clone> MethodData<T> {
     /// Constructs new `MethodData`.
     pub fn new(data: T, params_map: Option<Vec<ParamEntry>>) -> Self {
-        Self { data, params_map }
+        Self {
+            data,
+            params_map,
+            enabled: Arc::new(AtomicBool::new(true)),
+            middleware: Vec::new(),
+            required_scopes: None,
+            timeout_ms: None,
+            deprecated: None,
+            rate_limit: None,
+            upgrade: None,
+            cors: None,
+            cache: None,
+            description: None,
+            priority: 0,
+            guard: None,
+            transforms: None,
+            defaults: None,
+            host_constraint: None,
+            produces: None,
+            weight: None,
+            #[cfg(feature = "serde_json")]
+            attributes: None,
+            #[cfg(feature = "regex")]
+            constraints: None,
+        }
     }
 }
 
+impl<T> MethodData<T> {
+    /// Returns `true` if this handler's pattern ends in a repeated parameter
+    /// (`:name+`), which needs at least one more segment to match.
+    #[inline]
+    pub(crate) fn needs_trailing_segment(&self) -> bool {
+        matches!(
+            self.params_map.as_deref().and_then(<[ParamEntry]>::last),
+            Some(ParamEntry::Repeated(..))
+        )
+    }
+
+    /// Returns `true` unless the route has been disabled with `disable_route`.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if this handler is enabled and its guard, parameter
+    /// allowlists, and parameter constraints, if any, accept a request for `method`
+    /// on the normalized `path`.
+    #[inline]
+    pub(crate) fn accepts(&self, method: &str, path: &MatchPath<'_>) -> bool {
+        self.is_enabled()
+            && self
+                .guard
+                .as_ref()
+                .is_none_or(|guard| guard(method, path.as_str()))
+            && self.allowlists_accept(path)
+            && self.constraints_accept(path)
+    }
+
+    /// Returns `true` if this handler matches every request its pattern matches,
+    /// i.e. it has no guard, host constraint, content types, parameter allowlists, or
+    /// parameter constraints, and it was not added as a weighted variant.
+    pub(crate) fn is_unconditional(&self) -> bool {
+        #[cfg(feature = "regex")]
+        if self.constraints.is_some() {
+            return false;
+        }
+        self.guard.is_none()
+            && self.host_constraint.is_none()
+            && self.produces.is_none()
+            && self.weight.is_none()
+            && self.allowlisted_params().next().is_none()
+    }
+
+    /// Iterates over the parameters of this handler's pattern that have an allowlist.
+    fn allowlisted_params(&self) -> impl Iterator<Item = (&IndexParam, &[String])> {
+        self.params_map
+            .iter()
+            .flatten()
+            .filter_map(|entry| match entry {
+                ParamEntry::Index(param) => Some((param, param.allowlist.as_deref()?)),
+                ParamEntry::Wildcard(..) | ParamEntry::Repeated(..) => None,
+            })
+    }
+
+    fn allowlists_accept(&self, path: &MatchPath<'_>) -> bool {
+        // An absent optional parameter has no value to check.
+        self.allowlisted_params().all(|(param, allowlist)| {
+            path.segment(param.idx)
+                .is_none_or(|segment| allowlist.iter().any(|value| value == segment))
+        })
+    }
+
+    #[cfg(feature = "regex")]
+    fn constraints_accept(&self, path: &MatchPath<'_>) -> bool {
+        self.constraints.as_ref().is_none_or(|constraints| {
+            // An absent optional parameter has no value to check.
+            constraints.iter().all(|constraint| {
+                path.segment(constraint.segment_idx)
+                    .is_none_or(|segment| constraint.regex.is_match(segment))
+            })
+        })
+    }
+
+    #[cfg(not(feature = "regex"))]
+    #[inline]
+    fn constraints_accept(&self, _path: &MatchPath<'_>) -> bool {
+        true
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for MethodData<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("MethodData");
+        debug
+            .field("data", &self.data)
+            .field("params_map", &self.params_map)
+            .field("enabled", &self.enabled)
+            .field("middleware", &self.middleware)
+            .field("required_scopes", &self.required_scopes)
+            .field("timeout_ms", &self.timeout_ms)
+            .field("deprecated", &self.deprecated)
+            .field("rate_limit", &self.rate_limit)
+            .field("upgrade", &self.upgrade)
+            .field("cors", &self.cors)
+            .field("cache", &self.cache)
+            .field("description", &self.description)
+            .field("priority", &self.priority)
+            .field("guard", &self.guard.as_ref().map(|_| "<guard>"))
+            .field(
+                "transforms",
+                &self
+                    .transforms
+                    .as_ref()
+                    .map(|transforms| transforms.keys().collect::<Vec<_>>()),
+            )
+            .field("defaults", &self.defaults)
+            .field("host_constraint", &self.host_constraint)
+            .field("produces", &self.produces)
+            .field("weight", &self.weight);
+        #[cfg(feature = "serde_json")]
+        debug.field("attributes", &self.attributes);
+        #[cfg(feature = "regex")]
+        debug.field("constraints", &self.constraints);
+        debug.finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for MethodData<T> {
+    fn eq(&self, other: &Self) -> bool {
+        #[cfg(feature = "regex")]
+        if self.constraints != other.constraints {
+            return false;
+        }
+        #[cfg(feature = "serde_json")]
+        if self.attributes != other.attributes {
+            return false;
+        }
+        self.data == other.data
+            && self.params_map == other.params_map
+            && self.is_enabled() == other.is_enabled()
+            && self.middleware == other.middleware
+            && self.required_scopes == other.required_scopes
+            && self.timeout_ms == other.timeout_ms
+            && self.deprecated == other.deprecated
+            && self.rate_limit == other.rate_limit
+            && self.upgrade == other.upgrade
+            && self.cors == other.cors
+            && self.cache == other.cache
+            && self.description == other.description
+            && self.priority == other.priority
+            && match (&self.guard, &other.guard) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+            && match (&self.transforms, &other.transforms) {
+                (Some(a), Some(b)) => {
+                    a.len() == b.len()
+                        && a.iter().all(|(name, transform)| {
+                            b.get(name)
+                                .is_some_and(|other| Arc::ptr_eq(transform, other))
+                        })
+                }
+                (None, None) => true,
+                _ => false,
+            }
+            && self.defaults == other.defaults
+            && self.host_constraint == other.host_constraint
+            && self.produces == other.produces
+            && self.weight == other.weight
+    }
+}
+
+impl<T: Eq> Eq for MethodData<T> {}
+
+/// Deprecation details attached to a route with
+/// [`add_deprecated_route`](crate::operations::add::add_deprecated_route).
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct DeprecationInfo {
+    /// When the route was deprecated (e.g., a date or version).
+    pub since: String,
+    /// When the route is scheduled to be removed, if known.
+    pub sunset: Option<String>,
+    /// The route or URL clients should use instead, if any.
+    pub replacement: Option<String>,
+}
+
+/// A rate limit attached to a route with
+/// [`add_route_with_rate_limit`](crate::operations::add::add_route_with_rate_limit).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct RateLimit {
+    /// The sustained number of requests allowed per second.
+    pub requests_per_second: u32,
+    /// The number of requests allowed in a burst above `requests_per_second`.
+    pub burst: u32,
+}
+
+/// A protocol upgrade expected by a route added with
+/// [`add_upgrade_route`](crate::operations::add::add_upgrade_route).
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct UpgradeInfo {
+    /// The protocol the connection is upgraded to (e.g., `websocket`).
+    pub protocol: String,
+    /// The subprotocols the route supports, in order of preference
+    /// (e.g., the `Sec-WebSocket-Protocol` values for WebSocket).
+    pub subprotocols: Vec<String>,
+}
+
+/// A per-route CORS policy attached with
+/// [`add_route_with_cors`](crate::operations::add::add_route_with_cors).
+///
+/// The router does not emit headers itself; CORS middleware reads
+/// `MatchedRoute::cors` and answers preflight requests and sets the
+/// `Access-Control-*` headers accordingly.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct CorsConfig {
+    /// Origins allowed to access the route (e.g., `https://example.com`), or `*`.
+    pub allowed_origins: Vec<String>,
+    /// Whether credentials may be sent (`Access-Control-Allow-Credentials`).
+    pub allow_credentials: bool,
+    /// How long a preflight response may be cached, in seconds (`Access-Control-Max-Age`).
+    pub max_age_secs: Option<u32>,
+    /// Additional request headers allowed (`Access-Control-Allow-Headers`).
+    pub extra_headers: Vec<String>,
+}
+
+/// A per-route HTTP caching policy attached with
+/// [`add_route_with_cache`](crate::operations::add::add_route_with_cache).
+///
+/// Caching middleware reads `MatchedRoute::cache` to build the `Cache-Control`
+/// response header, so handlers don't hardcode it.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct CacheConfig {
+    /// How long the response may be cached, in seconds (`max-age`).
+    pub max_age_secs: u32,
+    /// Whether shared caches may store the response (`public` rather than `private`).
+    pub is_public: bool,
+    /// Whether a stale response must be revalidated before use (`must-revalidate`).
+    pub must_revalidate: bool,
+    /// Whether the response must not be stored at all (`no-store`).
+    pub no_store: bool,
+}
+
 /// Describes a parameter captured from a route's path pattern.
 ///
 /// Parameters can be simple placeholders (e.g., `/:id`), unnamed placeholders (`/*`),
@@ -31,16 +397,85 @@ impl<T: Clone> MethodData<T> {
 /// typically denoted by a `?` suffix in the path pattern (e.g., `/:id?`).
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum ParamEntry {
-    /// A parameter at a specific segment index.
-    /// `usize` is the segment index in the path.
-    /// `String` is the name of the parameter.
-    /// `bool` indicates if the parameter segment is optional.
-    Index(usize, String, bool),
+    /// A parameter at a specific segment index (e.g., `/:id` or `/*`).
+    Index(IndexParam),
     /// A wildcard parameter that captures all segments from a starting index.
     /// `usize` is the starting segment index.
     /// `String` is the name of the parameter.
     /// `bool` indicates if the wildcard itself is optional.
     Wildcard(usize, String, bool),
+    /// A parameter that captures one or more consecutive segments (e.g., `/:tags+`),
+    /// joined with `,`.
+    /// `usize` is the starting segment index; a repeated parameter must be the last
+    /// segment, so it captures every segment from there on.
+    /// `String` is the name of the parameter.
+    Repeated(usize, String),
+}
+
+/// A parameter that captures a single segment, described by [`ParamEntry::Index`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct IndexParam {
+    /// The segment index in the path.
+    pub idx: usize,
+    /// The name of the parameter (`_` for an unnamed `*`).
+    pub name: String,
+    /// Whether the parameter segment is optional.
+    pub optional: bool,
+    /// The only values the segment may take, set with `add_route_with_allowlist`.
+    /// `None` if any value is allowed.
+    pub allowlist: Option<Vec<String>>,
+}
+
+impl IndexParam {
+    /// Constructs a new `IndexParam` with no allowlist.
+    pub fn new(idx: usize, name: String, optional: bool) -> Self {
+        Self {
+            idx,
+            name,
+            optional,
+            allowlist: None,
+        }
+    }
+}
+
+/// The kind of a segment in a route pattern, as reported in a [`ParamSchema`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ParamKind {
+    /// A literal segment (e.g., `users`). Static segments capture nothing, so
+    /// `route_schemas` never reports them as parameters.
+    Static,
+    /// A single-segment parameter (e.g., `:id` or `*`).
+    Index,
+    /// A parameter capturing the rest of the path (e.g., `**:path` or `**`).
+    Wildcard,
+    /// A parameter capturing one or more remaining segments (e.g., `:tags+`).
+    Repeated,
+}
+
+/// A parameter of a route, as reported by
+/// [`route_schemas`](crate::operations::list::route_schemas).
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ParamSchema {
+    /// The parameter name (`_` for an unnamed `*` or `**`).
+    pub name: String,
+    /// Whether the parameter captures one segment or the rest of the path.
+    pub kind: ParamKind,
+    /// Whether the parameter may be absent (e.g., `:id?`).
+    pub optional: bool,
+    /// The index of the parameter's segment in the pattern, counting from `0`.
+    pub position: usize,
+}
+
+/// The structure of a registered route, as reported by
+/// [`route_schemas`](crate::operations::list::route_schemas).
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct RouteSchema {
+    /// The HTTP method the route was registered for (`""` for any method).
+    pub method: String,
+    /// The route pattern, in the same syntax accepted by `add_route`.
+    pub pattern: String,
+    /// The route's parameters, in pattern order.
+    pub params: Vec<ParamSchema>,
 }
 
 /// Represents a successfully matched route.
@@ -51,8 +486,267 @@ pub enum ParamEntry {
 pub struct MatchedRoute<T: Eq> {
     /// The data or handler associated with the matched route.
     pub data: T,
+    /// The normalized request path that was matched (e.g., `users/123` for a
+    /// request to `/users/123/`), without leading or trailing slashes.
+    pub matched_path: String,
+    /// The pattern of the matched route, in the syntax accepted by `add_route`
+    /// (e.g., `/users/:id`), including any global prefix.
+    pub route_pattern: String,
     /// An optional map of extracted parameters.
     /// Keys are parameter names (e.g., "id"), and values are the captured strings from the path.
     /// This is `None` if no parameters were captured or if capture was disabled.
     pub params: Option<AHashMap<String, String>>,
+    /// An optional map of query string parameters.
+    /// Only populated by `find_route_with_query` when capture is enabled and the
+    /// URL has a non-empty query string; `None` otherwise.
+    pub query: Option<AHashMap<String, String>>,
+    /// `true` when a `HEAD` request was answered by the route's `GET` handler because
+    /// no `HEAD` handler was registered. Callers should suppress the response body.
+    pub is_head_derived: bool,
+    /// Names of the middleware attached to the route with `add_route_with_middleware`.
+    pub middleware: Vec<String>,
+    /// Authorization scopes required by the route, set with `add_route_with_scopes`.
+    pub required_scopes: Option<Vec<String>>,
+    /// Request timeout of the route in milliseconds, set with `add_route_with_timeout`.
+    pub timeout_ms: Option<u64>,
+    /// Deprecation details of the route, set with `add_deprecated_route`.
+    pub deprecated: Option<DeprecationInfo>,
+    /// Rate limit of the route, set with `add_route_with_rate_limit`.
+    pub rate_limit: Option<RateLimit>,
+    /// Protocol upgrade the route expects, set with `add_upgrade_route`. When this is
+    /// `Some`, the request should be upgraded rather than answered as plain HTTP.
+    pub upgrade: Option<UpgradeInfo>,
+    /// CORS policy of the route, set with `add_route_with_cors`.
+    pub cors: Option<CorsConfig>,
+    /// Caching policy of the route, set with `add_route_with_cache`.
+    pub cache: Option<CacheConfig>,
+    /// Description of the route, set with `add_route_with_description`.
+    pub description: Option<String>,
+    /// Arbitrary attributes of the route, set with `add_route_with_attrs`.
+    #[cfg(feature = "serde_json")]
+    pub attributes: Option<serde_json::Map<String, serde_json::Value>>,
+    /// How specific the matched route pattern is; see
+    /// [`route_specificity_score`](crate::operations::util::route_specificity_score).
+    /// Higher scores mean more static segments, so results of `find_all_routes`
+    /// can be sorted by it.
+    pub specificity: u32,
+}
+
+impl<T: Eq> MatchedRoute<T> {
+    /// Iterates over the captured parameters as `(name, value)` pairs, in no
+    /// particular order. Yields nothing if no parameters were captured.
+    pub fn iter_params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params
+            .iter()
+            .flatten()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Returns the captured value of the parameter `name`, if any.
+    pub fn get_param(&self, name: &str) -> Option<&str> {
+        self.params.as_ref()?.get(name).map(String::as_str)
+    }
+
+    /// Iterates over the names of the captured parameters, in no particular order.
+    pub fn param_names(&self) -> impl Iterator<Item = &str> {
+        self.params
+            .iter()
+            .flat_map(|params| params.keys().map(String::as_str))
+    }
+}
+
+impl<T: Clone + Eq> MatchedRoute<T> {
+    /// Builds a `MatchedRoute` from the handler that matched, the captured parameters,
+    /// the normalized request path, and the segments that were matched against the trie.
+    pub(crate) fn from_method_data(
+        md: &MethodData<T>,
+        params: Option<AHashMap<String, String>>,
+        matched_path: String,
+        lookup_segments: &[&str],
+    ) -> Self {
+        let depth = matched_pattern_depth(&md.params_map, lookup_segments.len());
+        Self {
+            data: md.data.clone(),
+            matched_path,
+            route_pattern: render_handler_pattern(
+                &lookup_segments[..depth.min(lookup_segments.len())],
+                md,
+            ),
+            params,
+            query: None,
+            is_head_derived: false,
+            middleware: md.middleware.clone(),
+            required_scopes: md.required_scopes.clone(),
+            timeout_ms: md.timeout_ms,
+            deprecated: md.deprecated.clone(),
+            rate_limit: md.rate_limit,
+            upgrade: md.upgrade.clone(),
+            cors: md.cors.clone(),
+            cache: md.cache,
+            description: md.description.clone(),
+            #[cfg(feature = "serde_json")]
+            attributes: md.attributes.clone(),
+            specificity: route_specificity_score(&md.params_map, depth),
+        }
+    }
+}
+
+/// A matched route whose parameter values borrow from the request's path segments.
+///
+/// Returned by [`find_route_borrowed`](crate::operations::find::find_route_borrowed).
+/// Only the data and parameters are reported; use `find_route_segments` when the other
+/// `MatchedRoute` fields are needed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BorrowedMatchedRoute<'p, T: Eq> {
+    /// The data or handler associated with the matched route.
+    pub data: T,
+    /// An optional map of extracted parameters.
+    /// Values borrow the captured path segment, except for wildcards spanning several
+    /// segments and values produced by a transform or a default, which are owned.
+    /// This is `None` if no parameters were captured or if capture was disabled.
+    pub params: Option<AHashMap<String, Cow<'p, str>>>,
+}
+
+/// The parts of an incoming request the router can route on, passed to
+/// [`find_route_for_request`](crate::operations::find::find_route_for_request).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RoutableRequest<'a> {
+    /// The HTTP method (e.g., `GET`).
+    pub method: &'a str,
+    /// The request path, without the query string (e.g., `/users/123`).
+    pub path: &'a str,
+    /// The request headers, keyed by name. No route constrains headers yet, so they
+    /// are not consulted; they are accepted so callers can pass a complete request.
+    pub headers: Option<&'a AHashMap<String, String>>,
+    /// The raw query string, without the leading `?` (e.g., `q=rust&page=2`).
+    pub query: Option<&'a str>,
+}
+
+/// Which matches [`find_route_with_mode`](crate::operations::find_all::find_route_with_mode)
+/// returns for a request.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum MatchMode {
+    /// The route `find_route` selects.
+    #[default]
+    First,
+    /// The matching route with the highest `specificity`, as found by `find_all_routes`.
+    Best,
+    /// Every route `find_all_routes` returns.
+    All,
+}
+
+/// The result of [`find_route_with_mode`](crate::operations::find_all::find_route_with_mode).
+// `Single` is returned by value, like `find_route`'s result, rather than boxed.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MatchResult<T: Eq> {
+    /// The single route selected by `MatchMode::First` or `MatchMode::Best`.
+    Single(MatchedRoute<T>),
+    /// The routes returned for `MatchMode::All`, in `find_all_routes` order.
+    Multiple(Vec<MatchedRoute<T>>),
+}
+
+/// How a router treats a trailing slash on route patterns and request paths.
+///
+/// Set through [`RouterConfigBuilder::trailing_slash`](crate::RouterConfigBuilder::trailing_slash).
+/// The root path `/` is never considered to have a trailing slash.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum TrailingSlashMode {
+    /// Trailing slashes are stripped, so `/users/` and `/users` are the same path.
+    #[default]
+    Ignore,
+    /// Trailing slashes are kept, so `/users/` and `/users` are distinct paths.
+    Strict,
+    /// Request paths with a trailing slash that would otherwise match a route fail
+    /// with `RouterError::TrailingSlashRedirect`, naming the canonical path without it.
+    Redirect,
+}
+
+/// Which wildcard route a router picks when several could match a request path.
+///
+/// Set through [`RouterConfigBuilder::wildcard_preference`](crate::RouterConfigBuilder::wildcard_preference).
+/// Only matches served by a wildcard (`**`) route are affected; exact matches are
+/// selected as before.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum WildcardPreference {
+    /// The wildcard reached first by the normal lookup order: static children before
+    /// the parametric child before the wildcard child, at every level.
+    #[default]
+    FirstRegistered,
+    /// Among all matching wildcard routes, the one with the most static segments
+    /// before its `**` (e.g., `/:tenant/v1/data/**` over `/api/**` for
+    /// `/api/v1/data/x`). Ties keep the `FirstRegistered` choice.
+    LongestStaticPrefix,
+}
+
+/// A request path that has already been passed through [`normalize`](crate::operations::util::normalize).
+///
+/// Holding a `NormalizedPath` guarantees the inner string has no leading, trailing,
+/// or repeated slashes, so it can be split into segments directly.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct NormalizedPath(String);
+
+impl NormalizedPath {
+    /// Normalizes `path` and wraps the result.
+    pub fn new(path: &str) -> Self {
+        Self(crate::operations::util::normalize(path))
+    }
+
+    /// Returns the normalized path as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for NormalizedPath {
+    type Err = Infallible;
+
+    /// Normalizes `path`; this never fails.
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(path))
+    }
+}
+
+impl Deref for NormalizedPath {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for NormalizedPath {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for NormalizedPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The methods allowed on a path, as reported by
+/// [`get_options_response`](crate::operations::get_options_response).
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct OptionsResponse {
+    /// The allowed HTTP methods, sorted. Empty if no route matches the path.
+    pub allowed_methods: Vec<String>,
+    /// `allowed_methods` joined with `", "`, ready for an `Allow` response header.
+    pub allow_header: String,
+}
+
+/// A registered route, as reported by [`list_routes`](crate::operations::list_routes).
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct RouteEntry<T> {
+    /// The HTTP method the route was registered for (`""` for any method).
+    pub method: String,
+    /// The route pattern, reconstructed in the same syntax accepted by `add_route`
+    /// (e.g., `/users/:id`, `/files/**:path`).
+    pub pattern: String,
+    /// The data associated with the route.
+    pub data: T,
+    /// The route's description, if it was added with `add_route_with_description`.
+    pub description: Option<String>,
 }
@@ -0,0 +1,82 @@
+#![cfg(feature = "arc-swap")]
+
+use rou3::{ArcRouter, RouterConfig, RouterError, add_route_arc, find_route_arc, remove_route_arc};
+use std::sync::Arc;
+
+#[test]
+fn test_arc_router_add_find_remove() {
+    let router = ArcRouter::new();
+    add_route_arc(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route_arc(&router, "GET", "/health", "health").unwrap();
+    add_route_arc(&router, "POST", "/users/:id", "update_user").unwrap();
+
+    let matched = find_route_arc(&router, "GET", "/users/42", true).unwrap();
+    assert_eq!(matched.data, "get_user");
+    assert_eq!(
+        matched.params.unwrap().get("id").map(String::as_str),
+        Some("42")
+    );
+    assert_eq!(
+        find_route_arc(&router, "GET", "/health", false)
+            .unwrap()
+            .data,
+        "health"
+    );
+    assert!(matches!(
+        find_route_arc(&router, "DELETE", "/users/42", false),
+        Err(RouterError::MethodNotAllowed { .. })
+    ));
+
+    assert!(remove_route_arc(&router, "GET", "/health").unwrap());
+    assert!(!remove_route_arc(&router, "GET", "/health").unwrap());
+    assert!(matches!(
+        find_route_arc(&router, "GET", "/health", false),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+
+    // A failed write leaves the router unchanged.
+    assert!(add_route_arc(&router, "GET", "/files/:a/:a", "bad").is_err());
+    assert_eq!(
+        find_route_arc(&router, "GET", "/users/7", false)
+            .unwrap()
+            .data,
+        "get_user"
+    );
+}
+
+#[test]
+fn test_arc_router_uses_config() {
+    let router = ArcRouter::with_config(RouterConfig::builder().case_insensitive().build());
+    add_route_arc(&router, "GET", "/Users/:id", "get_user").unwrap();
+    assert_eq!(
+        find_route_arc(&router, "GET", "/USERS/1", false)
+            .unwrap()
+            .data,
+        "get_user"
+    );
+}
+
+#[test]
+fn test_arc_router_concurrent_writes_are_not_lost() {
+    let router = Arc::new(ArcRouter::new());
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let router = Arc::clone(&router);
+            std::thread::spawn(move || {
+                for j in 0..25 {
+                    add_route_arc(&router, "GET", &format!("/t{i}/r{j}/:id"), i * 100 + j).unwrap();
+                    find_route_arc(&router, "GET", &format!("/t{i}/r{j}/1"), false).unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    for i in 0..8 {
+        for j in 0..25 {
+            let matched = find_route_arc(&router, "GET", &format!("/t{i}/r{j}/1"), false).unwrap();
+            assert_eq!(matched.data, i * 100 + j);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use rou3::{Router, add_route, find_route, remove_route, route_count};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+};
+
+const WRITER_THREADS: usize = 16;
+const ROUTES_PER_THREAD: usize = 500;
+
+/// Starts a background thread that aborts the test run, printing the deadlocked
+/// threads' backtraces, if `parking_lot` detects a deadlock. A deadlocked test would
+/// otherwise hang forever. The returned flag stops the thread.
+#[cfg(feature = "deadlock_detection")]
+fn spawn_deadlock_detector() -> Arc<AtomicBool> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let detector_stop = Arc::clone(&stop);
+    thread::spawn(move || {
+        while !detector_stop.load(Ordering::Relaxed) {
+            thread::sleep(std::time::Duration::from_millis(50));
+            let deadlocks = parking_lot::deadlock::check_deadlock();
+            if deadlocks.is_empty() {
+                continue;
+            }
+            eprintln!("{} deadlock(s) detected", deadlocks.len());
+            for thread in deadlocks.iter().flatten() {
+                eprintln!("thread {:?}:\n{:?}", thread.thread_id(), thread.backtrace());
+            }
+            std::process::abort();
+        }
+    });
+    stop
+}
+
+#[cfg(not(feature = "deadlock_detection"))]
+fn spawn_deadlock_detector() -> Arc<AtomicBool> {
+    Arc::new(AtomicBool::new(false))
+}
+
+#[test]
+fn test_concurrent_add_route() {
+    let stop_detector = spawn_deadlock_detector();
+    let router: Arc<Router<String>> = Arc::new(Router::new());
+
+    let writers: Vec<_> = (0..WRITER_THREADS)
+        .map(|thread_id| {
+            let router = Arc::clone(&router);
+            thread::spawn(move || {
+                for i in 0..ROUTES_PER_THREAD {
+                    let pattern = if i % 2 == 0 {
+                        format!("/t{thread_id}/static/{i}")
+                    } else {
+                        format!("/t{thread_id}/param/{i}/:id")
+                    };
+                    add_route(&router, "GET", &pattern, format!("{thread_id}-{i}")).unwrap();
+                }
+            })
+        })
+        .collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+    stop_detector.store(true, Ordering::Relaxed);
+
+    assert_eq!(route_count(&router), WRITER_THREADS * ROUTES_PER_THREAD);
+    for thread_id in 0..WRITER_THREADS {
+        for i in [0, 1, ROUTES_PER_THREAD - 2, ROUTES_PER_THREAD - 1] {
+            let path = if i % 2 == 0 {
+                format!("/t{thread_id}/static/{i}")
+            } else {
+                format!("/t{thread_id}/param/{i}/42")
+            };
+            assert_eq!(
+                find_route(&router, "GET", &path, false).unwrap().data,
+                format!("{thread_id}-{i}")
+            );
+        }
+    }
+}
+
+#[test]
+fn test_concurrent_read_write() {
+    let stop_detector = spawn_deadlock_detector();
+    let router: Arc<Router<String>> = Arc::new(Router::new());
+    add_route(&router, "GET", "/stable/:id", "stable".to_string()).unwrap();
+    let writers_done = Arc::new(AtomicBool::new(false));
+
+    let readers: Vec<_> = (0..8)
+        .map(|_| {
+            let router = Arc::clone(&router);
+            let writers_done = Arc::clone(&writers_done);
+            thread::spawn(move || {
+                let mut lookups = 0;
+                while !writers_done.load(Ordering::Relaxed) || lookups == 0 {
+                    let matched = find_route(&router, "GET", "/stable/7", true).unwrap();
+                    assert_eq!(matched.data, "stable");
+                    // Churned routes may or may not be present, but must never be corrupt.
+                    if let Ok(matched) = find_route(&router, "GET", "/churn/13/5", false) {
+                        assert!(matched.data.starts_with("churn"));
+                    }
+                    lookups += 1;
+                }
+            })
+        })
+        .collect();
+
+    let writers: Vec<_> = (0..2)
+        .map(|writer_id| {
+            let router = Arc::clone(&router);
+            thread::spawn(move || {
+                for round in 0..200 {
+                    let pattern = format!("/churn/{writer_id}{round}/:id");
+                    add_route(&router, "GET", &pattern, format!("churn-{writer_id}")).unwrap();
+                    if round % 2 == 0 {
+                        assert!(remove_route(&router, "GET", &pattern).unwrap());
+                    }
+                }
+            })
+        })
+        .collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+    writers_done.store(true, Ordering::Relaxed);
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    stop_detector.store(true, Ordering::Relaxed);
+
+    // Each writer keeps its odd rounds.
+    assert_eq!(route_count(&router), 1 + 2 * 100);
+}
@@ -1,5 +1,32 @@
-use rou3::{Router, RouterError, add_route, find_all_routes, find_route, remove_route};
-use std::collections::{HashMap, HashSet};
+use rou3::{
+    AmbiguityReport, BorrowedMatchedRoute, CacheConfig, CorsConfig, DeprecationInfo, ExactRouter,
+    HotPathSuggestion, MatchMode, MatchResult, MatchedRoute, NamespaceRouter, NormalizedPath,
+    OptionsResponse, ParamKind, ParamSchema, RateLimit, RoutableRequest, RouteEntry, RouteGroup,
+    RouteSchema, Router, RouterConfig, RouterError, RouterInterface, RouterWarning,
+    TrailingSlashMode, UpgradeInfo, VHostRouter, WildcardPreference, add_deprecated_route,
+    add_host, add_namespace, add_route, add_route_for_host, add_route_multi, add_route_producing,
+    add_route_shared, add_route_with_allowlist, add_route_with_cache, add_route_with_cors,
+    add_route_with_defaults, add_route_with_description, add_route_with_guard,
+    add_route_with_middleware, add_route_with_priority, add_route_with_rate_limit,
+    add_route_with_scopes, add_route_with_timeout, add_route_with_transforms, add_static,
+    add_upgrade_route, add_versioned_routes, add_weighted_route, analyze_hot_paths, compact,
+    compress, compute_trie_stats, diff_routers, disable_route, drain_method_routes, enable_route,
+    export_mermaid, find_all_routes, find_ambiguous_routes, find_in_host, find_in_namespace,
+    find_route, find_route_accepting, find_route_best, find_route_borrowed, find_route_chain,
+    find_route_for_request, find_route_normalized, find_route_segments, find_route_with_host,
+    find_route_with_mode, find_route_with_query, find_routes_batch, find_static,
+    get_options_response, get_route_handle, iter_nodes, list_api_versions, list_routes,
+    memory_usage, remove_namespace, remove_route, restore, route, route_count, route_group,
+    route_schemas, shrink_to_fit, snapshot, transform_all, validate,
+};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 use tracing::Level;
 use tracing_subscriber::{EnvFilter, FmtSubscriber};
 
@@ -296,6 +323,16 @@ fn test_find_all_routes_behavior() {
             );
         }
     }
+
+    // Deduplication is by route pattern, so equal data on two patterns is kept.
+    add_route(&router, "GET", "/shared/:id", "shared").unwrap();
+    add_route(&router, "GET", "/shared/**:rest", "shared").unwrap();
+    let patterns: Vec<_> = find_all_routes(&router, "GET", "/shared/1", false)
+        .into_iter()
+        .filter(|m| m.data == "shared")
+        .map(|m| m.route_pattern)
+        .collect();
+    assert_eq!(patterns, ["/shared/**:rest", "/shared/:id"]);
 }
 
 #[test]
@@ -320,6 +357,34 @@ fn test_invalid_patterns_add_route() {
     ));
 }
 
+#[test]
+fn test_duplicate_param_name_rejected() {
+    setup_tracing_for_tests();
+    let router = Router::<&str>::new();
+    assert!(matches!(
+        add_route(&router, "GET", "/users/:id/posts/:id", "data"),
+        Err(RouterError::InvalidSegment { segment, reason })
+            if segment == ":id" && reason == "duplicate parameter name 'id' in pattern"
+    ));
+    assert!(matches!(
+        add_route(&router, "GET", "/files/:path/**:path", "data"),
+        Err(RouterError::InvalidSegment { segment, .. }) if segment == "**:path"
+    ));
+    assert!(
+        find_route(&router, "GET", "/users/1/posts/2", false).is_err(),
+        "Rejected pattern must not be registered"
+    );
+
+    // Unnamed parameters all capture as `_` and remain allowed.
+    add_route(&router, "GET", "/pairs/*/*", "pairs").unwrap();
+    assert_eq!(
+        find_route(&router, "GET", "/pairs/a/b", false)
+            .unwrap()
+            .data,
+        "pairs"
+    );
+}
+
 #[test]
 fn test_optional_trailing_param_find_route() {
     setup_tracing_for_tests();
@@ -366,3 +431,3172 @@ fn test_optional_trailing_wildcard_find_route() {
         "Optional wildcard matching empty should give empty string for param"
     );
 }
+
+#[test]
+fn test_list_routes_reconstructs_patterns() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users/:id", "user_by_id").unwrap();
+    add_route(&router, "GET", "/", "root").unwrap();
+    add_route(&router, "POST", "/users", "create_user").unwrap();
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "GET", "/search/:query?", "search").unwrap();
+    add_route(&router, "", "/files/*", "file").unwrap();
+    add_route(&router, "GET", "/assets/**:filepath", "assets").unwrap();
+    add_route(&router, "GET", "/data/**", "data").unwrap();
+
+    let entry = |method: &str, pattern: &str, data| RouteEntry {
+        method: method.to_string(),
+        pattern: pattern.to_string(),
+        data,
+        description: None,
+    };
+    assert_eq!(
+        list_routes(&router),
+        vec![
+            entry("GET", "/", "root"),
+            entry("GET", "/assets/**:filepath", "assets"),
+            entry("GET", "/data/**", "data"),
+            entry("", "/files/*", "file"),
+            entry("GET", "/search/:query?", "search"),
+            entry("GET", "/users", "list_users"),
+            entry("POST", "/users", "create_user"),
+            entry("GET", "/users/:id", "user_by_id"),
+        ]
+    );
+
+    remove_route(&router, "GET", "/users/:id").unwrap();
+    assert!(
+        list_routes(&router)
+            .iter()
+            .all(|r| r.pattern != "/users/:id")
+    );
+}
+
+#[test]
+fn test_disable_and_enable_route() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/toggle/static", "toggle_static").unwrap();
+    add_route(&router, "GET", "/toggle/:id", "toggle_param").unwrap();
+
+    assert!(disable_route(&router, "GET", "/toggle/static").unwrap());
+    assert_eq!(
+        find_route(&router, "GET", "/toggle/static", false)
+            .unwrap()
+            .data,
+        "toggle_param",
+        "Disabled static route should fall through to the param route"
+    );
+    assert!(
+        find_all_routes(&router, "GET", "/toggle/static", false)
+            .iter()
+            .all(|m| m.data != "toggle_static")
+    );
+
+    assert!(disable_route(&router, "GET", "/toggle/:id").unwrap());
+    assert!(find_route(&router, "GET", "/toggle/static", false).is_err());
+    assert!(find_route(&router, "GET", "/toggle/123", false).is_err());
+
+    assert!(enable_route(&router, "GET", "/toggle/static").unwrap());
+    assert!(enable_route(&router, "GET", "/toggle/:id").unwrap());
+    assert_eq!(
+        find_route(&router, "GET", "/toggle/static", false)
+            .unwrap()
+            .data,
+        "toggle_static"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/toggle/123", false)
+            .unwrap()
+            .data,
+        "toggle_param"
+    );
+
+    assert!(!disable_route(&router, "POST", "/toggle/static").unwrap());
+    assert!(!disable_route(&router, "GET", "/missing").unwrap());
+    assert!(disable_route(&router, "GET", "/toggle/:").is_err());
+}
+
+#[test]
+fn test_vhost_router_dispatches_by_host() {
+    setup_tracing_for_tests();
+    let api = Router::new();
+    add_route(&api, "GET", "/users/:id", "api_user").unwrap();
+    let tenants = Router::new();
+    add_route(&tenants, "GET", "/users/:id", "tenant_user").unwrap();
+    add_route(&tenants, "GET", "/dashboard", "tenant_dashboard").unwrap();
+
+    let vhost = VHostRouter::new();
+    add_host(&vhost, "api.example.com", api);
+    add_host(&vhost, "*.example.com", tenants);
+
+    let matched = find_in_host(&vhost, "API.example.com:8443", "GET", "/users/7", true).unwrap();
+    assert_eq!(matched.data, "api_user");
+    assert_eq!(matched.params.unwrap().get("id").unwrap(), "7");
+
+    assert_eq!(
+        find_in_host(&vhost, "acme.example.com", "GET", "/users/7", false)
+            .unwrap()
+            .data,
+        "tenant_user"
+    );
+    assert_eq!(
+        find_in_host(&vhost, "acme.example.com", "GET", "/dashboard", false)
+            .unwrap()
+            .data,
+        "tenant_dashboard"
+    );
+
+    assert!(
+        find_in_host(&vhost, "api.example.com", "GET", "/dashboard", false).is_err(),
+        "Exact host match should not fall back to the wildcard host"
+    );
+    assert!(matches!(
+        find_in_host(&vhost, "example.com", "GET", "/users/7", false),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+    assert!(matches!(
+        find_in_host(&vhost, "other.org", "GET", "/users/7", false),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_find_route_with_query() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/search", "search").unwrap();
+    add_route(&router, "GET", "/users/:id", "user_by_id").unwrap();
+
+    let matched = find_route_with_query(&router, "GET", "/search?q=rust&page=2", true).unwrap();
+    assert_eq!(matched.data, "search");
+    assert!(matched.params.is_none());
+    assert_eq!(
+        convert_params_to_hashmap(matched.query),
+        Some(HashMap::from([
+            ("q".to_string(), "rust".to_string()),
+            ("page".to_string(), "2".to_string()),
+        ]))
+    );
+
+    let matched_user = find_route_with_query(&router, "GET", "/users/42/?tab=posts", true).unwrap();
+    assert_eq!(matched_user.data, "user_by_id");
+    assert_eq!(
+        convert_params_to_hashmap(matched_user.params),
+        Some(HashMap::from([("id".to_string(), "42".to_string())]))
+    );
+    assert_eq!(
+        convert_params_to_hashmap(matched_user.query),
+        Some(HashMap::from([("tab".to_string(), "posts".to_string())]))
+    );
+
+    let uncaptured = find_route_with_query(&router, "GET", "/users/42?tab=posts", false).unwrap();
+    assert!(uncaptured.params.is_none());
+    assert!(uncaptured.query.is_none());
+
+    assert!(
+        find_route_with_query(&router, "GET", "/search", true)
+            .unwrap()
+            .query
+            .is_none()
+    );
+    assert!(matches!(
+        find_route_with_query(&router, "GET", "/missing?q=1", true),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_global_prefix() {
+    setup_tracing_for_tests();
+    let router = Router::with_global_prefix("/api/v1/");
+    add_route(&router, "GET", "/", "api_root").unwrap();
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "GET", "/users/:id", "user_by_id").unwrap();
+
+    assert_eq!(
+        find_route(&router, "GET", "/api/v1", false).unwrap().data,
+        "api_root"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/api/v1/users", false)
+            .unwrap()
+            .data,
+        "list_users"
+    );
+    let matched = find_route(&router, "GET", "/api/v1/users/9", true).unwrap();
+    assert_eq!(matched.data, "user_by_id");
+    assert_eq!(
+        convert_params_to_hashmap(matched.params),
+        Some(HashMap::from([("id".to_string(), "9".to_string())]))
+    );
+
+    assert!(matches!(
+        find_route(&router, "GET", "/users", false),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+    assert!(find_route(&router, "GET", "/api/v2/users", false).is_err());
+    assert!(
+        list_routes(&router)
+            .iter()
+            .any(|r| r.pattern == "/api/v1/users/:id")
+    );
+
+    assert!(remove_route(&router, "GET", "/users").unwrap());
+    assert!(find_route(&router, "GET", "/api/v1/users", false).is_err());
+}
+
+#[test]
+fn test_router_config_options() {
+    setup_tracing_for_tests();
+    let case_insensitive = Router::with_config(RouterConfig::builder().case_insensitive().build());
+    add_route(&case_insensitive, "GET", "/Users/:Name", "user").unwrap();
+    add_route(&case_insensitive, "GET", "/About", "about").unwrap();
+    let matched = find_route(&case_insensitive, "GET", "/USERS/Alice", true).unwrap();
+    assert_eq!(matched.data, "user");
+    assert_eq!(
+        convert_params_to_hashmap(matched.params),
+        Some(HashMap::from([("Name".to_string(), "Alice".to_string())])),
+        "Parameter names and values keep their case"
+    );
+    assert_eq!(
+        find_route(&case_insensitive, "GET", "/about", false)
+            .unwrap()
+            .data,
+        "about"
+    );
+    assert!(remove_route(&case_insensitive, "GET", "/ABOUT").unwrap());
+    assert!(find_route(&Router::<&str>::new(), "GET", "/about", false).is_err());
+
+    let decoding = Router::with_config(RouterConfig::builder().percent_decode().build());
+    add_route(&decoding, "GET", "/files/:name", "file").unwrap();
+    add_route(&decoding, "GET", "/hello world", "greeting").unwrap();
+    let matched = find_route(&decoding, "GET", "/files/my%20report.pdf", true).unwrap();
+    assert_eq!(
+        matched.params.unwrap().get("name").unwrap(),
+        "my report.pdf"
+    );
+    assert_eq!(
+        find_route(&decoding, "GET", "/hello%20world", false)
+            .unwrap()
+            .data,
+        "greeting"
+    );
+    let matched = find_route(&decoding, "GET", "/files/a%2Fb", true).unwrap();
+    assert_eq!(matched.params.unwrap().get("name").unwrap(), "a%2Fb");
+
+    let strict = Router::with_config(RouterConfig::builder().strict_empty_segments().build());
+    assert!(matches!(
+        add_route(&strict, "GET", "/a//b", "ab"),
+        Err(RouterError::InvalidSegment { .. })
+    ));
+    add_route(&strict, "GET", "/a/b", "ab").unwrap();
+    assert!(find_route(&strict, "GET", "/a/b", false).is_ok());
+    assert!(matches!(
+        find_route(&strict, "GET", "/a//b", false),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+
+    let shallow = Router::with_config(RouterConfig::builder().max_depth(2).build());
+    add_route(&shallow, "GET", "/a/:b", "ab").unwrap();
+    assert!(matches!(
+        add_route(&shallow, "GET", "/a/b/c", "abc"),
+        Err(RouterError::InvalidPath(reason)) if reason == "exceeds maximum path depth"
+    ));
+    assert!(matches!(
+        find_route(&shallow, "GET", "/a/b/c", false),
+        Err(RouterError::InvalidPath(_))
+    ));
+
+    assert_eq!(shallow.config, Router::<&str>::with_max_depth(2).config);
+
+    let prefixed = Router::with_config(RouterConfig::builder().global_prefix("/api").build());
+    add_route(&prefixed, "GET", "/health", "health").unwrap();
+    assert!(find_route(&prefixed, "GET", "/api/health", false).is_ok());
+    assert_eq!(
+        prefixed.config,
+        Router::<&str>::with_global_prefix("api/").config
+    );
+}
+
+#[test]
+fn test_trailing_slash_modes() {
+    setup_tracing_for_tests();
+    let router_with_mode = |mode: TrailingSlashMode| {
+        let router = Router::with_config(RouterConfig::builder().trailing_slash(mode).build());
+        add_route(&router, "GET", "/users", "users").unwrap();
+        add_route(&router, "GET", "/users/:id", "user").unwrap();
+        router
+    };
+
+    let ignore = router_with_mode(TrailingSlashMode::Ignore);
+    assert_eq!(
+        find_route(&ignore, "GET", "/users/", false).unwrap().data,
+        "users"
+    );
+    assert_eq!(
+        find_route(&ignore, "GET", "/users/1/", false).unwrap().data,
+        "user"
+    );
+
+    let strict = router_with_mode(TrailingSlashMode::Strict);
+    assert_eq!(
+        find_route(&strict, "GET", "/users", false).unwrap().data,
+        "users"
+    );
+    assert!(matches!(
+        find_route(&strict, "GET", "/users/", false),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+    add_route(&strict, "GET", "/users/", "users_slash").unwrap();
+    assert_eq!(
+        find_route(&strict, "GET", "/users/", false).unwrap().data,
+        "users_slash"
+    );
+    assert_eq!(
+        find_route(&strict, "GET", "/users", false).unwrap().data,
+        "users"
+    );
+    assert!(list_routes(&strict).iter().any(|r| r.pattern == "/users/"));
+    assert!(remove_route(&strict, "GET", "/users/").unwrap());
+    assert!(find_route(&strict, "GET", "/users/", false).is_err());
+    assert!(find_route(&strict, "GET", "/", false).is_err());
+
+    let redirect = router_with_mode(TrailingSlashMode::Redirect);
+    assert_eq!(
+        find_route(&redirect, "GET", "/users", false).unwrap().data,
+        "users"
+    );
+    assert_eq!(
+        find_route(&redirect, "GET", "/users/", false),
+        Err(RouterError::TrailingSlashRedirect {
+            canonical: "/users".to_string()
+        })
+    );
+    assert_eq!(
+        find_route(&redirect, "GET", "/users/42/", true),
+        Err(RouterError::TrailingSlashRedirect {
+            canonical: "/users/42".to_string()
+        })
+    );
+    assert!(matches!(
+        find_route(&redirect, "GET", "/missing/", false),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_head_derives_from_get() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "HEAD", "/status", "head_status").unwrap();
+    add_route(&router, "GET", "/status", "get_status").unwrap();
+
+    let matched = find_route(&router, "HEAD", "/users", false).unwrap();
+    assert_eq!(matched.data, "list_users");
+    assert!(matched.is_head_derived);
+
+    let matched = find_route(&router, "HEAD", "/users/7", true).unwrap();
+    assert_eq!(matched.data, "get_user");
+    assert!(matched.is_head_derived);
+    assert_eq!(matched.params.unwrap().get("id").unwrap(), "7");
+
+    let matched = find_route(&router, "HEAD", "/status", false).unwrap();
+    assert_eq!(matched.data, "head_status");
+    assert!(!matched.is_head_derived);
+
+    assert!(
+        !find_route(&router, "GET", "/users", false)
+            .unwrap()
+            .is_head_derived
+    );
+    assert!(find_route(&router, "POST", "/users", false).is_err());
+}
+
+#[test]
+fn test_get_options_response() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "POST", "/users", "create_user").unwrap();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "DELETE", "/users/:id", "delete_user").unwrap();
+    add_route(&router, "PUT", "/users/me", "update_me").unwrap();
+    add_route(&router, "POST", "/upload/**:path", "upload").unwrap();
+    add_route(&router, "", "/any", "any").unwrap();
+
+    let response = get_options_response(&router, "/users");
+    assert_eq!(
+        response.allowed_methods,
+        vec!["GET", "HEAD", "OPTIONS", "POST"]
+    );
+    assert_eq!(response.allow_header, "GET, HEAD, OPTIONS, POST");
+
+    let response = get_options_response(&router, "/users/me");
+    assert_eq!(
+        response.allow_header, "DELETE, GET, HEAD, OPTIONS, PUT",
+        "Static and parametric patterns matching the path are merged"
+    );
+
+    let response = get_options_response(&router, "/upload/a/b.txt");
+    assert_eq!(response.allowed_methods, vec!["OPTIONS", "POST"]);
+
+    disable_route(&router, "POST", "/users").unwrap();
+    assert_eq!(
+        get_options_response(&router, "/users").allow_header,
+        "GET, HEAD, OPTIONS"
+    );
+
+    assert_eq!(
+        get_options_response(&router, "/any"),
+        OptionsResponse::default()
+    );
+    let response = get_options_response(&router, "/missing");
+    assert!(response.allowed_methods.is_empty());
+    assert!(response.allow_header.is_empty());
+}
+
+#[test]
+fn test_add_route_with_middleware() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route_with_middleware(&router, "GET", "/admin/:page", "admin", &["auth", "audit"]).unwrap();
+    add_route_with_middleware(&router, "GET", "/health", "health", &["metrics"]).unwrap();
+    add_route(&router, "GET", "/public", "public").unwrap();
+
+    let matched = find_route(&router, "GET", "/admin/users", true).unwrap();
+    assert_eq!(matched.data, "admin");
+    assert_eq!(matched.middleware, vec!["auth", "audit"]);
+
+    let matched = find_route(&router, "GET", "/health", false).unwrap();
+    assert_eq!(matched.middleware, vec!["metrics"]);
+
+    assert!(
+        find_route(&router, "GET", "/public", false)
+            .unwrap()
+            .middleware
+            .is_empty()
+    );
+
+    let all = find_all_routes(&router, "GET", "/admin/settings", false);
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].middleware, vec!["auth", "audit"]);
+}
+
+#[test]
+fn test_add_route_with_scopes() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route_with_scopes(
+        &router,
+        "DELETE",
+        "/users/:id",
+        "delete_user",
+        &["users:write", "admin"],
+    )
+    .unwrap();
+    add_route_with_scopes(&router, "GET", "/me", "me", &[]).unwrap();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+
+    let matched = find_route(&router, "DELETE", "/users/1", false).unwrap();
+    assert_eq!(
+        matched.required_scopes,
+        Some(vec!["users:write".to_string(), "admin".to_string()])
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/me", false)
+            .unwrap()
+            .required_scopes,
+        Some(Vec::new()),
+        "An empty scope list is distinct from no requirement"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/users/1", false)
+            .unwrap()
+            .required_scopes,
+        None
+    );
+}
+
+#[test]
+fn test_add_route_with_timeout() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route_with_timeout(&router, "GET", "/reports/**:path", "reports", 10_000).unwrap();
+    add_route_with_timeout(&router, "GET", "/users/:id", "get_user", 500).unwrap();
+    add_route(&router, "GET", "/health", "health").unwrap();
+
+    assert_eq!(
+        find_route(&router, "GET", "/reports/2024/q1", false)
+            .unwrap()
+            .timeout_ms,
+        Some(10_000)
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/users/9", false)
+            .unwrap()
+            .timeout_ms,
+        Some(500)
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/health", false)
+            .unwrap()
+            .timeout_ms,
+        None
+    );
+}
+
+#[test]
+fn test_add_deprecated_route() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_deprecated_route(
+        &router,
+        "GET",
+        "/v1/users/:id",
+        "get_user_v1",
+        "2024-01-01",
+        Some("2025-01-01"),
+        Some("/v2/users/:id"),
+    )
+    .unwrap();
+    add_deprecated_route(&router, "GET", "/legacy", "legacy", "v3.2", None, None).unwrap();
+    add_route(&router, "GET", "/v2/users/:id", "get_user_v2").unwrap();
+
+    let matched = find_route(&router, "GET", "/v1/users/5", true).unwrap();
+    assert_eq!(
+        matched.deprecated,
+        Some(DeprecationInfo {
+            since: "2024-01-01".to_string(),
+            sunset: Some("2025-01-01".to_string()),
+            replacement: Some("/v2/users/:id".to_string()),
+        })
+    );
+
+    let legacy = find_route(&router, "GET", "/legacy", false)
+        .unwrap()
+        .deprecated
+        .unwrap();
+    assert_eq!(legacy.since, "v3.2");
+    assert!(legacy.sunset.is_none() && legacy.replacement.is_none());
+
+    assert!(
+        find_route(&router, "GET", "/v2/users/5", false)
+            .unwrap()
+            .deprecated
+            .is_none()
+    );
+}
+
+#[test]
+fn test_add_route_with_rate_limit() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route_with_rate_limit(&router, "POST", "/login", "login", 5, 10).unwrap();
+    add_route_with_rate_limit(&router, "GET", "/search/:query?", "search", 100, 0).unwrap();
+    add_route(&router, "GET", "/health", "health").unwrap();
+
+    assert_eq!(
+        find_route(&router, "POST", "/login", false)
+            .unwrap()
+            .rate_limit,
+        Some(RateLimit {
+            requests_per_second: 5,
+            burst: 10
+        })
+    );
+    let search_limit = find_route(&router, "GET", "/search", false)
+        .unwrap()
+        .rate_limit
+        .unwrap();
+    assert_eq!(search_limit.requests_per_second, 100);
+    assert_eq!(search_limit.burst, 0);
+    assert!(
+        find_route(&router, "GET", "/health", false)
+            .unwrap()
+            .rate_limit
+            .is_none()
+    );
+}
+
+#[test]
+fn test_add_upgrade_route() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_upgrade_route(
+        &router,
+        "/ws/:room",
+        "chat",
+        "websocket",
+        &["chat.v2", "chat.v1"],
+    )
+    .unwrap();
+    add_route(&router, "GET", "/rooms/:room", "room").unwrap();
+
+    let matched = find_route(&router, "GET", "/ws/lobby", true).unwrap();
+    assert_eq!(matched.data, "chat");
+    assert_eq!(
+        matched.upgrade,
+        Some(UpgradeInfo {
+            protocol: "websocket".to_string(),
+            subprotocols: vec!["chat.v2".to_string(), "chat.v1".to_string()],
+        })
+    );
+    assert!(find_route(&router, "POST", "/ws/lobby", false).is_err());
+    assert!(
+        find_route(&router, "GET", "/rooms/lobby", false)
+            .unwrap()
+            .upgrade
+            .is_none()
+    );
+}
+
+#[test]
+fn test_add_route_with_cors() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    let cors = CorsConfig {
+        allowed_origins: vec!["https://app.example.com".to_string()],
+        allow_credentials: true,
+        max_age_secs: Some(600),
+        extra_headers: vec!["X-Request-Id".to_string()],
+    };
+    add_route_with_cors(&router, "GET", "/api/users/:id", "get_user", cors.clone()).unwrap();
+    add_route(&router, "GET", "/health", "health").unwrap();
+
+    assert_eq!(
+        find_route(&router, "GET", "/api/users/1", false)
+            .unwrap()
+            .cors,
+        Some(cors)
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/health", false).unwrap().cors,
+        None
+    );
+}
+
+#[test]
+fn test_add_route_with_cache() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    let cache = CacheConfig {
+        max_age_secs: 3600,
+        is_public: true,
+        must_revalidate: true,
+        no_store: false,
+    };
+    add_route_with_cache(&router, "GET", "/assets/**:path", "assets", cache).unwrap();
+    add_route(&router, "GET", "/health", "health").unwrap();
+
+    assert_eq!(
+        find_route(&router, "GET", "/assets/app.js", false)
+            .unwrap()
+            .cache,
+        Some(cache)
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/health", false).unwrap().cache,
+        None
+    );
+}
+
+#[test]
+fn test_diff_routers() {
+    setup_tracing_for_tests();
+    let old = Router::new();
+    add_route(&old, "GET", "/users", "list_users").unwrap();
+    add_route(&old, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&old, "DELETE", "/users/:id", "delete_user").unwrap();
+
+    let new = Router::new();
+    add_route(&new, "GET", "/users", "list_users").unwrap();
+    add_route(&new, "GET", "/users/:id", "get_user_v2").unwrap();
+    add_route(&new, "POST", "/users", "create_user").unwrap();
+
+    let entry = |method: &str, pattern: &str, data| RouteEntry {
+        method: method.to_string(),
+        pattern: pattern.to_string(),
+        data,
+        description: None,
+    };
+    let diff = diff_routers(&old, &new);
+    assert_eq!(
+        diff.added,
+        vec![
+            entry("POST", "/users", "create_user"),
+            entry("GET", "/users/:id", "get_user_v2"),
+        ]
+    );
+    assert_eq!(
+        diff.removed,
+        vec![
+            entry("DELETE", "/users/:id", "delete_user"),
+            entry("GET", "/users/:id", "get_user"),
+        ]
+    );
+    assert_eq!(diff.unchanged, vec![entry("GET", "/users", "list_users")]);
+
+    let same = diff_routers(&old, &old);
+    assert!(same.added.is_empty() && same.removed.is_empty());
+    assert_eq!(same.unchanged.len(), 3);
+}
+
+#[test]
+fn test_snapshot_and_restore() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "GET", "/files/**:path", "files").unwrap();
+
+    let saved = snapshot(&router);
+    assert_eq!(saved.routes.len(), 3);
+
+    remove_route(&router, "GET", "/users/:id").unwrap();
+    add_route(&router, "POST", "/users", "create_user").unwrap();
+    assert!(find_route(&router, "GET", "/users/1", false).is_err());
+
+    let restored = restore(saved.clone()).unwrap();
+    assert_eq!(list_routes(&restored), saved.routes);
+    assert_eq!(
+        find_route(&restored, "GET", "/users/1", false)
+            .unwrap()
+            .data,
+        "get_user"
+    );
+    assert_eq!(
+        find_route(&restored, "GET", "/files/a/b", false)
+            .unwrap()
+            .data,
+        "files"
+    );
+    assert!(find_route(&restored, "POST", "/users", false).is_err());
+}
+
+#[test]
+fn test_matched_path() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "GET", "/about", "about").unwrap();
+    add_route(&router, "GET", "/", "root").unwrap();
+
+    let matched = find_route(&router, "GET", "/users/123/", true).unwrap();
+    assert_eq!(matched.matched_path, "users/123");
+    assert_eq!(
+        find_route(&router, "GET", "//about", false)
+            .unwrap()
+            .matched_path,
+        "about"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/", false).unwrap().matched_path,
+        ""
+    );
+    assert_eq!(
+        find_route_with_query(&router, "GET", "/users/7?tab=posts", false)
+            .unwrap()
+            .matched_path,
+        "users/7"
+    );
+    let all = find_all_routes(&router, "GET", "users/42", false);
+    assert_eq!(all[0].matched_path, "users/42");
+}
+
+#[test]
+fn test_route_specificity() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users/me", "me").unwrap();
+    add_route(&router, "GET", "/users/:id", "user").unwrap();
+    add_route(&router, "GET", "/users/*", "any_user").unwrap();
+    add_route(&router, "GET", "/users/**:rest", "rest").unwrap();
+    add_route(&router, "GET", "/search/:query?", "search").unwrap();
+
+    assert_eq!(
+        find_route(&router, "GET", "/users/me", false)
+            .unwrap()
+            .specificity,
+        6
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/users/7", false)
+            .unwrap()
+            .specificity,
+        5
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/users/a/b/c", false)
+            .unwrap()
+            .specificity,
+        4
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/search", false)
+            .unwrap()
+            .specificity,
+        5,
+        "An absent optional parameter still counts toward the pattern's score"
+    );
+
+    let mut all = find_all_routes(&router, "GET", "/users/me", false);
+    all.sort_by_key(|m| std::cmp::Reverse(m.specificity));
+    let ranked: Vec<_> = all.iter().map(|m| (m.data, m.specificity)).collect();
+    assert_eq!(ranked[0], ("me", 6));
+    assert_eq!(ranked.last().unwrap().1, 4);
+    assert!(ranked.contains(&("rest", 4)));
+}
+
+#[test]
+fn test_router_into_iterator() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "POST", "/users", "create_user").unwrap();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "GET", "/static/**:path", "static").unwrap();
+
+    let mut listed = Vec::new();
+    for route in &router {
+        listed.push(format!("{} {}", route.method, route.pattern));
+    }
+    assert_eq!(
+        listed,
+        vec![
+            "GET /static/**:path",
+            "GET /users",
+            "POST /users",
+            "GET /users/:id"
+        ]
+    );
+    assert_eq!(
+        (&router).into_iter().collect::<Vec<_>>(),
+        list_routes(&router)
+    );
+    assert_eq!((&Router::<&str>::new()).into_iter().count(), 0);
+}
+
+#[test]
+fn test_matched_route_pattern() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users/:id", "user").unwrap();
+    add_route(&router, "GET", "/users/me", "me").unwrap();
+    add_route(&router, "GET", "/files/*/raw", "raw").unwrap();
+    add_route(&router, "GET", "/assets/**:path", "assets").unwrap();
+    add_route(&router, "GET", "/search/:query?", "search").unwrap();
+
+    let pattern = |path: &str| {
+        find_route(&router, "GET", path, false)
+            .unwrap()
+            .route_pattern
+    };
+    assert_eq!(pattern("/users/42"), "/users/:id");
+    assert_eq!(pattern("/users/me"), "/users/me");
+    assert_eq!(pattern("/files/x/raw"), "/files/*/raw");
+    assert_eq!(pattern("/assets/css/site.css"), "/assets/**:path");
+    assert_eq!(pattern("/assets"), "/assets/**:path");
+    assert_eq!(pattern("/search"), "/search/:query?");
+    assert_eq!(pattern("/search/rust"), "/search/:query?");
+
+    let case_insensitive = Router::with_config(RouterConfig::builder().case_insensitive().build());
+    add_route(&case_insensitive, "GET", "/Users/:id", "user").unwrap();
+    assert_eq!(
+        find_route(&case_insensitive, "GET", "/USERS/7", false)
+            .unwrap()
+            .route_pattern,
+        "/users/:id"
+    );
+}
+
+#[test]
+fn test_find_route_segments() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "GET", "/users/me", "me").unwrap();
+    add_route(&router, "GET", "/files/**:path", "files").unwrap();
+    add_route(&router, "GET", "/", "root").unwrap();
+
+    let matched = find_route_segments(&router, "GET", &["users", "42"], "/users/42", true).unwrap();
+    assert_eq!(
+        matched,
+        find_route(&router, "GET", "/users/42", true).unwrap()
+    );
+    assert_eq!(matched.params.unwrap().get("id").unwrap(), "42");
+
+    assert_eq!(
+        find_route_segments(&router, "GET", &["users", "me"], "/users/me", false)
+            .unwrap()
+            .data,
+        "me"
+    );
+    let matched = find_route_segments(
+        &router,
+        "GET",
+        &["files", "a", "b.txt"],
+        "/files/a/b.txt",
+        true,
+    )
+    .unwrap();
+    assert_eq!(matched.params.unwrap().get("path").unwrap(), "a/b.txt");
+    assert_eq!(
+        find_route_segments(&router, "GET", &[], "/", false)
+            .unwrap()
+            .data,
+        "root"
+    );
+    assert_eq!(
+        find_route_segments(&router, "GET", &["teams", "42"], "/teams/42?x=1", false),
+        Err(RouterError::RouteNotFound {
+            method: "GET".to_string(),
+            path: "/teams/42?x=1".to_string()
+        })
+    );
+
+    let case_insensitive = Router::with_config(
+        RouterConfig::builder()
+            .case_insensitive()
+            .max_depth(2)
+            .build(),
+    );
+    add_route(&case_insensitive, "GET", "/Users/:id", "get_user").unwrap();
+    let matched = find_route_segments(
+        &case_insensitive,
+        "GET",
+        &["USERS", "Ab"],
+        "/USERS/Ab",
+        true,
+    )
+    .unwrap();
+    assert_eq!(matched.params.unwrap().get("id").unwrap(), "Ab");
+    assert!(matches!(
+        find_route_segments(&case_insensitive, "GET", &["a", "b", "c"], "/a/b/c", false),
+        Err(RouterError::InvalidPath(_))
+    ));
+}
+
+#[test]
+fn test_find_route_borrowed() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "GET", "/files/**:path", "files").unwrap();
+
+    let segments = ["users", "42"];
+    let matched = find_route_borrowed(&router, "GET", &segments, "users/42", true).unwrap();
+    assert_eq!(matched.data, "get_user");
+    let params = matched.params.unwrap();
+    assert!(matches!(&params["id"], Cow::Borrowed(value) if std::ptr::eq(*value, segments[1])));
+
+    let segments = ["files", "a", "b.txt"];
+    let matched = find_route_borrowed(&router, "GET", &segments, "files/a/b.txt", true).unwrap();
+    assert!(matches!(&matched.params.unwrap()["path"], Cow::Owned(value) if value == "a/b.txt"));
+
+    let segments = ["users", "42"];
+    assert_eq!(
+        find_route_borrowed(&router, "GET", &segments, "users/42", false),
+        Ok(BorrowedMatchedRoute {
+            data: "get_user",
+            params: None
+        })
+    );
+    let segments = ["teams", "42"];
+    assert_eq!(
+        find_route_borrowed(&router, "GET", &segments, "teams/42", true),
+        Err(RouterError::RouteNotFound {
+            method: "GET".to_string(),
+            path: "teams/42".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_find_route_segments_checks_guards_and_allowlists() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route_with_guard(&router, "GET", "/admin/:page", "admin", |_, path| {
+        path == "admin/stats"
+    })
+    .unwrap();
+    add_route_with_allowlist(
+        &router,
+        "GET",
+        "/reports/:kind",
+        "report",
+        &[("kind", &["daily", "weekly"])],
+    )
+    .unwrap();
+
+    let matched =
+        find_route_segments(&router, "GET", &["admin", "stats"], "/admin/stats", false).unwrap();
+    assert_eq!(matched.data, "admin");
+    assert_eq!(matched.matched_path, "admin/stats");
+    assert!(
+        find_route_segments(&router, "GET", &["admin", "users"], "/admin/users", false).is_err()
+    );
+
+    assert_eq!(
+        find_route_segments(
+            &router,
+            "GET",
+            &["reports", "daily"],
+            "/reports/daily",
+            true
+        )
+        .unwrap()
+        .data,
+        "report"
+    );
+    assert!(
+        find_route_segments(
+            &router,
+            "GET",
+            &["reports", "yearly"],
+            "/reports/yearly",
+            true
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_find_route_segments_method_not_allowed() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "DELETE", "/users/:id", "delete_user").unwrap();
+
+    assert_eq!(
+        find_route_segments(&router, "POST", &["users"], "/users", false),
+        find_route(&router, "POST", "/users", false)
+    );
+    assert_eq!(
+        find_route_segments(&router, "POST", &["users"], "/users", false),
+        Err(RouterError::MethodNotAllowed {
+            method: "POST".to_string(),
+            path: "/users".to_string(),
+            allowed: vec!["GET".to_string(), "HEAD".to_string()],
+        })
+    );
+    assert_eq!(
+        find_route_segments(&router, "GET", &["users", "7"], "/users/7", false),
+        Err(RouterError::MethodNotAllowed {
+            method: "GET".to_string(),
+            path: "/users/7".to_string(),
+            allowed: vec!["DELETE".to_string()],
+        })
+    );
+}
+
+#[test]
+fn test_find_route_borrowed_method_not_allowed() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "DELETE", "/users/:id", "delete_user").unwrap();
+
+    let segments = ["users"];
+    assert_eq!(
+        find_route_borrowed(&router, "POST", &segments, "/users", false),
+        Err(RouterError::MethodNotAllowed {
+            method: "POST".to_string(),
+            path: "/users".to_string(),
+            allowed: vec!["GET".to_string(), "HEAD".to_string()],
+        })
+    );
+    let segments = ["users", "7"];
+    assert_eq!(
+        find_route_borrowed(&router, "PUT", &segments, "/users/7", true),
+        Err(RouterError::MethodNotAllowed {
+            method: "PUT".to_string(),
+            path: "/users/7".to_string(),
+            allowed: vec!["DELETE".to_string()],
+        })
+    );
+}
+
+#[test]
+fn test_find_route_chain() {
+    setup_tracing_for_tests();
+    let plugin = Router::new();
+    add_route(&plugin, "GET", "/users/:id", "plugin_user").unwrap();
+    let fallback = Router::new();
+    add_route(&fallback, "GET", "/users/:id", "fallback_user").unwrap();
+    add_route(&fallback, "GET", "/**", "not_found_page").unwrap();
+
+    let routers = [&plugin, &fallback];
+    assert_eq!(
+        find_route_chain(&routers, "GET", "/users/7", false)
+            .unwrap()
+            .data,
+        "plugin_user"
+    );
+    assert_eq!(
+        find_route_chain(&routers, "GET", "/about", false)
+            .unwrap()
+            .data,
+        "not_found_page"
+    );
+    assert_eq!(
+        find_route_chain(&routers, "POST", "/about", false),
+        Err(RouterError::MethodNotAllowed {
+            method: "POST".to_string(),
+            path: "/about".to_string(),
+            allowed: vec!["GET".to_string(), "HEAD".to_string()],
+        })
+    );
+    assert!(find_route_chain::<&str>(&[], "GET", "/", false).is_err());
+}
+
+#[test]
+fn test_with_max_depth_rejects_deep_paths() {
+    setup_tracing_for_tests();
+    let router = Router::with_max_depth(3);
+    add_route(&router, "GET", "/a/b/:c", "abc").unwrap();
+    add_route(&router, "GET", "/files/**:path", "files").unwrap();
+    assert_eq!(
+        add_route(&router, "GET", "/a/b/c/d", "abcd"),
+        Err(RouterError::InvalidPath(
+            "exceeds maximum path depth".to_string()
+        ))
+    );
+
+    assert_eq!(
+        find_route(&router, "GET", "/a/b/c", false).unwrap().data,
+        "abc"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/files/x/y", false)
+            .unwrap()
+            .data,
+        "files"
+    );
+    let deep_path = "/files".to_string() + &"/x".repeat(10_000);
+    assert_eq!(
+        find_route(&router, "GET", &deep_path, false),
+        Err(RouterError::InvalidPath(
+            "exceeds maximum path depth".to_string()
+        ))
+    );
+    assert!(find_all_routes(&router, "GET", &deep_path, false).is_empty());
+}
+
+#[test]
+fn test_add_route_multi() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    assert_eq!(
+        add_route_multi(
+            &router,
+            "GET",
+            &["/profile", "/me", "/users/:id/profile"],
+            "profile"
+        ),
+        Ok(3)
+    );
+    for path in ["/profile", "/me", "/users/7/profile"] {
+        assert_eq!(
+            find_route(&router, "GET", path, false).unwrap().data,
+            "profile"
+        );
+    }
+
+    let result = add_route_multi(
+        &router,
+        "GET",
+        &["/a/**/x", "/alias", "/b/**/y", "/other-alias"],
+        "alias",
+    );
+    assert_eq!(
+        result,
+        Err(RouterError::PartialSuccess {
+            count: 2,
+            last_error: Box::new(RouterError::InvalidSegment {
+                segment: "**".to_string(),
+                reason: "wildcard (**) must be the last segment".to_string(),
+            }),
+        })
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/alias", false).unwrap().data,
+        "alias"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/other-alias", false)
+            .unwrap()
+            .data,
+        "alias"
+    );
+}
+
+#[test]
+fn test_route_macro() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    route!(router, GET "/users/:id" => "get_user");
+    route!(router, GET "/posts" => "list_posts", POST "/posts" => "create_post",);
+
+    assert_eq!(
+        find_route(&router, "GET", "/users/1", false).unwrap().data,
+        "get_user"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/posts", false).unwrap().data,
+        "list_posts"
+    );
+    assert_eq!(
+        find_route(&router, "POST", "/posts", false).unwrap().data,
+        "create_post"
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid route GET /files/**/x")]
+fn test_route_macro_panics_on_invalid_pattern() {
+    let router = Router::new();
+    route!(router, GET "/files/**/x" => "files");
+}
+
+#[test]
+fn test_route_macro_propagates_errors() {
+    setup_tracing_for_tests();
+    fn register(router: &Router<&'static str>, bad: bool) -> Result<(), RouterError> {
+        route!(?router, GET "/ok" => "ok");
+        if bad {
+            route!(?router, GET "/files/**/x" => "files", GET "/never" => "never");
+        }
+        Ok(())
+    }
+
+    let router = Router::new();
+    assert_eq!(register(&router, false), Ok(()));
+    assert!(matches!(
+        register(&router, true),
+        Err(RouterError::InvalidSegment { .. })
+    ));
+    assert!(find_route(&router, "GET", "/never", false).is_err());
+}
+
+#[test]
+fn test_route_group_macro() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    route_group!(router, "/api/v1/", {
+        GET "/users" => "list_users",
+        POST "users" => "create_user",
+        GET "/users/:id" => "get_user",
+    });
+    assert_eq!(RouteGroup::new(&router, "api/v1/").prefix(), "/api/v1");
+    assert_eq!(
+        find_route(&router, "GET", "/api/v1/users", false)
+            .unwrap()
+            .data,
+        "list_users"
+    );
+    assert_eq!(
+        find_route(&router, "POST", "/api/v1/users", false)
+            .unwrap()
+            .data,
+        "create_user"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/api/v1/users/9", false)
+            .unwrap()
+            .data,
+        "get_user"
+    );
+    assert!(find_route(&router, "GET", "/users", false).is_err());
+
+    let register = || -> Result<(), RouterError> {
+        route_group!(?router, "/", { GET "/health" => "health" });
+        Ok(())
+    };
+    register().unwrap();
+    assert_eq!(
+        find_route(&router, "GET", "/health", false).unwrap().data,
+        "health"
+    );
+}
+
+#[test]
+fn test_versioned_routes() {
+    setup_tracing_for_tests();
+    let router = Router::with_global_prefix("/api");
+    add_versioned_routes(&router, 2, |v2| {
+        v2.add("GET", "/users", "list_users_v2")?;
+        v2.add("GET", "/users/:id", "get_user_v2")
+    })
+    .unwrap();
+    add_versioned_routes(&router, 10, |v10| {
+        v10.add("GET", "/users", "list_users_v10")
+    })
+    .unwrap();
+    add_route(&router, "GET", "/version", "version").unwrap();
+    add_route(&router, "GET", "/vnext/users", "preview").unwrap();
+
+    assert_eq!(
+        find_route(&router, "GET", "/api/v2/users/5", false)
+            .unwrap()
+            .data,
+        "get_user_v2"
+    );
+    assert_eq!(list_api_versions(&router), [2, 10]);
+
+    assert!(matches!(
+        add_versioned_routes(&router, 3, |v3| v3.add("GET", "/files/**/x", "bad")),
+        Err(RouterError::InvalidSegment { .. })
+    ));
+    assert_eq!(list_api_versions(&router), [2, 10]);
+}
+
+#[test]
+fn test_find_routes_batch() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "POST", "/users", "create_user").unwrap();
+
+    let requests = [
+        ("GET", "/users"),
+        ("GET", "/users/7"),
+        ("DELETE", "/users/7"),
+        ("POST", "/users/"),
+        ("HEAD", "/users/8"),
+    ];
+    let results = find_routes_batch(&router, &requests, true);
+    assert_eq!(results.len(), requests.len());
+    for ((method, path), result) in requests.iter().zip(&results) {
+        assert_eq!(result, &find_route(&router, method, path, true));
+    }
+    assert_eq!(
+        results[1].as_ref().unwrap().params.as_ref().unwrap()["id"],
+        "7"
+    );
+    assert!(matches!(
+        results[2],
+        Err(RouterError::MethodNotAllowed { .. })
+    ));
+    assert!(results[4].as_ref().unwrap().is_head_derived);
+
+    assert!(find_routes_batch(&router, &[], false).is_empty());
+}
+
+#[test]
+fn test_normalized_path_and_find_route_normalized() {
+    setup_tracing_for_tests();
+    let path: NormalizedPath = "//users//42/".parse().unwrap();
+    assert_eq!(&*path, "users/42");
+    assert_eq!(path.as_ref(), "users/42");
+    assert_eq!(path.to_string(), "users/42");
+    assert_eq!(path.split('/').count(), 2, "Deref exposes str methods");
+
+    let router = Router::new();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "GET", "/about", "about").unwrap();
+
+    let matched = find_route_normalized(&router, "GET", &path, true).unwrap();
+    assert_eq!(
+        matched,
+        find_route(&router, "GET", "/users/42", true).unwrap()
+    );
+    assert_eq!(
+        find_route_normalized(&router, "GET", &NormalizedPath::new("about"), false)
+            .unwrap()
+            .data,
+        "about"
+    );
+    assert_eq!(
+        find_route_normalized(&router, "POST", &path, false),
+        Err(RouterError::MethodNotAllowed {
+            method: "POST".to_string(),
+            path: "users/42".to_string(),
+            allowed: vec!["GET".to_string(), "HEAD".to_string()],
+        })
+    );
+
+    let decoding = Router::with_config(RouterConfig::builder().percent_decode().build());
+    add_route(&decoding, "GET", "/files/:name", "file").unwrap();
+    let matched =
+        find_route_normalized(&decoding, "GET", &NormalizedPath::new("files/a%20b"), true).unwrap();
+    assert_eq!(matched.params.unwrap()["name"], "a b");
+}
+
+#[test]
+fn test_memory_usage() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    let empty = memory_usage(&router);
+    assert_eq!(empty.trie_nodes, 1);
+    assert_eq!(empty.static_entries, 0);
+    assert_eq!(empty.total_method_handlers, 0);
+
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "POST", "/users", "create_user").unwrap();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "GET", "/files/**:path", "files").unwrap();
+    let stats = memory_usage(&router);
+    // root, users, users/:id, files, files/**
+    assert_eq!(stats.trie_nodes, 5);
+    assert_eq!(stats.static_entries, 1);
+    assert_eq!(stats.total_method_handlers, 4);
+    assert!(stats.estimated_heap_bytes > empty.estimated_heap_bytes);
+
+    for i in 0..100 {
+        add_route(&router, "GET", &format!("/bulk/{i}"), "bulk").unwrap();
+    }
+    let grown = memory_usage(&router);
+    assert_eq!(grown.trie_nodes, 106);
+    assert_eq!(grown.static_entries, 101);
+    assert!(grown.estimated_heap_bytes > stats.estimated_heap_bytes);
+}
+
+#[test]
+fn test_shrink_to_fit_after_removal() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/core", "core").unwrap();
+    add_route(&router, "GET", "/core/:id", "core_item").unwrap();
+    for i in 0..200 {
+        add_route(&router, "GET", &format!("/plugin/{i}"), "plugin").unwrap();
+        add_route(
+            &router,
+            "POST",
+            &format!("/plugin/{i}/:action"),
+            "plugin_action",
+        )
+        .unwrap();
+    }
+    for i in 0..200 {
+        remove_route(&router, "GET", &format!("/plugin/{i}")).unwrap();
+        remove_route(&router, "POST", &format!("/plugin/{i}/:action")).unwrap();
+    }
+    let before = memory_usage(&router);
+    shrink_to_fit(&router);
+    let after = memory_usage(&router);
+
+    assert_eq!(after.trie_nodes, before.trie_nodes);
+    assert_eq!(after.static_entries, before.static_entries);
+    assert_eq!(after.total_method_handlers, before.total_method_handlers);
+    assert!(after.estimated_heap_bytes < before.estimated_heap_bytes);
+
+    assert_eq!(
+        find_route(&router, "GET", "/core", false).unwrap().data,
+        "core"
+    );
+    let matched = find_route(&router, "GET", "/core/42", true).unwrap();
+    assert_eq!(matched.data, "core_item");
+    assert_eq!(
+        matched.params.unwrap().get("id").map(String::as_str),
+        Some("42")
+    );
+    assert!(find_route(&router, "GET", "/plugin/1", false).is_err());
+}
+
+#[test]
+fn test_compute_trie_stats() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    assert_eq!(compute_trie_stats(&router).total_nodes, 1);
+    assert_eq!(compute_trie_stats(&router).avg_branching_factor, 0.0);
+
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "POST", "/users", "create_user").unwrap();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "GET", "/users/:id/posts/:post", "get_post").unwrap();
+    add_route(&router, "", "/files/**:path", "files").unwrap();
+
+    let stats = compute_trie_stats(&router);
+    // root -> users -> :id -> posts -> :post, root -> files -> **
+    assert_eq!(stats.total_nodes, 7);
+    assert_eq!(stats.max_depth, 4);
+    assert_eq!(stats.leaf_count, 2);
+    assert_eq!(stats.static_node_count, 3);
+    assert_eq!(stats.param_node_count, 2);
+    assert_eq!(stats.wildcard_node_count, 1);
+    // Six edges spread over the root (2 children) and four single-child nodes.
+    assert_eq!(stats.avg_branching_factor, 6.0 / 5.0);
+    assert_eq!(stats.handlers_per_method.get("GET"), Some(&3));
+    assert_eq!(stats.handlers_per_method.get("POST"), Some(&1));
+    assert_eq!(stats.handlers_per_method.get(""), Some(&1));
+}
+
+#[test]
+fn test_iter_nodes() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "GET", "/files/**:path", "files").unwrap();
+    add_route(&router, "GET", "/about", "about").unwrap();
+
+    let root = router.root.read();
+    let nodes: Vec<(String, usize, usize)> = iter_nodes(&root)
+        .map(|node_ref| {
+            (
+                node_ref.path_prefix,
+                node_ref.depth,
+                node_ref.node.methods.len(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        nodes,
+        [
+            (String::new(), 0, 0),
+            ("/about".to_string(), 1, 1),
+            ("/files".to_string(), 1, 0),
+            ("/files/**".to_string(), 2, 1),
+            ("/users".to_string(), 1, 0),
+            ("/users/:*".to_string(), 2, 1),
+        ]
+    );
+}
+
+#[test]
+fn test_export_mermaid() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/", "index").unwrap();
+    add_route(&router, "GET", "/health", "health").unwrap();
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "POST", "/users", "create_user").unwrap();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "PUT", "/users/:id", "update_user").unwrap();
+    add_route(&router, "DELETE", "/users/:id", "delete_user").unwrap();
+    add_route(&router, "GET", "/static/**:path", "static_files").unwrap();
+
+    let diagram = export_mermaid(&router);
+    assert!(diagram.starts_with("graph TD\n"));
+    assert!(diagram.contains(r#"N0["/<br/>GET: #quot;index#quot;"]"#));
+    assert!(diagram.contains(r#"["/health<br/>GET: #quot;health#quot;"]"#));
+    assert!(diagram.contains(
+        r#"["/users<br/>GET: #quot;list_users#quot;<br/>POST: #quot;create_user#quot;"]"#
+    ));
+    assert!(diagram.contains(r#"<br/>DELETE: #quot;delete_user#quot;<br/>GET: #quot;get_user#quot;<br/>PUT: #quot;update_user#quot;"]"#));
+    assert!(diagram.contains(r#"["/**<br/>GET: #quot;static_files#quot;"]"#));
+    assert!(diagram.contains(" --> "));
+    assert!(diagram.contains(" -.-> "));
+    assert!(diagram.contains(" -. ** .-> "));
+}
+
+#[test]
+fn test_compress_preserves_matching() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/a/b/c/d/e/f/:id", "deep").unwrap();
+    add_route(&router, "GET", "/a/b/c/d/x/:id", "branch").unwrap();
+    add_route(&router, "GET", "/api/v1/users/:id?", "user").unwrap();
+    add_route(&router, "GET", "/files/public/**:path", "files").unwrap();
+    let routes_before = list_routes(&router);
+    let nodes_before = memory_usage(&router).trie_nodes;
+
+    compress(&router);
+
+    assert!(memory_usage(&router).trie_nodes < nodes_before);
+    assert_eq!(list_routes(&router), routes_before);
+    let matched = find_route(&router, "GET", "/a/b/c/d/e/f/7", true).unwrap();
+    assert_eq!(matched.data, "deep");
+    assert_eq!(
+        matched.params.unwrap().get("id").map(String::as_str),
+        Some("7")
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/a/b/c/d/x/1", false)
+            .unwrap()
+            .data,
+        "branch"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/api/v1/users", false)
+            .unwrap()
+            .data,
+        "user"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/files/public/css/site.css", false)
+            .unwrap()
+            .data,
+        "files"
+    );
+    assert!(find_route(&router, "GET", "/a/b/c/d/e/7", false).is_err());
+    assert!(find_route(&router, "GET", "/api/v2/users/1", false).is_err());
+    assert_eq!(
+        find_all_routes(&router, "GET", "/a/b/c/d/e/f/7", false).len(),
+        1
+    );
+}
+
+#[test]
+fn test_compact_compresses_static_chains() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(
+        &router,
+        "GET",
+        "/api/v1/users/settings/notifications/:id",
+        "notifications",
+    )
+    .unwrap();
+    add_route(
+        &router,
+        "GET",
+        "/api/v1/users/settings/privacy/:id",
+        "privacy",
+    )
+    .unwrap();
+    let routes_before = list_routes(&router);
+    let nodes_before = memory_usage(&router).trie_nodes;
+
+    compact(&router);
+
+    assert!(memory_usage(&router).trie_nodes < nodes_before);
+    assert_eq!(list_routes(&router), routes_before);
+    assert_eq!(
+        find_route(
+            &router,
+            "GET",
+            "/api/v1/users/settings/notifications/3",
+            false
+        )
+        .unwrap()
+        .data,
+        "notifications"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/api/v1/users/settings/privacy/3", false)
+            .unwrap()
+            .data,
+        "privacy"
+    );
+}
+
+#[test]
+fn test_add_and_remove_after_compress_split_edge_labels() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/api/v1/users/:id", "user").unwrap();
+    compress(&router);
+
+    // Diverges in the middle of the compressed `v1/users` label.
+    add_route(&router, "GET", "/api/v1/posts/:id", "post").unwrap();
+    // Ends in the middle of the label.
+    add_route(&router, "GET", "/api/v1/:version", "version").unwrap();
+
+    assert_eq!(
+        find_route(&router, "GET", "/api/v1/users/1", false)
+            .unwrap()
+            .data,
+        "user"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/api/v1/posts/2", false)
+            .unwrap()
+            .data,
+        "post"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/api/v1/v3", false)
+            .unwrap()
+            .data,
+        "version"
+    );
+
+    assert!(remove_route(&router, "GET", "/api/v1/users/:id").unwrap());
+    assert!(find_route(&router, "GET", "/api/v1/users/1", false).is_err());
+    assert_eq!(
+        find_route(&router, "GET", "/api/v1/posts/2", false)
+            .unwrap()
+            .data,
+        "post"
+    );
+    assert_eq!(list_routes(&router).len(), 2);
+}
+
+#[test]
+fn test_find_route_with_mode() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users/me", "me").unwrap();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "GET", "/users/**:rest", "users_fallback").unwrap();
+
+    match find_route_with_mode(&router, "GET", "/users/me", false, MatchMode::First).unwrap() {
+        MatchResult::Single(matched) => assert_eq!(matched.data, "me"),
+        other => panic!("expected a single match, got {other:?}"),
+    }
+
+    match find_route_with_mode(&router, "GET", "/users/42", true, MatchMode::Best).unwrap() {
+        MatchResult::Single(matched) => {
+            assert_eq!(matched.data, "get_user");
+            assert_eq!(
+                matched.params.unwrap().get("id").map(String::as_str),
+                Some("42")
+            );
+        }
+        other => panic!("expected a single match, got {other:?}"),
+    }
+
+    match find_route_with_mode(&router, "GET", "/users/me", false, MatchMode::All).unwrap() {
+        MatchResult::Multiple(matches) => {
+            let mut data: Vec<_> = matches.iter().map(|m| m.data).collect();
+            data.sort_unstable();
+            assert_eq!(data, vec!["get_user", "me", "users_fallback"]);
+        }
+        other => panic!("expected multiple matches, got {other:?}"),
+    }
+
+    for mode in [MatchMode::First, MatchMode::Best, MatchMode::All] {
+        assert!(matches!(
+            find_route_with_mode(&router, "GET", "/posts", false, mode),
+            Err(RouterError::RouteNotFound { .. })
+        ));
+        assert!(matches!(
+            find_route_with_mode(&router, "POST", "/users/me", false, mode),
+            Err(RouterError::MethodNotAllowed { allowed, .. }) if allowed == ["GET", "HEAD"]
+        ));
+    }
+}
+
+#[test]
+fn test_add_route_with_guard() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    let beta_enabled = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&beta_enabled);
+    add_route_with_guard(
+        &router,
+        "GET",
+        "/dashboard",
+        "beta_dashboard",
+        move |_, _| flag.load(Ordering::Relaxed),
+    )
+    .unwrap();
+    add_route(&router, "GET", "/dashboard", "dashboard").unwrap();
+    add_route(&router, "GET", "/:page", "page").unwrap();
+
+    // The guard rejects, so the next handler for the same pattern matches.
+    assert_eq!(
+        find_route(&router, "GET", "/dashboard", false)
+            .unwrap()
+            .data,
+        "dashboard"
+    );
+    beta_enabled.store(true, Ordering::Relaxed);
+    assert_eq!(
+        find_route(&router, "GET", "/dashboard", false)
+            .unwrap()
+            .data,
+        "beta_dashboard"
+    );
+
+    // A rejected guard falls through to parametric routes.
+    add_route_with_guard(&router, "POST", "/reports/:id", "report", |method, path| {
+        method == "POST" && !path.ends_with("/0")
+    })
+    .unwrap();
+    add_route(&router, "POST", "/reports/**:rest", "reports_fallback").unwrap();
+    let matched = find_route(&router, "POST", "/reports/7", true).unwrap();
+    assert_eq!(matched.data, "report");
+    assert_eq!(
+        find_route(&router, "POST", "/reports/0", false)
+            .unwrap()
+            .data,
+        "reports_fallback"
+    );
+
+    add_route_with_guard(&router, "GET", "/never", "never", |_, _| false).unwrap();
+    assert!(matches!(
+        find_route(&router, "DELETE", "/never", false),
+        Err(RouterError::MethodNotAllowed { .. })
+    ));
+    assert_eq!(
+        find_route(&router, "GET", "/never", false).unwrap().data,
+        "page"
+    );
+    let all: Vec<_> = find_all_routes(&router, "GET", "/never", false)
+        .into_iter()
+        .map(|m| m.data)
+        .collect();
+    assert_eq!(all, vec!["page"]);
+}
+
+#[test]
+fn test_exact_router() {
+    setup_tracing_for_tests();
+    let router = ExactRouter::new();
+    add_static(&router, "GET", "/health", "health").unwrap();
+    add_static(&router, "POST", "/users/", "create_user").unwrap();
+    add_static(&router, "", "/ping", "ping").unwrap();
+
+    let matched = find_static(&router, "GET", "/health").unwrap();
+    assert_eq!(matched.data, "health");
+    assert!(matched.params.is_none());
+    assert_eq!(
+        find_static(&router, "POST", "/users").unwrap().data,
+        "create_user"
+    );
+    assert_eq!(
+        find_static(&router, "DELETE", "/ping").unwrap().data,
+        "ping"
+    );
+    let head = find_static(&router, "HEAD", "/health").unwrap();
+    assert!(head.is_head_derived);
+
+    assert_eq!(
+        find_static(&router, "GET", "/users"),
+        Err(RouterError::MethodNotAllowed {
+            method: "GET".to_string(),
+            path: "/users".to_string(),
+            allowed: vec!["POST".to_string()],
+        })
+    );
+    assert!(matches!(
+        find_static(&router, "GET", "/missing"),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+    assert!(matches!(
+        add_static(&router, "GET", "/users/:id", "get_user"),
+        Err(RouterError::InvalidPath(_))
+    ));
+    assert!(matches!(
+        add_static(&router, "GET", "/files/**", "files"),
+        Err(RouterError::InvalidPath(_))
+    ));
+
+    let insensitive = ExactRouter::with_config(RouterConfig::builder().case_insensitive().build());
+    add_static(&insensitive, "GET", "/About", "about").unwrap();
+    assert_eq!(
+        find_static(&insensitive, "GET", "/ABOUT").unwrap().data,
+        "about"
+    );
+}
+
+#[test]
+fn test_add_route_with_transforms() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route_with_transforms(
+        &router,
+        "GET",
+        "/users/:name",
+        "get_user",
+        "name",
+        |value| value.to_lowercase(),
+    )
+    .unwrap();
+    add_route_with_transforms(
+        &router,
+        "GET",
+        "/v/:version/**:rest",
+        "versioned",
+        "version",
+        |value| match value {
+            "latest" => "3".to_string(),
+            other => other.trim_start_matches('v').to_string(),
+        },
+    )
+    .unwrap();
+    add_route(&router, "GET", "/plain/:name", "plain").unwrap();
+
+    let params = convert_params_to_hashmap(
+        find_route(&router, "GET", "/users/Alice", true)
+            .unwrap()
+            .params,
+    );
+    assert_eq!(
+        params.unwrap().get("name").map(String::as_str),
+        Some("alice")
+    );
+
+    let params = convert_params_to_hashmap(
+        find_route(&router, "GET", "/v/latest/a/B", true)
+            .unwrap()
+            .params,
+    )
+    .unwrap();
+    assert_eq!(params.get("version").map(String::as_str), Some("3"));
+    assert_eq!(params.get("rest").map(String::as_str), Some("a/B"));
+    let params =
+        convert_params_to_hashmap(find_route(&router, "GET", "/v/v2/x", true).unwrap().params);
+    assert_eq!(
+        params.unwrap().get("version").map(String::as_str),
+        Some("2")
+    );
+
+    let params = convert_params_to_hashmap(
+        find_route(&router, "GET", "/plain/Alice", true)
+            .unwrap()
+            .params,
+    );
+    assert_eq!(
+        params.unwrap().get("name").map(String::as_str),
+        Some("Alice")
+    );
+
+    let all = find_all_routes(&router, "GET", "/users/BOB", true);
+    assert_eq!(all.len(), 1);
+    assert_eq!(
+        all[0]
+            .params
+            .as_ref()
+            .unwrap()
+            .get("name")
+            .map(String::as_str),
+        Some("bob")
+    );
+    assert!(
+        find_route(&router, "GET", "/users/Alice", false)
+            .unwrap()
+            .params
+            .is_none()
+    );
+}
+
+#[test]
+fn test_add_route_with_defaults() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route_with_defaults(
+        &router,
+        "GET",
+        "/search/:query?",
+        "search",
+        &[("query", "latest"), ("page", "1")],
+    )
+    .unwrap();
+    add_route(&router, "GET", "/tags/:tag?", "tags").unwrap();
+
+    let params =
+        convert_params_to_hashmap(find_route(&router, "GET", "/search", true).unwrap().params)
+            .unwrap();
+    assert_eq!(params.len(), 1);
+    assert_eq!(params.get("query").map(String::as_str), Some("latest"));
+
+    let params = convert_params_to_hashmap(
+        find_route(&router, "GET", "/search/rust", true)
+            .unwrap()
+            .params,
+    );
+    assert_eq!(
+        params.unwrap().get("query").map(String::as_str),
+        Some("rust")
+    );
+
+    assert!(
+        find_route(&router, "GET", "/search", false)
+            .unwrap()
+            .params
+            .is_none()
+    );
+    assert!(
+        find_route(&router, "GET", "/tags", true)
+            .unwrap()
+            .params
+            .is_none()
+    );
+}
+
+#[test]
+fn test_router_interface_trait_object() {
+    setup_tracing_for_tests();
+
+    fn lookup(router: &dyn RouterInterface<&'static str>, path: &str) -> Option<&'static str> {
+        router
+            .find("GET", path, false)
+            .ok()
+            .map(|matched| matched.data)
+    }
+
+    /// Delegates to a real router and records every lookup it receives.
+    struct RecordingRouter {
+        inner: Router<&'static str>,
+        lookups: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl RouterInterface<&'static str> for RecordingRouter {
+        fn add(&self, method: &str, path: &str, data: &'static str) -> Result<(), RouterError> {
+            RouterInterface::add(&self.inner, method, path, data)
+        }
+
+        fn find(
+            &self,
+            method: &str,
+            path: &str,
+            capture: bool,
+        ) -> Result<MatchedRoute<&'static str>, RouterError> {
+            self.lookups
+                .lock()
+                .unwrap()
+                .push(format!("{method} {path}"));
+            self.inner.find(method, path, capture)
+        }
+
+        fn remove(&self, method: &str, path: &str) -> Result<bool, RouterError> {
+            self.inner.remove(method, path)
+        }
+    }
+
+    let router = Router::new();
+    let interface: &dyn RouterInterface<&'static str> = &router;
+    interface.add("GET", "/users/:id", "get_user").unwrap();
+    assert_eq!(lookup(&router, "/users/1"), Some("get_user"));
+    let matched = interface.find("GET", "/users/7", true).unwrap();
+    assert_eq!(
+        matched.params.unwrap().get("id").map(String::as_str),
+        Some("7")
+    );
+    assert!(interface.remove("GET", "/users/:id").unwrap());
+    assert!(!interface.remove("GET", "/users/:id").unwrap());
+    assert_eq!(lookup(&router, "/users/1"), None);
+
+    let recording = RecordingRouter {
+        inner: Router::new(),
+        lookups: std::sync::Mutex::new(Vec::new()),
+    };
+    recording.add("GET", "/health", "health").unwrap();
+    assert_eq!(lookup(&recording, "/health"), Some("health"));
+    assert_eq!(lookup(&recording, "/missing"), None);
+    assert_eq!(
+        *recording.lookups.lock().unwrap(),
+        vec!["GET /health".to_string(), "GET /missing".to_string()]
+    );
+}
+
+#[test]
+fn test_route_lifecycle_hooks() {
+    setup_tracing_for_tests();
+    let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut router = Router::new();
+    let add_events = Arc::clone(&events);
+    router.set_on_add(move |method, path| {
+        add_events
+            .lock()
+            .unwrap()
+            .push(format!("add {method} {path}"));
+    });
+    let remove_events = Arc::clone(&events);
+    router.set_on_remove(move |method, path| {
+        remove_events
+            .lock()
+            .unwrap()
+            .push(format!("remove {method} {path}"));
+    });
+
+    add_route(&router, "GET", "/users/:id/", "get_user").unwrap();
+    add_route_with_timeout(&router, "POST", "/users", "create_user", 500).unwrap();
+    assert!(add_route(&router, "GET", "/files/**/x", "invalid").is_err());
+    assert!(remove_route(&router, "POST", "/users").unwrap());
+    assert!(!remove_route(&router, "POST", "/users").unwrap());
+
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![
+            "add GET users/:id".to_string(),
+            "add POST users".to_string(),
+            "remove POST /users".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_route_hook_can_read_router() {
+    setup_tracing_for_tests();
+    let router = Arc::new(std::sync::OnceLock::<Router<&str>>::new());
+    let route_counts = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut inner = Router::new();
+    let (hook_router, hook_counts) = (Arc::clone(&router), Arc::clone(&route_counts));
+    inner.set_on_add(move |_, _| {
+        let count = list_routes(hook_router.get().unwrap()).len();
+        hook_counts.lock().unwrap().push(count);
+    });
+    router.set(inner).unwrap();
+    let router = router.get().unwrap();
+    add_route(router, "GET", "/a", "a").unwrap();
+    add_route(router, "GET", "/b/:id", "b").unwrap();
+    assert_eq!(*route_counts.lock().unwrap(), vec![1, 2]);
+}
+
+#[test]
+fn test_on_match_hook() {
+    setup_tracing_for_tests();
+    let matches = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut router = Router::new();
+    let match_events = Arc::clone(&matches);
+    router.set_on_match(move |method, path, pattern| {
+        match_events
+            .lock()
+            .unwrap()
+            .push(format!("{method} {path} {pattern}"));
+    });
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "GET", "/health", "health").unwrap();
+
+    find_route(&router, "GET", "/users/42/", false).unwrap();
+    find_route(&router, "GET", "/health", false).unwrap();
+    assert!(find_route(&router, "POST", "/users/42", false).is_err());
+
+    assert_eq!(
+        *matches.lock().unwrap(),
+        vec![
+            "GET users/42 /users/:id".to_string(),
+            "GET health /health".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_wildcard_preference_longest_static_prefix() {
+    setup_tracing_for_tests();
+    let register = |router: &Router<&'static str>| {
+        add_route(router, "GET", "/api/**:rest", "api_fallback").unwrap();
+        add_route(router, "GET", "/api/v1/**:rest", "api_v1").unwrap();
+        add_route(
+            router,
+            "GET",
+            "/:tenant/v1/data/files/**:rest",
+            "tenant_data",
+        )
+        .unwrap();
+        add_route(router, "GET", "/api/:version/status", "status").unwrap();
+    };
+    let default_router = Router::new();
+    register(&default_router);
+    let longest_router = Router::with_config(
+        RouterConfig::builder()
+            .wildcard_preference(WildcardPreference::LongestStaticPrefix)
+            .build(),
+    );
+    register(&longest_router);
+
+    // A deeper static wildcard is found first either way.
+    for router in [&default_router, &longest_router] {
+        assert_eq!(
+            find_route(router, "GET", "/api/v1/users", false)
+                .unwrap()
+                .data,
+            "api_v1"
+        );
+        assert_eq!(
+            find_route(router, "GET", "/api/v2/users", false)
+                .unwrap()
+                .data,
+            "api_fallback"
+        );
+        // Exact matches are not affected by the preference.
+        assert_eq!(
+            find_route(router, "GET", "/api/v3/status", false)
+                .unwrap()
+                .data,
+            "status"
+        );
+    }
+
+    // `/api/v1/**` is reached before `/:tenant/v1/data/files/**`, which has more
+    // static segments. Equal counts keep the default choice.
+    assert_eq!(
+        find_route(&default_router, "GET", "/api/v1/data/files/x", false)
+            .unwrap()
+            .data,
+        "api_v1"
+    );
+    let matched = find_route(&longest_router, "GET", "/api/v1/data/files/x/y", true).unwrap();
+    assert_eq!(matched.data, "tenant_data");
+    let params = convert_params_to_hashmap(matched.params).unwrap();
+    assert_eq!(params.get("tenant").map(String::as_str), Some("api"));
+    assert_eq!(params.get("rest").map(String::as_str), Some("x/y"));
+    assert_eq!(matched.route_pattern, "/:tenant/v1/data/files/**:rest");
+
+    add_route(
+        &longest_router,
+        "GET",
+        "/:tenant/v1/docs/**:rest",
+        "tenant_docs",
+    )
+    .unwrap();
+    assert_eq!(
+        find_route(&longest_router, "GET", "/api/v1/docs/x", false)
+            .unwrap()
+            .data,
+        "api_v1"
+    );
+}
+
+#[test]
+fn test_strict_shadowing() {
+    setup_tracing_for_tests();
+    let router = Router::with_config(RouterConfig::builder().strict_shadowing().build());
+    add_route(&router, "GET", "/users/:id", "by_id").unwrap();
+    assert_eq!(
+        add_route(&router, "GET", "/users/:name", "by_name"),
+        Err(RouterError::ShadowedRoute {
+            pattern: "/users/:name".to_string(),
+            shadowed_by: "/users/:id".to_string(),
+        })
+    );
+    add_route(&router, "GET", "/files/**:path", "files").unwrap();
+    assert_eq!(
+        add_route(&router, "GET", "/files/**:rest", "rest"),
+        Err(RouterError::ShadowedRoute {
+            pattern: "/files/**:rest".to_string(),
+            shadowed_by: "/files/**:path".to_string(),
+        })
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/users/1", false).unwrap().data,
+        "by_id"
+    );
+
+    // Other methods, more specific routes, and routes behind a guarded handler are reachable.
+    add_route(&router, "POST", "/users/:name", "create").unwrap();
+    add_route(&router, "GET", "/users/me", "me").unwrap();
+    add_route(&router, "GET", "/files/:name", "file").unwrap();
+    add_route_with_guard(&router, "GET", "/admin", "admin", |_, _| false).unwrap();
+    add_route(&router, "GET", "/admin", "fallback").unwrap();
+    assert_eq!(
+        find_route(&router, "GET", "/admin", false).unwrap().data,
+        "fallback"
+    );
+
+    let lenient = Router::new();
+    add_route(&lenient, "GET", "/users/:id", "by_id").unwrap();
+    assert!(add_route(&lenient, "GET", "/users/:name", "by_name").is_ok());
+    assert_eq!(
+        find_route(&lenient, "GET", "/users/1", false).unwrap().data,
+        "by_id"
+    );
+}
+
+#[test]
+fn test_transform_all() {
+    setup_tracing_for_tests();
+    let router = Router::with_global_prefix("/api");
+    add_route(&router, "GET", "/", 1).unwrap();
+    add_route(&router, "GET", "/users/:id", 2).unwrap();
+    add_route(&router, "POST", "/files/**:path", 3).unwrap();
+
+    let transformed = transform_all(&router, |n| format!("handler-{n}"));
+    assert_eq!(transformed.config, router.config);
+    assert_eq!(
+        find_route(&transformed, "GET", "/api", false).unwrap().data,
+        "handler-1"
+    );
+    let user = find_route(&transformed, "GET", "/api/users/7", true).unwrap();
+    assert_eq!(user.data, "handler-2");
+    assert_eq!(user.params.unwrap().get("id"), Some(&"7".to_string()));
+    assert_eq!(
+        find_route(&transformed, "POST", "/api/files/a/b", false)
+            .unwrap()
+            .data,
+        "handler-3"
+    );
+    let transformed_routes: Vec<_> = list_routes(&transformed)
+        .into_iter()
+        .map(|entry| (entry.method, entry.pattern))
+        .collect();
+    let original_routes: Vec<_> = list_routes(&router)
+        .into_iter()
+        .map(|entry| (entry.method, entry.pattern))
+        .collect();
+    assert_eq!(transformed_routes, original_routes);
+}
+
+#[test]
+fn test_route_description() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route_with_description(&router, "GET", "/users/:id", "get_user", "Fetches a user").unwrap();
+    add_route(&router, "GET", "/health", "health").unwrap();
+    assert_eq!(
+        find_route(&router, "GET", "/users/1", false)
+            .unwrap()
+            .description
+            .as_deref(),
+        Some("Fetches a user")
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/health", false)
+            .unwrap()
+            .description,
+        None
+    );
+    let descriptions: Vec<_> = list_routes(&router)
+        .into_iter()
+        .map(|entry| (entry.pattern, entry.description))
+        .collect();
+    assert_eq!(
+        descriptions,
+        [
+            ("/health".to_string(), None),
+            ("/users/:id".to_string(), Some("Fetches a user".to_string())),
+        ]
+    );
+
+    // The description survives removing the route and re-adding it from a snapshot.
+    let saved = snapshot(&router);
+    remove_route(&router, "GET", "/users/:id").unwrap();
+    let restored = restore(saved).unwrap();
+    assert_eq!(
+        find_route(&restored, "GET", "/users/1", false)
+            .unwrap()
+            .description
+            .as_deref(),
+        Some("Fetches a user")
+    );
+}
+
+#[test]
+fn test_find_route_for_request() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/search/:scope", "search").unwrap();
+
+    let headers =
+        ahash::AHashMap::from_iter([("accept".to_string(), "application/json".to_string())]);
+    let req = RoutableRequest {
+        method: "GET",
+        path: "/search/docs",
+        headers: Some(&headers),
+        query: Some("q=rust&page=2"),
+    };
+    let matched = find_route_for_request(&router, &req, true).unwrap();
+    assert_eq!(matched.data, "search");
+    assert_eq!(
+        matched.params.unwrap().get("scope"),
+        Some(&"docs".to_string())
+    );
+    let query = matched.query.unwrap();
+    assert_eq!(query.get("q"), Some(&"rust".to_string()));
+    assert_eq!(query.get("page"), Some(&"2".to_string()));
+
+    let uncaptured = find_route_for_request(&router, &req, false).unwrap();
+    assert!(uncaptured.params.is_none() && uncaptured.query.is_none());
+    let no_query = RoutableRequest { query: None, ..req };
+    assert!(
+        find_route_for_request(&router, &no_query, true)
+            .unwrap()
+            .query
+            .is_none()
+    );
+    let wrong_method = RoutableRequest {
+        method: "POST",
+        ..req
+    };
+    assert!(find_route_for_request(&router, &wrong_method, true).is_err());
+}
+
+#[test]
+fn test_route_schemas() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/health", "health").unwrap();
+    add_route(&router, "GET", "/users/:id/posts/:slug?", "post").unwrap();
+    add_route(&router, "POST", "/files/*/**:path", "upload").unwrap();
+
+    let param = |name: &str, kind, optional, position| ParamSchema {
+        name: name.to_string(),
+        kind,
+        optional,
+        position,
+    };
+    assert_eq!(
+        route_schemas(&router),
+        vec![
+            RouteSchema {
+                method: "POST".to_string(),
+                pattern: "/files/*/**:path".to_string(),
+                params: vec![
+                    param("_", ParamKind::Index, false, 1),
+                    param("path", ParamKind::Wildcard, false, 2),
+                ],
+            },
+            RouteSchema {
+                method: "GET".to_string(),
+                pattern: "/health".to_string(),
+                params: vec![],
+            },
+            RouteSchema {
+                method: "GET".to_string(),
+                pattern: "/users/:id/posts/:slug?".to_string(),
+                params: vec![
+                    param("id", ParamKind::Index, false, 1),
+                    param("slug", ParamKind::Index, true, 3),
+                ],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_static_children_keep_insertion_order() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    for segment in ["zeta", "alpha", "mu", "beta"] {
+        add_route(&router, "GET", &format!("/{segment}/:id"), segment).unwrap();
+    }
+    remove_route(&router, "GET", "/alpha/:id").unwrap();
+    add_route(&router, "GET", "/alpha/:id", "alpha").unwrap();
+    let segments: Vec<String> = router.root.read().static_children.keys().cloned().collect();
+    assert_eq!(segments, ["zeta", "mu", "beta", "alpha"]);
+}
+
+#[test]
+fn test_matched_route_param_accessors() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users/:user/posts/:post", "post").unwrap();
+    add_route(&router, "GET", "/health", "health").unwrap();
+
+    let matched = find_route(&router, "GET", "/users/ada/posts/42", true).unwrap();
+    assert_eq!(matched.get_param("user"), Some("ada"));
+    assert_eq!(matched.get_param("post"), Some("42"));
+    assert_eq!(matched.get_param("missing"), None);
+    let mut params: Vec<_> = matched.iter_params().collect();
+    params.sort_unstable();
+    assert_eq!(params, [("post", "42"), ("user", "ada")]);
+    let mut names: Vec<_> = matched.param_names().collect();
+    names.sort_unstable();
+    assert_eq!(names, ["post", "user"]);
+
+    let uncaptured = find_route(&router, "GET", "/users/ada/posts/42", false).unwrap();
+    assert_eq!(uncaptured.get_param("user"), None);
+    assert_eq!(uncaptured.iter_params().count(), 0);
+    assert_eq!(uncaptured.param_names().count(), 0);
+}
+
+#[test]
+fn test_add_route_with_priority() {
+    setup_tracing_for_tests();
+    let router = Router::with_config(RouterConfig::builder().strict_shadowing().build());
+    add_route(&router, "GET", "/users/:id", "by_id").unwrap();
+    add_route_with_priority(&router, "GET", "/users/:name", "by_name", 10).unwrap();
+    let matched = find_route(&router, "GET", "/users/ada", true).unwrap();
+    assert_eq!(matched.data, "by_name");
+    assert_eq!(matched.get_param("name"), Some("ada"));
+    assert_eq!(
+        add_route_with_priority(&router, "GET", "/users/:slug", "by_slug", 10),
+        Err(RouterError::ShadowedRoute {
+            pattern: "/users/:slug".to_string(),
+            shadowed_by: "/users/:name".to_string(),
+        })
+    );
+
+    add_route_with_priority(&router, "GET", "/about", "old_about", -1).unwrap();
+    add_route(&router, "GET", "/about", "about").unwrap();
+    assert_eq!(
+        find_route(&router, "GET", "/about", false).unwrap().data,
+        "about"
+    );
+    let all: Vec<_> = find_all_routes(&router, "GET", "/users/ada", false)
+        .into_iter()
+        .map(|matched| matched.data)
+        .collect();
+    assert_eq!(all, ["by_name", "by_id"]);
+}
+
+#[test]
+fn test_repeated_param() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/search/:terms+", "search").unwrap();
+
+    let matched = find_route(&router, "GET", "/search/rust/async/web", true).unwrap();
+    assert_eq!(matched.data, "search");
+    assert_eq!(matched.get_param("terms"), Some("rust,async,web"));
+    assert_eq!(matched.route_pattern, "/search/:terms+");
+    assert_eq!(
+        find_route(&router, "GET", "/search/rust", true)
+            .unwrap()
+            .get_param("terms"),
+        Some("rust")
+    );
+    // At least one segment is required.
+    assert!(find_route(&router, "GET", "/search", false).is_err());
+    assert!(find_all_routes(&router, "GET", "/search", false).is_empty());
+    assert_eq!(
+        find_route_borrowed(
+            &router,
+            "GET",
+            &["search", "rust", "async"],
+            "/search/rust/async",
+            true
+        )
+        .unwrap()
+        .params
+        .unwrap()
+        .get("terms")
+        .map(|terms| terms.as_ref()),
+        Some("rust,async")
+    );
+    assert_eq!(
+        list_routes(&router)
+            .into_iter()
+            .map(|entry| entry.pattern)
+            .collect::<Vec<_>>(),
+        ["/search/:terms+"]
+    );
+
+    assert_eq!(
+        add_route(&router, "GET", "/tags/:tags+/edit", "edit"),
+        Err(RouterError::InvalidSegment {
+            segment: ":tags+".to_string(),
+            reason: "repeated parameter (:name+) must be the last segment".to_string(),
+        })
+    );
+    assert!(remove_route(&router, "GET", "/search/:terms+").unwrap());
+    assert!(find_route(&router, "GET", "/search/rust", false).is_err());
+}
+
+#[test]
+fn test_find_ambiguous_routes() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users/:userId", "get_user").unwrap();
+    add_route(&router, "DELETE", "/users/:teamId", "delete_team").unwrap();
+    add_route(&router, "PUT", "/users/:userId", "update_user").unwrap();
+    add_route(&router, "GET", "/users/:userId/posts", "posts").unwrap();
+    add_route(&router, "GET", "/users/:teamId/members", "members").unwrap();
+    add_route(&router, "GET", "/files/**:path", "files").unwrap();
+    add_route(&router, "POST", "/files/**:rest", "upload").unwrap();
+
+    assert_eq!(
+        find_ambiguous_routes(&router),
+        vec![
+            AmbiguityReport {
+                pattern_a: "/files/**:path".to_string(),
+                pattern_b: "/files/**:rest".to_string(),
+                example_path: "/files/1".to_string(),
+            },
+            AmbiguityReport {
+                pattern_a: "/users/:teamId".to_string(),
+                pattern_b: "/users/:userId".to_string(),
+                example_path: "/users/1".to_string(),
+            },
+        ]
+    );
+    assert!(find_ambiguous_routes(&Router::<&str>::new()).is_empty());
+}
+
+#[test]
+fn test_add_route_for_host() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route_for_host(&router, "api.example.com", "GET", "/", "api_home").unwrap();
+    add_route_for_host(&router, "*.Example.com", "GET", "/", "tenant_home").unwrap();
+    add_route_for_host(
+        &router,
+        "shop-*.example.org",
+        "GET",
+        "/items/:id",
+        "shop_item",
+    )
+    .unwrap();
+    add_route(&router, "GET", "/items/:id", "item").unwrap();
+
+    let find = |host: &str, path: &str| {
+        find_route_with_host(&router, host, "GET", path, true).map(|matched| matched.data)
+    };
+    assert_eq!(find("api.example.com", "/").unwrap(), "api_home");
+    assert_eq!(find("API.example.com:8080", "/").unwrap(), "api_home");
+    assert_eq!(find("acme.example.com", "/").unwrap(), "tenant_home");
+    assert!(matches!(
+        find("example.net", "/"),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+    assert_eq!(
+        find("shop-eu.example.org", "/items/1").unwrap(),
+        "shop_item"
+    );
+    assert_eq!(find("blog.example.org", "/items/1").unwrap(), "item");
+    assert_eq!(
+        find_route_with_host(&router, "blog.example.org", "POST", "/items/1", false),
+        Err(RouterError::MethodNotAllowed {
+            method: "POST".to_string(),
+            path: "/items/1".to_string(),
+            allowed: vec!["GET".to_string(), "HEAD".to_string()],
+        })
+    );
+
+    // Lookups without a host ignore host constraints.
+    assert_eq!(
+        find_route(&router, "GET", "/", false).unwrap().data,
+        "api_home"
+    );
+}
+
+#[test]
+fn test_drain_method_routes() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "POST", "/users", "create_user").unwrap();
+    add_route(&router, "POST", "/users/:id/avatar", "upload_avatar").unwrap();
+    add_route(&router, "POST", "/files/**:path", "upload_file").unwrap();
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "", "/health", "health").unwrap();
+
+    assert_eq!(drain_method_routes(&router, "POST").unwrap(), 3);
+    assert_eq!(route_count(&router), 3);
+    for path in ["/users", "/users/1/avatar", "/files/a.txt"] {
+        assert!(find_route(&router, "POST", path, false).is_err(), "{path}");
+    }
+    assert_eq!(
+        find_route(&router, "GET", "/users", false).unwrap().data,
+        "list_users"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/users/1", false).unwrap().data,
+        "get_user"
+    );
+    assert_eq!(
+        find_route(&router, "POST", "/health", false).unwrap().data,
+        "health"
+    );
+    assert!(!router.root.read().static_children.contains_key("files"));
+
+    assert_eq!(drain_method_routes(&router, "POST").unwrap(), 0);
+}
+
+#[test]
+fn test_differently_named_params_share_level() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/orgs/:userId", "user").unwrap();
+    add_route(&router, "GET", "/orgs/:teamId/members", "team_members").unwrap();
+    add_route(&router, "DELETE", "/orgs/:orgId", "delete_org").unwrap();
+
+    let user = find_route(&router, "GET", "/orgs/42", true).unwrap();
+    assert_eq!(user.data, "user");
+    assert_eq!(user.get_param("userId"), Some("42"));
+
+    let members = find_route(&router, "GET", "/orgs/7/members", true).unwrap();
+    assert_eq!(members.data, "team_members");
+    assert_eq!(members.get_param("teamId"), Some("7"));
+    assert_eq!(members.get_param("userId"), None);
+
+    let deleted = find_route(&router, "DELETE", "/orgs/9", true).unwrap();
+    assert_eq!(deleted.get_param("orgId"), Some("9"));
+}
+
+#[test]
+fn test_find_route_best() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/docs/latest/**:page", "latest_docs").unwrap();
+    add_route(&router, "GET", "/docs/:version/index", "docs_index").unwrap();
+    add_route(&router, "GET", "/docs/:version/:page", "docs_page").unwrap();
+
+    let first = find_route(&router, "GET", "/docs/latest/index", true).unwrap();
+    assert_eq!(first.data, "latest_docs");
+    let best = find_route_best(&router, "GET", "/docs/latest/index", true).unwrap();
+    assert_eq!(best.data, "docs_index");
+    assert_eq!(best.get_param("version"), Some("latest"));
+
+    // Both agree when the depth-first match is also the most specific.
+    assert_eq!(
+        find_route_best(&router, "GET", "/docs/v2/intro", false)
+            .unwrap()
+            .data,
+        find_route(&router, "GET", "/docs/v2/intro", false)
+            .unwrap()
+            .data
+    );
+    assert!(matches!(
+        find_route_best(&router, "GET", "/missing", false),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+    assert_eq!(
+        find_route_best(&router, "POST", "/docs/v2/intro", false),
+        Err(RouterError::MethodNotAllowed {
+            method: "POST".to_string(),
+            path: "/docs/v2/intro".to_string(),
+            allowed: vec!["GET".to_string(), "HEAD".to_string()],
+        })
+    );
+}
+
+#[test]
+fn test_namespace_router_isolates_tenants() {
+    setup_tracing_for_tests();
+    let acme = Router::new();
+    add_route(&acme, "GET", "/dashboard", "acme_dashboard").unwrap();
+    add_route(&acme, "GET", "/reports/:id", "acme_report").unwrap();
+    let globex = Router::new();
+    add_route(&globex, "GET", "/dashboard", "globex_dashboard").unwrap();
+
+    let ns = NamespaceRouter::new();
+    add_namespace(&ns, "acme", Arc::new(acme));
+    add_namespace(&ns, "globex", Arc::new(globex));
+
+    let report = find_in_namespace(&ns, "acme", "GET", "/reports/3", true).unwrap();
+    assert_eq!(report.data, "acme_report");
+    assert_eq!(report.get_param("id"), Some("3"));
+    assert_eq!(
+        find_in_namespace(&ns, "globex", "GET", "/dashboard", false)
+            .unwrap()
+            .data,
+        "globex_dashboard"
+    );
+    assert!(find_in_namespace(&ns, "globex", "GET", "/reports/3", false).is_err());
+
+    let removed = remove_namespace(&ns, "acme").unwrap();
+    assert_eq!(route_count(&removed), 2);
+    assert!(remove_namespace(&ns, "acme").is_none());
+    assert!(matches!(
+        find_in_namespace(&ns, "acme", "GET", "/dashboard", false),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_fallback_to_wildcard_after_param_miss() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/a/:x/c", "param_then_c").unwrap();
+    add_route(&router, "GET", "/a/b/:y/z", "static_then_param").unwrap();
+    add_route(&router, "GET", "/a/**:rest", "wildcard").unwrap();
+
+    // The static `b` branch misses (no `/z`), so the parametric branch matches.
+    let matched = find_route(&router, "GET", "/a/b/c", true).unwrap();
+    assert_eq!(matched.route_pattern, "/a/:x/c");
+    assert_eq!(matched.get_param("x"), Some("b"));
+
+    // Both the static and the parametric branches miss, so the wildcard matches.
+    let matched = find_route(&router, "GET", "/a/b/d", true).unwrap();
+    assert_eq!(matched.route_pattern, "/a/**:rest");
+    assert_eq!(matched.get_param("rest"), Some("b/d"));
+
+    // A wildcard below the parametric child is found after the static branch misses.
+    add_route(&router, "GET", "/a/:x/files/**:path", "param_wildcard").unwrap();
+    let matched = find_route(&router, "GET", "/a/b/files/x/y", true).unwrap();
+    assert_eq!(matched.route_pattern, "/a/:x/files/**:path");
+    assert_eq!(matched.get_param("path"), Some("x/y"));
+}
+
+#[test]
+fn test_method_not_allowed() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "PUT", "/users/:id", "update_user").unwrap();
+    add_route(&router, "DELETE", "/users/**:rest", "delete_users").unwrap();
+    add_route(&router, "", "/health", "health").unwrap();
+
+    assert_eq!(
+        find_route(&router, "POST", "/users/7", false),
+        Err(RouterError::MethodNotAllowed {
+            method: "POST".to_string(),
+            path: "/users/7".to_string(),
+            allowed: ["DELETE", "GET", "HEAD", "PUT"].map(String::from).to_vec(),
+        })
+    );
+    assert!(matches!(
+        find_route(&router, "POST", "/posts/7", false),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+    assert!(find_route(&router, "POST", "/health", false).is_ok());
+
+    let err = find_route(&router, "PATCH", "/users/7", false).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "method 'PATCH' not allowed for path '/users/7' (allowed: DELETE, GET, HEAD, PUT)"
+    );
+}
+
+#[test]
+fn test_validate() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users/:userId", "get_user").unwrap();
+    add_route(&router, "GET", "/users/:teamId", "get_team").unwrap();
+    add_route(&router, "GET", "/users/:userId/posts", "posts").unwrap();
+    add_route(&router, "GET", "/files/**:path", "files").unwrap();
+    add_route(&router, "GET", "/files/:parts+", "parts").unwrap();
+    add_route(&router, "GET", "/health", "health").unwrap();
+    add_route(&router, "GET", "/health", "health_again").unwrap();
+
+    let validation = validate(&router);
+    assert_eq!(
+        validation.errors,
+        vec![RouterError::DuplicateRoute {
+            method: "GET".to_string(),
+            pattern: "/health".to_string(),
+        }]
+    );
+    assert_eq!(
+        validation.warnings,
+        vec![
+            RouterWarning::ShadowedRoute {
+                method: "GET".to_string(),
+                pattern: "/files/:parts+".to_string(),
+                shadowed_by: "/files/**:path".to_string(),
+            },
+            RouterWarning::ShadowedRoute {
+                method: "GET".to_string(),
+                pattern: "/users/:teamId".to_string(),
+                shadowed_by: "/users/:userId".to_string(),
+            },
+            RouterWarning::InconsistentParamNames {
+                prefix: "/users/:*".to_string(),
+                names: vec!["teamId".to_string(), "userId".to_string()],
+            },
+        ]
+    );
+
+    // A `+` wildcard does not match the empty rest, so a later `**` still can.
+    let router = Router::new();
+    add_route(&router, "GET", "/files/:parts+", "parts").unwrap();
+    add_route(&router, "GET", "/files/**:path", "files").unwrap();
+    add_route(&router, "POST", "/files/**:path", "upload").unwrap();
+    assert!(validate(&router).is_clean());
+}
+
+#[test]
+fn test_find_route_accepting() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route_producing(
+        &router,
+        "GET",
+        "/reports/:id",
+        "xml",
+        &["application/xml", "text/xml"],
+    )
+    .unwrap();
+    add_route_producing(
+        &router,
+        "GET",
+        "/reports/:id",
+        "json",
+        &["application/json"],
+    )
+    .unwrap();
+    add_route(&router, "GET", "/reports/:id", "fallback").unwrap();
+
+    let accepting = |accept: &str| find_route_accepting(&router, "GET", "/reports/7", accept, true);
+    assert_eq!(accepting("application/json").unwrap().data, "json");
+    assert_eq!(accepting("application/xml").unwrap().data, "xml");
+    assert_eq!(
+        accepting("application/xml;q=0.5, application/json")
+            .unwrap()
+            .data,
+        "json"
+    );
+    assert_eq!(
+        accepting("application/json;q=0.2, text/*;q=0.9")
+            .unwrap()
+            .data,
+        "xml"
+    );
+    // Ties go to the handler registered first.
+    assert_eq!(accepting("*/*").unwrap().data, "xml");
+    assert_eq!(accepting("").unwrap().data, "xml");
+    // The most specific range decides, so `q=0` rules a type out despite `*/*`.
+    assert_eq!(
+        accepting("*/*, application/xml;q=0, text/xml;q=0")
+            .unwrap()
+            .data,
+        "json"
+    );
+    // Nothing acceptable is produced, so the handler without content types answers.
+    assert_eq!(accepting("text/html").unwrap().data, "fallback");
+    let matched = accepting("application/json").unwrap();
+    assert_eq!(
+        matched.params.unwrap().get("id").map(String::as_str),
+        Some("7")
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/reports/7", false)
+            .unwrap()
+            .data,
+        "xml"
+    );
+
+    let strict = Router::new();
+    add_route_producing(&strict, "GET", "/data", "json", &["application/json"]).unwrap();
+    add_route_producing(&strict, "GET", "/data", "xml", &["application/xml"]).unwrap();
+    assert_eq!(
+        find_route_accepting(&strict, "GET", "/data", "text/html", false),
+        Err(RouterError::NotAcceptable {
+            method: "GET".to_string(),
+            path: "/data".to_string(),
+        })
+    );
+    assert!(matches!(
+        find_route_accepting(&strict, "GET", "/missing", "application/json", false),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+    assert!(matches!(
+        find_route_accepting(&strict, "POST", "/data", "application/json", false),
+        Err(RouterError::MethodNotAllowed { .. })
+    ));
+    // Handlers for different content types don't shadow each other.
+    assert!(validate(&strict).is_clean());
+}
+
+#[test]
+fn test_add_weighted_route() {
+    setup_tracing_for_tests();
+    let router = Router::with_config(RouterConfig::builder().strict_shadowing().build());
+    add_weighted_route(&router, "GET", "/checkout", "stable", 95).unwrap();
+    add_weighted_route(&router, "GET", "/checkout", "canary", 5).unwrap();
+
+    // Without a random number generator the first variant answers.
+    assert_eq!(
+        find_route(&router, "GET", "/checkout", false).unwrap().data,
+        "stable"
+    );
+    assert_eq!(route_count(&router), 2);
+    // Weighted variants are not reported as duplicates.
+    assert!(validate(&router).is_clean());
+}
+
+#[test]
+fn test_add_weighted_route_with_weight_one() {
+    setup_tracing_for_tests();
+    for weights in [[1, 3], [3, 1]] {
+        let router = Router::new();
+        let strict = Router::with_config(RouterConfig::builder().strict_shadowing().build());
+        for (data, weight) in ["a", "b"].into_iter().zip(weights) {
+            add_weighted_route(&router, "GET", "/checkout", data, weight).unwrap();
+            add_weighted_route(&strict, "GET", "/checkout", data, weight).unwrap();
+        }
+        // A variant with weight 1 is still a weighted variant, not a plain handler.
+        assert!(validate(&router).is_clean());
+        assert!(validate(&strict).is_clean());
+        assert_eq!(route_count(&strict), 2);
+    }
+}
+
+#[test]
+fn test_analyze_hot_paths() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "GET", "/teams/:id", "get_team").unwrap();
+    add_route(&router, "GET", "/files/**:path", "files").unwrap();
+    add_route(&router, "GET", "/health", "health").unwrap();
+
+    let mut access_log = vec![("GET", "/users/admin/"); 5];
+    access_log.extend([("GET", "/teams/1"), ("GET", "/teams/2")].repeat(3));
+    access_log.extend([("GET", "/files/logo.png"); 2]);
+    access_log.extend(
+        [
+            ("GET", "/health"),
+            ("GET", "/missing"),
+            ("POST", "/users/admin"),
+        ]
+        .repeat(10),
+    );
+
+    assert_eq!(
+        analyze_hot_paths(&router, &access_log, 3),
+        vec![HotPathSuggestion {
+            pattern: "/users/:id".to_string(),
+            actual_path: "/users/admin".to_string(),
+            suggestion: "add a static route for GET /users/admin: all 5 requests matched \
+                         /users/:id with this path"
+                .to_string(),
+        }]
+    );
+    // `/files/**:path` always got the same path, but only twice.
+    assert_eq!(analyze_hot_paths(&router, &access_log, 1).len(), 2);
+    assert!(analyze_hot_paths(&router, &access_log, 5).is_empty());
+}
+
+#[test]
+fn test_router_add_chains_with_question_mark() {
+    setup_tracing_for_tests();
+    fn register(router: &Router<&'static str>) -> Result<(), RouterError> {
+        router
+            .add("GET", "/users/:id", "get_user")?
+            .add("POST", "/users", "create_user")?
+            .add("GET", "/files/**:path", "files")?;
+        Ok(())
+    }
+    fn register_invalid(router: &Router<&'static str>) -> Result<(), RouterError> {
+        router
+            .add("GET", "/teams", "list_teams")?
+            .add("GET", "/teams/**/x", "invalid")?
+            .add("GET", "/after", "never_added")?;
+        Ok(())
+    }
+
+    let router = Router::new();
+    register(&router).unwrap();
+    assert_eq!(
+        find_route(&router, "GET", "/users/7", false).unwrap().data,
+        "get_user"
+    );
+    assert_eq!(
+        find_route(&router, "POST", "/users", false).unwrap().data,
+        "create_user"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/files/a/b", false)
+            .unwrap()
+            .data,
+        "files"
+    );
+
+    assert!(register_invalid(&router).is_err());
+    assert_eq!(
+        find_route(&router, "GET", "/teams", false).unwrap().data,
+        "list_teams"
+    );
+    assert!(find_route(&router, "GET", "/after", false).is_err());
+}
+
+#[test]
+fn test_add_route_with_allowlist() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route_with_allowlist(
+        &router,
+        "GET",
+        "/paint/:color",
+        "primary",
+        &[("color", &["red", "green", "blue"])],
+    )
+    .unwrap();
+
+    for color in ["red", "green", "blue"] {
+        let matched = find_route(&router, "GET", &format!("/paint/{color}"), true).unwrap();
+        assert_eq!(matched.data, "primary");
+        assert_eq!(
+            matched.params.unwrap().get("color").map(String::as_str),
+            Some(color)
+        );
+    }
+    assert!(matches!(
+        find_route(&router, "GET", "/paint/purple", false),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+
+    // An allowlisted route does not shadow a later route with the same shape, which
+    // takes the values the allowlist rejects.
+    add_route(&router, "GET", "/paint/:name", "custom").unwrap();
+    assert_eq!(
+        find_route(&router, "GET", "/paint/purple", false)
+            .unwrap()
+            .data,
+        "custom"
+    );
+    assert_eq!(
+        find_route(&router, "GET", "/paint/red", false)
+            .unwrap()
+            .data,
+        "primary"
+    );
+    assert_eq!(
+        find_all_routes(&router, "GET", "/paint/purple", false).len(),
+        1
+    );
+}
+
+#[test]
+fn test_add_route_with_allowlist_rejects_unknown_param() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    assert_eq!(
+        add_route_with_allowlist(
+            &router,
+            "GET",
+            "/paint/:colour",
+            "primary",
+            &[("color", &["red"])],
+        ),
+        Err(RouterError::InvalidPath(
+            "allowlist for unknown parameter 'color' in pattern '/paint/:colour'".to_string()
+        ))
+    );
+    // Only single-segment parameters take allowlists.
+    assert!(matches!(
+        add_route_with_allowlist(
+            &router,
+            "GET",
+            "/files/**:path",
+            "files",
+            &[("path", &["a"])]
+        ),
+        Err(RouterError::InvalidPath(_))
+    ));
+    assert_eq!(route_count(&router), 0);
+    assert!(find_route(&router, "GET", "/paint/purple", false).is_err());
+}
+
+#[test]
+fn test_add_route_shared_swaps_handler_in_place() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    let user_handler = Arc::new(parking_lot::RwLock::new("get_user_v1"));
+    add_route_shared(&router, "GET", "/users/:id", Arc::clone(&user_handler)).unwrap();
+    add_route_shared(
+        &router,
+        "GET",
+        "/health",
+        Arc::new(parking_lot::RwLock::new("health")),
+    )
+    .unwrap();
+
+    let handle = get_route_handle(&router, "GET", "/users/7").unwrap();
+    assert!(Arc::ptr_eq(&handle, &user_handler));
+    *handle.write() = "get_user_v2";
+    assert_eq!(
+        *get_route_handle(&router, "GET", "/users/8").unwrap().read(),
+        "get_user_v2"
+    );
+
+    let health = get_route_handle(&router, "GET", "/health").unwrap();
+    *health.write() = "health_v2";
+    assert_eq!(
+        *get_route_handle(&router, "GET", "/health").unwrap().read(),
+        "health_v2"
+    );
+    assert_eq!(route_count(&router), 2);
+
+    assert!(get_route_handle(&router, "POST", "/users/7").is_none());
+    assert!(get_route_handle(&router, "GET", "/missing").is_none());
+}
+
+#[test]
+fn test_router_from_entries() {
+    setup_tracing_for_tests();
+    let router = Router::new();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route_with_description(&router, "POST", "/users", "create_user", "Create a user").unwrap();
+    add_route(&router, "", "/files/**:path", "files").unwrap();
+
+    let copy = Router::from_entries(&router).unwrap();
+    assert_eq!(list_routes(&copy), list_routes(&router));
+    assert_eq!(
+        find_route(&copy, "GET", "/users/7", false).unwrap().data,
+        "get_user"
+    );
+
+    let restored = Router::from_entries(snapshot(&router).routes).unwrap();
+    assert_eq!(list_routes(&restored), list_routes(&router));
+
+    let entries = vec![
+        RouteEntry {
+            method: "GET".to_string(),
+            pattern: "/health".to_string(),
+            data: "health",
+            description: None,
+        },
+        RouteEntry {
+            method: "GET".to_string(),
+            pattern: "/files/**/x".to_string(),
+            data: "invalid",
+            description: None,
+        },
+    ];
+    assert!(matches!(
+        Router::from_entries(entries),
+        Err(RouterError::InvalidSegment { .. })
+    ));
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "tracing")]
+
+use rou3::{Router, add_route, find_route_traced};
+use std::sync::{Arc, Mutex};
+use tracing::Level;
+use tracing_subscriber::FmtSubscriber;
+
+#[test]
+fn test_find_route_traced() {
+    let logs = Arc::new(Mutex::new(Vec::new()));
+    let writer_logs = Arc::clone(&logs);
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(Level::DEBUG)
+        .with_ansi(false)
+        .with_writer(move || CapturedLogs(Arc::clone(&writer_logs)))
+        .finish();
+
+    let mut router = Router::new();
+    router.set_on_match(|_, _, pattern| tracing::info!(pattern, "hook called"));
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let matched = find_route_traced(&router, "GET", "/users/42", true, "req-1").unwrap();
+        assert_eq!(matched.data, "get_user");
+        assert!(find_route_traced(&router, "GET", "/nope", false, "req-2").is_err());
+    });
+
+    let output = String::from_utf8(logs.lock().unwrap().clone()).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    let span_1 = r#"find_route{request_id="req-1" method="GET" path="/users/42"}"#;
+    let span_2 = r#"find_route{request_id="req-2" method="GET" path="/nope"}"#;
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.contains(span_1) && line.contains("hook called")),
+        "{output}"
+    );
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.contains(span_1) && line.contains("route matched")),
+        "{output}"
+    );
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.contains(span_2) && line.contains("no route matched")),
+        "{output}"
+    );
+}
+
+/// A `tracing_subscriber` writer appending to a shared buffer.
+struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
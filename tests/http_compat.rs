@@ -0,0 +1,37 @@
+#![cfg(feature = "http")]
+
+use http::{Response, StatusCode};
+use rou3::{Router, add_route, find_route};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Reply {
+    Ok(&'static str),
+    Status(u16),
+}
+
+impl From<Reply> for Response<String> {
+    fn from(reply: Reply) -> Self {
+        match reply {
+            Reply::Ok(body) => Response::new(body.to_string()),
+            Reply::Status(code) => Response::builder()
+                .status(code)
+                .body(String::new())
+                .unwrap(),
+        }
+    }
+}
+
+#[test]
+fn test_matched_route_into_response() {
+    let router = Router::new();
+    add_route(&router, "GET", "/users/:id", Reply::Ok("user")).unwrap();
+    add_route(&router, "DELETE", "/users/:id", Reply::Status(204)).unwrap();
+
+    let response: Response<String> = find_route(&router, "GET", "/users/1", true).unwrap().into();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body(), "user");
+
+    let response = Response::from(find_route(&router, "DELETE", "/users/1", false).unwrap());
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(response.body().is_empty());
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "json_config")]
+
+use rou3::context::to_json_string;
+use rou3::{Router, RouterError, add_route, find_route, list_routes};
+
+const ROUTES_JSON: &str = r#"[
+    {"method": "GET", "path": "/home", "data": "home"},
+    {"method": "GET", "path": "/users/:id", "data": "user_by_id"},
+    {"method": "", "path": "/assets/**:filepath", "data": "assets"}
+]"#;
+
+#[test]
+fn test_from_json_str_all_route_types() {
+    let router: Router<String> = Router::from_json_str(ROUTES_JSON).unwrap();
+
+    assert_eq!(
+        find_route(&router, "GET", "/home", false).unwrap().data,
+        "home"
+    );
+
+    let user = find_route(&router, "GET", "/users/42", true).unwrap();
+    assert_eq!(user.data, "user_by_id");
+    assert_eq!(user.params.unwrap().get("id").unwrap(), "42");
+
+    let asset = find_route(&router, "POST", "/assets/css/site.css", true).unwrap();
+    assert_eq!(asset.data, "assets");
+    assert_eq!(
+        asset.params.unwrap().get("filepath").unwrap(),
+        "css/site.css"
+    );
+}
+
+#[test]
+fn test_from_json_str_errors() {
+    assert!(matches!(
+        Router::<String>::from_json_str("not json"),
+        Err(RouterError::InvalidPath(_))
+    ));
+    assert!(matches!(
+        Router::<String>::from_json_str(r#"[{"method": "GET", "path": "/a/:", "data": "x"}]"#),
+        Err(RouterError::InvalidSegment { .. })
+    ));
+}
+
+#[test]
+fn test_to_json_string_round_trip() {
+    let router = Router::new();
+    add_route(&router, "GET", "/home", "home".to_string()).unwrap();
+    add_route(&router, "POST", "/users/:id?", "update_user".to_string()).unwrap();
+    add_route(&router, "GET", "/files/*", "file".to_string()).unwrap();
+    add_route(&router, "", "/assets/**:filepath", "assets".to_string()).unwrap();
+
+    let json = to_json_string(&router).unwrap();
+    let restored: Router<String> = Router::from_json_str(&json).unwrap();
+
+    assert_eq!(list_routes(&router), list_routes(&restored));
+    assert_eq!(
+        find_route(&restored, "GET", "/home", false).unwrap().data,
+        "home"
+    );
+    assert_eq!(
+        find_route(&restored, "POST", "/users", false).unwrap().data,
+        "update_user"
+    );
+    assert_eq!(
+        find_route(&restored, "GET", "/files/a.txt", false)
+            .unwrap()
+            .data,
+        "file"
+    );
+    assert_eq!(
+        find_route(&restored, "DELETE", "/assets/js/app.js", false)
+            .unwrap()
+            .data,
+        "assets"
+    );
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "openapi")]
+
+use rou3::{Router, RouterError, find_route, from_openapi_paths, list_routes};
+use serde_json::json;
+
+#[test]
+fn test_from_openapi_paths() {
+    let paths = json!({
+        "/users": {
+            "summary": "Users",
+            "parameters": [],
+            "get": { "operationId": "listUsers" },
+            "post": { "operationId": "createUser" }
+        },
+        "/users/{userId}": {
+            "get": {},
+            "patch": {},
+            "delete": {}
+        },
+        "/files/{+path}": { "get": {} }
+    });
+    let router: Router<Option<&str>> = from_openapi_paths(&paths).unwrap();
+
+    let patterns: Vec<_> = list_routes(&router)
+        .into_iter()
+        .map(|route| format!("{} {}", route.method, route.pattern))
+        .collect();
+    assert_eq!(
+        patterns,
+        vec![
+            "GET /files/**:path",
+            "GET /users",
+            "POST /users",
+            "DELETE /users/:userId",
+            "GET /users/:userId",
+            "PATCH /users/:userId",
+        ]
+    );
+
+    let matched = find_route(&router, "PATCH", "/users/7", true).unwrap();
+    assert_eq!(matched.data, None);
+    assert_eq!(
+        matched.params.unwrap().get("userId").map(String::as_str),
+        Some("7")
+    );
+    let matched = find_route(&router, "GET", "/files/docs/a.txt", true).unwrap();
+    assert_eq!(
+        matched.params.unwrap().get("path").map(String::as_str),
+        Some("docs/a.txt")
+    );
+    assert!(find_route(&router, "PUT", "/users/7", false).is_err());
+}
+
+#[test]
+fn test_from_openapi_paths_rejects_unsupported_templates() {
+    for paths in [
+        json!({ "/files/{name}.txt": { "get": {} } }),
+        json!({ "/files/{}": { "get": {} } }),
+        json!({ "/files/{a,b}": { "get": {} } }),
+        json!({ "/users": "not an object" }),
+        json!(["/users"]),
+    ] {
+        assert!(
+            matches!(
+                from_openapi_paths::<String>(&paths),
+                Err(RouterError::InvalidPath(_))
+            ),
+            "{paths} should be rejected"
+        );
+    }
+}
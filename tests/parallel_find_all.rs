@@ -0,0 +1,27 @@
+#![cfg(feature = "rayon")]
+
+use rou3::{Router, add_route, find_all_routes, find_all_routes_parallel};
+
+#[test]
+fn test_find_all_routes_parallel_matches_sequential() {
+    let router = Router::new();
+    add_route(&router, "GET", "/config", "config").unwrap();
+    add_route(&router, "GET", "/config/timeout", "timeout").unwrap();
+    add_route(&router, "", "/config/timeout", "any_timeout").unwrap();
+    add_route(&router, "GET", "/config/:key", "key").unwrap();
+    add_route(&router, "GET", "/config/**:path", "config_wildcard").unwrap();
+    add_route(&router, "GET", "/**", "catch_all").unwrap();
+
+    for path in ["/config/timeout", "/config", "/config/a/b", "/missing"] {
+        assert_eq!(
+            find_all_routes_parallel(&router, "GET", path, true),
+            find_all_routes(&router, "GET", path, true),
+            "{path}"
+        );
+    }
+    let data: Vec<_> = find_all_routes_parallel(&router, "GET", "/config/timeout", false)
+        .into_iter()
+        .map(|matched| matched.data)
+        .collect();
+    assert_eq!(data, ["catch_all", "config_wildcard", "key", "timeout"]);
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "postman")]
+
+use rou3::{Router, add_route, add_route_with_description, export_postman};
+use serde_json::json;
+
+#[test]
+fn test_export_postman_collection() {
+    let router = Router::new();
+    add_route(&router, "GET", "/health", "health").unwrap();
+    add_route_with_description(&router, "GET", "/users/:id", "get_user", "Fetch a user").unwrap();
+    add_route(&router, "DELETE", "/users/:id", "delete_user").unwrap();
+    add_route(&router, "GET", "/files/**:path", "files").unwrap();
+    add_route(&router, "", "/files/*/raw", "raw").unwrap();
+
+    let collection = export_postman(&router, "Example API");
+    assert_eq!(collection["info"]["name"], "Example API");
+    assert_eq!(
+        collection["info"]["schema"],
+        "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+    );
+    assert_eq!(
+        collection["variable"],
+        json!([{ "key": "baseUrl", "value": "http://localhost" }])
+    );
+
+    let items = collection["item"].as_array().unwrap();
+    let names: Vec<&str> = items
+        .iter()
+        .map(|item| item["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["GET /health", "files", "users"]);
+    assert_eq!(
+        items[0]["request"],
+        json!({
+            "method": "GET",
+            "url": { "raw": "{{baseUrl}}/health", "host": ["{{baseUrl}}"], "path": ["health"] },
+            "description": "\"health\"",
+        })
+    );
+
+    let users = items[2]["item"].as_array().unwrap();
+    assert_eq!(users.len(), 2);
+    let get_user = users
+        .iter()
+        .find(|item| item["name"] == "GET /users/:id")
+        .unwrap();
+    assert_eq!(
+        get_user["request"]["url"]["raw"],
+        "{{baseUrl}}/users/{{id}}"
+    );
+    assert_eq!(
+        get_user["request"]["url"]["path"],
+        json!(["users", "{{id}}"])
+    );
+    assert_eq!(get_user["request"]["description"], "Fetch a user");
+    assert!(
+        users
+            .iter()
+            .any(|item| item["request"]["method"] == "DELETE")
+    );
+
+    let files = items[1]["item"].as_array().unwrap();
+    let raw_urls: Vec<&str> = files
+        .iter()
+        .map(|item| item["request"]["url"]["raw"].as_str().unwrap())
+        .collect();
+    assert!(raw_urls.contains(&"{{baseUrl}}/files/{{path}}"));
+    assert!(raw_urls.contains(&"{{baseUrl}}/files/{{_}}/raw"));
+    let any_method = files
+        .iter()
+        .find(|item| item["name"] == "* /files/*/raw")
+        .unwrap();
+    assert_eq!(any_method["request"]["method"], "GET");
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "regex")]
+
+use rou3::{Router, RouterError, add_route, find_all_routes, find_route, list_routes};
+
+#[test]
+fn test_regex_constraint_falls_back_to_unconstrained_route() {
+    let router = Router::new();
+    add_route(&router, "GET", r"/users/:id<\d+>", "user_by_id").unwrap();
+    add_route(&router, "GET", "/users/:name", "user_by_name").unwrap();
+
+    let by_id = find_route(&router, "GET", "/users/42", true).unwrap();
+    assert_eq!(by_id.data, "user_by_id");
+    assert_eq!(
+        by_id.params.unwrap().get("id").map(String::as_str),
+        Some("42")
+    );
+    assert_eq!(by_id.route_pattern, r"/users/:id<\d+>");
+
+    let by_name = find_route(&router, "GET", "/users/alice", true).unwrap();
+    assert_eq!(by_name.data, "user_by_name");
+    assert_eq!(
+        by_name.params.unwrap().get("name").map(String::as_str),
+        Some("alice")
+    );
+
+    let all: Vec<_> = find_all_routes(&router, "GET", "/users/alice", false)
+        .into_iter()
+        .map(|matched| matched.data)
+        .collect();
+    assert_eq!(all, vec!["user_by_name"]);
+}
+
+#[test]
+fn test_regex_constraint_must_match_whole_segment() {
+    let router = Router::new();
+    add_route(&router, "GET", "/posts/:slug<[a-z]+>/comments", "comments").unwrap();
+
+    assert!(find_route(&router, "GET", "/posts/hello/comments", false).is_ok());
+    assert!(matches!(
+        find_route(&router, "GET", "/posts/hello1/comments", false),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_regex_constraint_on_optional_param() {
+    let router = Router::new();
+    add_route(&router, "GET", r"/page/:n<\d+>?", "page").unwrap();
+
+    assert!(find_route(&router, "GET", "/page", false).is_ok());
+    assert!(find_route(&router, "GET", "/page/2", false).is_ok());
+    assert!(find_route(&router, "GET", "/page/two", false).is_err());
+    assert_eq!(list_routes(&router)[0].pattern, r"/page/:n<\d+>?");
+}
+
+#[test]
+fn test_invalid_regex_constraint() {
+    let router: Router<&str> = Router::new();
+    assert!(matches!(
+        add_route(&router, "GET", "/users/:id<[0-9>", "user"),
+        Err(RouterError::InvalidSegment { .. })
+    ));
+    assert!(matches!(
+        add_route(&router, "GET", r"/users/:id<\d+", "user"),
+        Err(RouterError::InvalidSegment { .. })
+    ));
+    assert!(list_routes(&router).is_empty());
+}
+
+#[test]
+fn test_regex_constraint_does_not_shadow() {
+    let router = Router::with_config(rou3::RouterConfig::builder().strict_shadowing().build());
+    add_route(&router, "GET", r"/items/:id<\d+>", "numeric").unwrap();
+    add_route(&router, "GET", "/items/:key", "any").unwrap();
+    assert!(matches!(
+        add_route(&router, "GET", "/items/:other", "shadowed"),
+        Err(RouterError::ShadowedRoute { .. })
+    ));
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "serde_json")]
+
+use rou3::{Router, add_route, add_route_with_attrs, find_all_routes, find_route};
+use serde_json::json;
+
+#[test]
+fn test_add_route_with_attrs() {
+    let router = Router::new();
+    add_route_with_attrs(
+        &router,
+        "GET",
+        "/checkout/:step",
+        "checkout",
+        &[
+            ("feature_flag", json!("new_checkout")),
+            ("ab_weights", json!({"a": 80, "b": 20})),
+            ("log_level", json!("debug")),
+        ],
+    )
+    .unwrap();
+    add_route(&router, "GET", "/checkout/**:rest", "checkout_fallback").unwrap();
+
+    let matched = find_route(&router, "GET", "/checkout/payment", true).unwrap();
+    let attributes = matched.attributes.unwrap();
+    assert_eq!(attributes["feature_flag"], "new_checkout");
+    assert_eq!(attributes["ab_weights"]["b"], 20);
+    assert_eq!(attributes.len(), 3);
+
+    let all = find_all_routes(&router, "GET", "/checkout/payment", false);
+    let fallback = all.iter().find(|m| m.data == "checkout_fallback").unwrap();
+    assert!(fallback.attributes.is_none());
+}
+
+#[test]
+fn test_later_attrs_replace_earlier_keys() {
+    let router = Router::new();
+    add_route_with_attrs(
+        &router,
+        "GET",
+        "/home",
+        "home",
+        &[("tier", json!("free")), ("tier", json!("pro"))],
+    )
+    .unwrap();
+    let matched = find_route(&router, "GET", "/home", false).unwrap();
+    assert_eq!(matched.attributes.unwrap()["tier"], "pro");
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "proptest")]
+
+use proptest::prelude::*;
+use rou3::proptest_strategies::{method, route_with_path};
+use rou3::{Router, add_route, find_all_routes, find_route, remove_route};
+use std::collections::HashSet;
+
+/// Reduces a pattern to the trie node it lands on, ignoring parameter names and
+/// optionality, so generated route sets never register two patterns on one node.
+fn pattern_shape(pattern: &str) -> String {
+    pattern
+        .split('/')
+        .map(|seg| {
+            if seg.starts_with("**") {
+                "**"
+            } else if seg.starts_with(':') {
+                ":"
+            } else {
+                seg
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn distinct_routes() -> impl Strategy<Value = Vec<(String, String, String)>> {
+    prop::collection::vec((method(), route_with_path()), 1..12).prop_map(|routes| {
+        let mut seen = HashSet::new();
+        routes
+            .into_iter()
+            .filter(|(m, (pattern, _))| seen.insert((m.clone(), pattern_shape(pattern))))
+            .map(|(m, (pattern, path))| (m, pattern, path))
+            .collect()
+    })
+}
+
+proptest! {
+    #[test]
+    fn prop_added_route_is_findable(m in method(), (pattern, path) in route_with_path()) {
+        let router = Router::new();
+        add_route(&router, &m, &pattern, pattern.clone()).unwrap();
+
+        let matched = find_route(&router, &m, &path, true);
+        prop_assert!(matched.is_ok(), "{} {} not found via {}", m, pattern, path);
+        prop_assert_eq!(matched.unwrap().data, pattern);
+    }
+
+    #[test]
+    fn prop_remove_then_add_is_identity(routes in distinct_routes(), pick in any::<prop::sample::Index>()) {
+        let router = Router::new();
+        for (i, (m, pattern, _)) in routes.iter().enumerate() {
+            add_route(&router, m, pattern, i).unwrap();
+        }
+        let lookup_all = |router: &Router<usize>| {
+            routes
+                .iter()
+                .map(|(m, _, path)| find_route(router, m, path, true))
+                .collect::<Vec<_>>()
+        };
+        let before = lookup_all(&router);
+
+        let idx = pick.index(routes.len());
+        let (m, pattern, _) = &routes[idx];
+        prop_assert!(remove_route(&router, m, pattern).unwrap());
+        add_route(&router, m, pattern, idx).unwrap();
+
+        prop_assert_eq!(before, lookup_all(&router));
+    }
+
+    #[test]
+    fn prop_find_all_contains_find_route(routes in distinct_routes()) {
+        let router = Router::new();
+        for (i, (m, pattern, _)) in routes.iter().enumerate() {
+            add_route(&router, m, pattern, i).unwrap();
+        }
+        for (m, _, path) in &routes {
+            if let Ok(matched) = find_route(&router, m, path, true) {
+                let all = find_all_routes(&router, m, path, true);
+                prop_assert!(
+                    all.iter().any(|r| r.data == matched.data),
+                    "find_all_routes({}, {}) = {:?} is missing {:?}", m, path, all, matched
+                );
+            }
+        }
+    }
+}
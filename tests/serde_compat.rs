@@ -0,0 +1,49 @@
+#![cfg(feature = "serde_json")]
+
+use rou3::{Router, add_route, find_route, from_json_value, to_json_value};
+use serde_json::json;
+
+#[test]
+fn test_matched_route_json_round_trip() {
+    let router = Router::new();
+    add_route(&router, "GET", "/health", "health".to_string()).unwrap();
+    add_route(&router, "GET", "/users/:id", "get_user".to_string()).unwrap();
+    add_route(&router, "GET", "/files/**:path", "files".to_string()).unwrap();
+
+    for (path, expected) in [
+        (
+            "/health",
+            json!({ "data": "health", "params": {}, "route_pattern": "/health", "matched_path": "health" }),
+        ),
+        (
+            "/users/42",
+            json!({ "data": "get_user", "params": { "id": "42" }, "route_pattern": "/users/:id", "matched_path": "users/42" }),
+        ),
+        (
+            "/files/docs/a.txt",
+            json!({ "data": "files", "params": { "path": "docs/a.txt" }, "route_pattern": "/files/**:path", "matched_path": "files/docs/a.txt" }),
+        ),
+    ] {
+        let matched = find_route(&router, "GET", path, true).unwrap();
+        let value = to_json_value(&matched);
+        assert_eq!(value, expected, "{path}");
+        assert_eq!(
+            from_json_value::<String>(&value).unwrap(),
+            matched,
+            "{path}"
+        );
+    }
+}
+
+#[test]
+fn test_from_json_value_errors() {
+    let missing_path = json!({ "data": "x" });
+    assert!(from_json_value::<String>(&missing_path).is_err());
+    let wrong_data_type = json!({ "data": 1, "matched_path": "x" });
+    assert!(from_json_value::<String>(&wrong_data_type).is_err());
+
+    let minimal = from_json_value::<u32>(&json!({ "data": 7, "matched_path": "a" })).unwrap();
+    assert_eq!(minimal.data, 7);
+    assert_eq!(minimal.route_pattern, "");
+    assert_eq!(minimal.params, None);
+}
@@ -0,0 +1,219 @@
+#![cfg(feature = "testing")]
+
+use rou3::testing::{
+    MockRouter, TestRouterBuilder, assert_all_routes_findable, coverage_report,
+    generate_test_paths, mock_route, standard_api_router,
+};
+use rou3::{
+    Router, RouterError, RouterInterface, add_route, assert_route_matches, assert_route_not_found,
+    disable_route, find_route, list_routes,
+};
+
+fn fixture() -> Router<&'static str> {
+    let router = Router::new();
+    add_route(&router, "GET", "/users", "list_users").unwrap();
+    add_route(&router, "GET", "/users/:id", "get_user").unwrap();
+    add_route(&router, "GET", "/orgs/:org/repos/:repo", "get_repo").unwrap();
+    add_route(&router, "GET", "/files/**:path", "files").unwrap();
+    router
+}
+
+#[test]
+fn test_assert_route_matches() {
+    let router = fixture();
+    assert_route_matches!(&router, "GET", "/users", "list_users");
+    assert_route_matches!(&router, "GET", "/users", "list_users", params: {});
+    assert_route_matches!(&router, "GET", "/users/7", "get_user", params: { "id" => "7" });
+    assert_route_matches!(
+        &router,
+        "GET",
+        "/orgs/rust-lang/repos/rust",
+        "get_repo",
+        params: { "org" => "rust-lang", "repo" => "rust" },
+    );
+    assert_route_matches!(&router, "GET", "/files/a/b.txt", "files", params: { "path" => "a/b.txt" });
+}
+
+#[test]
+fn test_assert_route_not_found() {
+    let router = fixture();
+    assert_route_not_found!(&router, "POST", "/users");
+    assert_route_not_found!(&router, "GET", "/posts/1");
+}
+
+#[test]
+#[should_panic(expected = "matched the wrong route")]
+fn test_assert_route_matches_wrong_data() {
+    assert_route_matches!(&fixture(), "GET", "/users/7", "list_users");
+}
+
+#[test]
+#[should_panic(expected = "unexpected parameters")]
+fn test_assert_route_matches_missing_expected_param() {
+    assert_route_matches!(&fixture(), "GET", "/orgs/a/repos/b", "get_repo", params: { "org" => "a" });
+}
+
+#[test]
+#[should_panic(expected = "to match a route")]
+fn test_assert_route_matches_not_found() {
+    assert_route_matches!(&fixture(), "GET", "/nope", "list_users");
+}
+
+#[test]
+#[should_panic(expected = "to find no route")]
+fn test_assert_route_not_found_when_matched() {
+    assert_route_not_found!(&fixture(), "GET", "/users");
+}
+
+#[test]
+fn test_test_router_builder() {
+    let router = TestRouterBuilder::new()
+        .get("/items", "list_items")
+        .post("/items", "create_item")
+        .put("/items/:id", "update_item")
+        .delete("/items/:id", "delete_item")
+        .build();
+    assert_route_matches!(&router, "GET", "/items", "list_items");
+    assert_route_matches!(&router, "POST", "/items", "create_item");
+    assert_route_matches!(&router, "PUT", "/items/3", "update_item", params: { "id" => "3" });
+    assert_route_matches!(&router, "DELETE", "/items/3", "delete_item");
+    assert_route_not_found!(&router, "PATCH", "/items/3");
+}
+
+#[test]
+#[should_panic(expected = "failed to add test route")]
+fn test_test_router_builder_rejects_invalid_pattern() {
+    TestRouterBuilder::new().get("/files/**:path/more", "bad");
+}
+
+#[test]
+fn test_standard_api_router() {
+    let router = standard_api_router();
+    assert_route_matches!(&router, "GET", "/", "index");
+    assert_route_matches!(&router, "DELETE", "/users/1", "delete_user");
+    assert_route_matches!(&router, "GET", "/users/1/posts/2", "get_user_post", params: { "id" => "1", "post_id" => "2" });
+    assert_route_matches!(&router, "GET", "/search", "search");
+    assert_route_matches!(&router, "GET", "/static/css/app.css", "static_files", params: { "path" => "css/app.css" });
+    assert_all_routes_findable(&router);
+}
+
+#[test]
+#[should_panic(expected = "is not findable")]
+fn test_assert_all_routes_findable_reports_disabled_route() {
+    let router = standard_api_router();
+    disable_route(&router, "GET", "/health").unwrap();
+    assert_all_routes_findable(&router);
+}
+
+#[test]
+fn test_coverage_report() {
+    let router = standard_api_router();
+    let report = coverage_report(
+        &router,
+        &[
+            ("GET", "/"),
+            ("GET", "/users"),
+            ("POST", "/users"),
+            ("DELETE", "/users/9"),
+            ("GET", "/static/js/app.js"),
+            ("GET", "/missing"),
+        ],
+    );
+    assert_eq!(
+        report.covered,
+        vec!["/", "/static/**:path", "/users", "/users/:id"]
+    );
+    assert_eq!(
+        report.uncovered,
+        vec!["/health", "/search/:query?", "/users/:id/posts/:post_id"]
+    );
+    assert!((report.coverage_percent - 400.0 / 7.0).abs() < 1e-9);
+
+    let empty = coverage_report(&Router::<&str>::new(), &[("GET", "/")]);
+    assert!(empty.covered.is_empty() && empty.uncovered.is_empty());
+    assert_eq!(empty.coverage_percent, 100.0);
+}
+
+#[test]
+fn test_mock_router_expectations_in_order() {
+    let mut mock = MockRouter::new();
+    mock.on_find("GET", "/users/me", Ok(mock_route("me", "/users/me")))
+        .on_find(
+            "GET",
+            "/users/:id",
+            Ok(mock_route("get_user", "/users/:id")),
+        )
+        .on_find(
+            "DELETE",
+            "/users/:id",
+            Err(RouterError::InvalidPath("read only".to_string())),
+        );
+
+    fn handle(router: &dyn RouterInterface<&'static str>, method: &str, path: &str) -> String {
+        match router.find(method, path, true) {
+            Ok(matched) => matched.data.to_string(),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    assert_eq!(handle(&mock, "GET", "/users/me"), "me");
+    assert_eq!(handle(&mock, "GET", "/users/123"), "get_user");
+    assert_eq!(
+        handle(&mock, "DELETE", "/users/123"),
+        "invalid path pattern: read only"
+    );
+    assert!(matches!(
+        mock.find("POST", "/users", false),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+    assert!(mock.add("GET", "/ignored", "ignored").is_ok());
+    assert!(!mock.remove("GET", "/users/:id").unwrap());
+
+    mock.assert_find_called_with("GET", "/users/123");
+    mock.assert_find_called_with("POST", "/users");
+    assert_eq!(mock.find_calls().len(), 4);
+}
+
+#[test]
+#[should_panic(expected = "expected find to be called with GET /users/1")]
+fn test_mock_router_assert_find_called_with_panics() {
+    let mut mock = MockRouter::new();
+    mock.on_find("GET", "/users/:id", Ok(mock_route(1, "/users/:id")));
+    let _ = mock.find("GET", "/users/2", false);
+    mock.assert_find_called_with("GET", "/users/1");
+}
+
+#[test]
+fn test_generate_test_paths_are_findable() {
+    let router = standard_api_router();
+    add_route(&router, "", "/any/*/:tags+", "any").unwrap();
+
+    let paths = generate_test_paths(&router);
+    for (method, path) in &paths {
+        assert!(
+            find_route(&router, method, path, false).is_ok(),
+            "{method} {path} is not findable"
+        );
+    }
+    let contains =
+        |method: &str, path: &str| paths.contains(&(method.to_string(), path.to_string()));
+    assert!(contains("GET", "/health"));
+    assert!(contains("DELETE", "/users/id_test_value"));
+    assert!(contains(
+        "GET",
+        "/users/id_test_value/posts/post_id_test_value"
+    ));
+    assert!(contains("GET", "/search/query_test_value"));
+    assert!(contains("GET", "/static/wildcard/test/value"));
+    assert!(contains("", "/any/param_test_value/tags_test_value"));
+    assert_eq!(paths.len(), list_routes(&router).len());
+
+    let report = coverage_report(
+        &router,
+        &paths
+            .iter()
+            .map(|(method, path)| (method.as_str(), path.as_str()))
+            .collect::<Vec<_>>(),
+    );
+    assert!(report.uncovered.is_empty(), "{:?}", report.uncovered);
+}
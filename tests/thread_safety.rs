@@ -0,0 +1,38 @@
+//! Compile-time checks that routers can be shared across threads. These tests
+//! fail to build, rather than fail at runtime, if a change makes `Router<T>`
+//! lose its `Send` or `Sync` implementation.
+
+use rou3::{MatchedRoute, Router, RouterError, VHostRouter};
+use std::{any::Any, sync::Arc};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+fn router_is_send<T: Send>() {}
+
+fn router_is_sync<T: Sync>() {}
+
+#[test]
+fn test_router_is_send_and_sync() {
+    assert_send_sync::<Router<String>>();
+    assert_send_sync::<Router<&'static str>>();
+    assert_send_sync::<Router<usize>>();
+    assert_send_sync::<Router<Arc<str>>>();
+    assert_send_sync::<Router<Box<dyn Any + Send + Sync>>>();
+    assert_send_sync::<Router<fn() -> u16>>();
+}
+
+#[test]
+fn test_router_send_and_sync_separately() {
+    router_is_send::<Router<String>>();
+    router_is_sync::<Router<String>>();
+    router_is_send::<Router<Box<dyn Any + Send + Sync>>>();
+    router_is_sync::<Router<Box<dyn Any + Send + Sync>>>();
+}
+
+#[test]
+fn test_related_types_are_send_and_sync() {
+    assert_send_sync::<VHostRouter<String>>();
+    assert_send_sync::<MatchedRoute<String>>();
+    assert_send_sync::<RouterError>();
+    assert_send_sync::<Arc<Router<String>>>();
+}
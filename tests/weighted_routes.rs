@@ -0,0 +1,73 @@
+#![cfg(feature = "rand")]
+
+use rand::{SeedableRng, rngs::StdRng};
+use rou3::{Router, RouterError, add_route, add_weighted_route, find_route, find_route_weighted};
+
+#[test]
+fn test_find_route_weighted_splits_by_weight() {
+    let router = Router::new();
+    add_weighted_route(&router, "GET", "/users/:id", "stable", 90).unwrap();
+    add_weighted_route(&router, "GET", "/users/:id", "canary", 10).unwrap();
+    add_weighted_route(&router, "GET", "/users/:id", "disabled", 0).unwrap();
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut canary = 0;
+    for _ in 0..10_000 {
+        let matched = find_route_weighted(&router, "GET", "/users/7", true, &mut rng).unwrap();
+        assert_ne!(matched.data, "disabled");
+        assert_eq!(
+            matched.params.unwrap().get("id").map(String::as_str),
+            Some("7")
+        );
+        if matched.data == "canary" {
+            canary += 1;
+        }
+    }
+    assert!(
+        (800..1_200).contains(&canary),
+        "canary served {canary} of 10000"
+    );
+
+    // Unweighted lookups always use the first variant.
+    assert_eq!(
+        find_route(&router, "GET", "/users/7", false).unwrap().data,
+        "stable"
+    );
+}
+
+#[test]
+fn test_find_route_weighted_single_and_static_routes() {
+    let router = Router::new();
+    add_route(&router, "GET", "/health", "health").unwrap();
+    add_weighted_route(&router, "GET", "/static", "a", 1).unwrap();
+    add_weighted_route(&router, "GET", "/static", "b", 3).unwrap();
+
+    let mut rng = StdRng::seed_from_u64(1);
+    assert_eq!(
+        find_route_weighted(&router, "GET", "/health", false, &mut rng)
+            .unwrap()
+            .data,
+        "health"
+    );
+    let mut seen = Vec::new();
+    for _ in 0..100 {
+        seen.push(
+            find_route_weighted(&router, "GET", "/static", false, &mut rng)
+                .unwrap()
+                .data,
+        );
+    }
+    assert!(seen.contains(&"a") && seen.contains(&"b"));
+    // HEAD falls back to the GET variants.
+    let head = find_route_weighted(&router, "HEAD", "/static", false, &mut rng).unwrap();
+    assert!(head.is_head_derived);
+
+    assert!(matches!(
+        find_route_weighted(&router, "GET", "/missing", false, &mut rng),
+        Err(RouterError::RouteNotFound { .. })
+    ));
+    assert!(matches!(
+        find_route_weighted(&router, "POST", "/static", false, &mut rng),
+        Err(RouterError::MethodNotAllowed { .. })
+    ));
+}